@@ -0,0 +1,712 @@
+use super::{Builtins, define_method, iterator_result, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, IterationKind, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+/// Array.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "at", array_at);
+    define_method(&prototype, "entries", array_entries);
+    define_method(&prototype, "fill", array_fill);
+    define_method(&prototype, "filter", array_filter);
+    define_method(&prototype, "flat", array_flat);
+    define_method(&prototype, "flatMap", array_flat_map);
+    define_method(&prototype, "join", array_join);
+    define_method(&prototype, "keys", array_keys);
+    define_method(&prototype, "map", array_map);
+    define_method(&prototype, "pop", array_pop);
+    define_method(&prototype, "push", array_push);
+    define_method(&prototype, "reverse", array_reverse);
+    define_method(&prototype, "shift", array_shift);
+    define_method(&prototype, "sort", array_sort);
+    define_method(&prototype, "splice", array_splice);
+    define_method(&prototype, "toString", array_to_string);
+    define_method(&prototype, "unshift", array_unshift);
+    define_method(&prototype, "values", array_values);
+    prototype
+}
+
+/// 配列イテレータのプロトタイプを作成
+pub(super) fn create_iterator_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "next", array_iterator_next);
+    prototype
+}
+
+impl Builtins {
+    /// target を先頭から走査する配列イテレータを作成
+    pub fn create_array_iterator(&self, target: JSValue, kind: IterationKind) -> JSValue {
+        let mut iterator = JSObject::with_prototype(Some(self.array_iterator_prototype.clone()));
+        iterator.set_internal_slot(InternalSlot::ArrayIterator {
+            target,
+            kind,
+            index: 0,
+        });
+        JSValue::Object(Rc::new(RefCell::new(iterator)))
+    }
+}
+
+/// 配列イテレータを1つ進め、次の値を返す（走査し終えていれば None）
+///
+/// length は進めるたびに読み直すため、走査中に追加した要素も返す。
+/// 一度走査し終えたイテレータは以後も None を返す。
+pub fn array_iterator_step(vm: &mut VM, iterator: &RefCell<JSObject>) -> JSResult<Option<JSValue>> {
+    let (target, kind, index) = match iterator.borrow().internal_slot() {
+        Some(InternalSlot::ArrayIterator {
+            target,
+            kind,
+            index,
+        }) => (target.clone(), *kind, *index),
+        _ => {
+            return Err(JSError::TypeError(
+                "next method called on incompatible receiver".to_string(),
+            ));
+        }
+    };
+    let length = match target {
+        JSValue::Undefined => 0,
+        _ => vm.get_property(&target, "length")?.to_number() as usize,
+    };
+    if index >= length {
+        iterator
+            .borrow_mut()
+            .set_internal_slot(InternalSlot::ArrayIterator {
+                target: JSValue::Undefined,
+                kind,
+                index,
+            });
+        return Ok(None);
+    }
+
+    let value = match kind {
+        IterationKind::Keys => JSValue::Number(index as f64),
+        IterationKind::Values => vm.get_property(&target, &index.to_string())?,
+        IterationKind::Entries => {
+            let element = vm.get_property(&target, &index.to_string())?;
+            vm.create_array(vec![JSValue::Number(index as f64), element])
+        }
+    };
+    iterator
+        .borrow_mut()
+        .set_internal_slot(InternalSlot::ArrayIterator {
+            target,
+            kind,
+            index: index + 1,
+        });
+    Ok(Some(value))
+}
+
+/// 配列イテレータの next(): { value, done } を返す
+fn array_iterator_next(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let JSValue::Object(iterator) = this else {
+        return Err(JSError::TypeError(
+            "next method called on incompatible receiver".to_string(),
+        ));
+    };
+    let step = array_iterator_step(vm, iterator)?;
+    Ok(iterator_result(vm, step))
+}
+
+/// Array.prototype.keys(): インデックスを返すイテレータ
+fn array_keys(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    this_object(this, "keys")?;
+    Ok(vm
+        .builtins()
+        .create_array_iterator(this.clone(), IterationKind::Keys))
+}
+
+/// Array.prototype.values(): 要素を返すイテレータ
+fn array_values(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    this_object(this, "values")?;
+    Ok(vm
+        .builtins()
+        .create_array_iterator(this.clone(), IterationKind::Values))
+}
+
+/// Array.prototype.entries(): [インデックス, 要素] を返すイテレータ
+fn array_entries(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    this_object(this, "entries")?;
+    Ok(vm
+        .builtins()
+        .create_array_iterator(this.clone(), IterationKind::Entries))
+}
+
+/// Array コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let array = native_function("Array", array_construct, Some(array_construct));
+    array.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.array_prototype.clone())),
+    );
+    JSValue::NativeFunction(array)
+}
+
+/// Array(...) / new Array(...)
+///
+/// 数値1つの場合はその長さの空の配列、それ以外は引数を要素とする配列を作成する。
+fn array_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let [JSValue::Number(len)] = args else {
+        return Ok(vm.create_array(args.to_vec()));
+    };
+
+    if *len < 0.0 || len.fract() != 0.0 || *len > u32::MAX as f64 {
+        return Err(JSError::RangeError("Invalid array length".to_string()));
+    }
+
+    // 要素を持たない（穴だけの）配列
+    let array = vm.create_array(Vec::new());
+    if let JSValue::Object(obj) = &array {
+        set_length(&mut obj.borrow_mut(), *len as usize);
+    }
+    Ok(array)
+}
+
+/// this を配列（配列風オブジェクト）として取得
+fn this_object(this: &JSValue, method: &str) -> JSResult<Rc<RefCell<JSObject>>> {
+    match this {
+        JSValue::Object(obj) => Ok(obj.clone()),
+        _ => Err(JSError::TypeError(format!(
+            "Array.prototype.{} called on non-object",
+            method
+        ))),
+    }
+}
+
+/// length プロパティを 0 以上の整数として取得
+fn length_of(object: &JSObject) -> usize {
+    let length = object.get("length").to_number();
+    if length.is_finite() && length > 0.0 {
+        length as usize
+    } else {
+        0
+    }
+}
+
+/// length プロパティを設定（列挙不可）
+fn set_length(object: &mut JSObject, length: usize) {
+    object.define_property(
+        "length".to_string(),
+        Property::non_enumerable(JSValue::Number(length as f64)),
+    );
+}
+
+/// 相対インデックス引数を 0..=length に丸める（負の値は末尾から数える）
+fn relative_index(value: Option<&JSValue>, length: usize, default: usize) -> usize {
+    let relative = match value {
+        None | Some(JSValue::Undefined) => return default,
+        Some(value) => value.to_integer_or_infinity(),
+    };
+    if relative < 0.0 {
+        (length as f64 + relative).max(0.0) as usize
+    } else {
+        relative.min(length as f64) as usize
+    }
+}
+
+/// Array.prototype.at(index)
+///
+/// 負の index は末尾から数える。範囲外なら undefined。
+fn array_at(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "at")?;
+    let obj = obj.borrow();
+    let length = length_of(&obj) as f64;
+    let relative = args
+        .first()
+        .unwrap_or(&JSValue::Undefined)
+        .to_integer_or_infinity();
+    let index = if relative < 0.0 {
+        length + relative
+    } else {
+        relative
+    };
+    if index < 0.0 || index >= length {
+        return Ok(JSValue::Undefined);
+    }
+    Ok(obj.get(&(index as usize).to_string()))
+}
+
+/// Array.prototype.join(separator)
+///
+/// 各要素を文字列に変換して separator（省略時は ","）で連結する。
+/// undefined・null・穴は空文字列とする。
+fn array_join(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "join")?;
+    let separator = match args.first() {
+        None | Some(JSValue::Undefined) => ",".to_string(),
+        Some(value) => vm.to_string(value.clone())?,
+    };
+    let mut joining = Vec::new();
+    Ok(JSValue::String(join_elements(
+        vm,
+        &obj,
+        &separator,
+        &mut joining,
+    )?))
+}
+
+/// Array.prototype.toString(): join() と同じ
+fn array_to_string(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    array_join(vm, this, &[])
+}
+
+/// 配列の要素を連結（joining は連結中の配列で、循環参照は空文字列にする）
+///
+/// 入れ子の配列は toString を呼ばずに同じ区切りの "," で直接連結する。
+fn join_elements(
+    vm: &mut VM,
+    obj: &Rc<RefCell<JSObject>>,
+    separator: &str,
+    joining: &mut Vec<*const RefCell<JSObject>>,
+) -> JSResult<String> {
+    joining.push(Rc::as_ptr(obj));
+    let length = length_of(&obj.borrow());
+    let mut parts = Vec::with_capacity(length);
+    for i in 0..length {
+        let part = match element_at(obj, i) {
+            None | Some(JSValue::Undefined | JSValue::Null) => String::new(),
+            Some(JSValue::Object(inner)) if inner.borrow().is_array() => {
+                if joining.contains(&Rc::as_ptr(&inner)) {
+                    String::new()
+                } else {
+                    join_elements(vm, &inner, ",", joining)?
+                }
+            }
+            Some(value) => vm.to_string(value)?,
+        };
+        parts.push(part);
+    }
+    joining.pop();
+    Ok(parts.join(separator))
+}
+
+/// Array.prototype.fill(value, start, end)
+fn array_fill(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "fill")?;
+    let value = args.first().cloned().unwrap_or(JSValue::Undefined);
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    let start = relative_index(args.get(1), length, 0);
+    let end = relative_index(args.get(2), length, length);
+    for i in start..end {
+        obj.set(i.to_string(), value.clone());
+    }
+    Ok(this.clone())
+}
+
+/// コールバック引数を取得（呼び出し可能でなければ TypeError）
+fn callback_arg(args: &[JSValue]) -> JSResult<JSValue> {
+    match args.first() {
+        Some(f) if f.is_callable() => Ok(f.clone()),
+        other => Err(JSError::TypeError(format!(
+            "{} is not a function",
+            other.unwrap_or(&JSValue::Undefined)
+        ))),
+    }
+}
+
+/// 穴でなければ index 番目の要素を取得
+///
+/// コールバックが配列を変更できるよう、要素ごとに借用する。
+fn element_at(object: &RefCell<JSObject>, index: usize) -> Option<JSValue> {
+    let object = object.borrow();
+    let key = index.to_string();
+    object.has_property(&key).then(|| object.get(&key))
+}
+
+/// Array.prototype.filter(callback, thisArg)
+///
+/// callback(value, index, array) が真になる要素からなる新しい配列を返す。穴は飛ばす。
+fn array_filter(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "filter")?;
+    let callback = callback_arg(args)?;
+    let this_arg = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+    let length = length_of(&obj.borrow());
+    let mut selected = Vec::new();
+    for i in 0..length {
+        let Some(value) = element_at(&obj, i) else {
+            continue;
+        };
+        let args = vec![value.clone(), JSValue::Number(i as f64), this.clone()];
+        if vm
+            .call_value(&callback, this_arg.clone(), args)?
+            .to_boolean()
+        {
+            selected.push(value);
+        }
+    }
+    Ok(vm.create_array(selected))
+}
+
+/// Array.prototype.map(callback, thisArg)
+///
+/// 各要素に callback(value, index, array) を適用した新しい配列を返す。穴は穴のまま残す。
+fn array_map(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "map")?;
+    let callback = callback_arg(args)?;
+    let this_arg = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+    let length = length_of(&obj.borrow());
+    let mut mapped = Vec::new();
+    for i in 0..length {
+        let Some(value) = element_at(&obj, i) else {
+            continue;
+        };
+        let args = vec![value, JSValue::Number(i as f64), this.clone()];
+        mapped.push((i, vm.call_value(&callback, this_arg.clone(), args)?));
+    }
+
+    let array = vm.create_array(Vec::new());
+    if let JSValue::Object(obj) = &array {
+        let mut obj = obj.borrow_mut();
+        for (i, value) in mapped {
+            obj.set(i.to_string(), value);
+        }
+        set_length(&mut obj, length);
+    }
+    Ok(array)
+}
+
+/// Array.prototype.flatMap(callback, thisArg)
+///
+/// 各要素に callback(value, index, array) を適用し、結果が配列なら1段展開した新しい配列を返す。
+fn array_flat_map(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "flatMap")?;
+    let callback = callback_arg(args)?;
+    let this_arg = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+    let length = length_of(&obj.borrow());
+    let mut elements = Vec::new();
+    for i in 0..length {
+        let Some(value) = element_at(&obj, i) else {
+            continue;
+        };
+        let args = vec![value, JSValue::Number(i as f64), this.clone()];
+        match vm.call_value(&callback, this_arg.clone(), args)? {
+            JSValue::Object(inner) if inner.borrow().is_array() => {
+                flatten_into(&inner, 0.0, &mut elements, &mut Vec::new())?;
+            }
+            value => elements.push(value),
+        }
+    }
+    Ok(vm.create_array(elements))
+}
+
+/// Array.prototype.flat(depth)
+///
+/// depth（デフォルト 1）段までネストした配列を展開した新しい配列を返す。穴は取り除く。
+/// 展開中の配列を自身の要素として再び展開しようとした場合は RangeError。
+fn array_flat(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "flat")?;
+    let depth = match args.first() {
+        None | Some(JSValue::Undefined) => 1.0,
+        Some(value) => value.to_integer_or_infinity(),
+    };
+    let mut elements = Vec::new();
+    flatten_into(&obj, depth, &mut elements, &mut Vec::new())?;
+    Ok(vm.create_array(elements))
+}
+
+/// 配列の要素を depth 段まで展開して out に追加
+///
+/// flattening は展開中の配列で、循環参照を検出する。
+fn flatten_into(
+    obj: &Rc<RefCell<JSObject>>,
+    depth: f64,
+    out: &mut Vec<JSValue>,
+    flattening: &mut Vec<*const RefCell<JSObject>>,
+) -> JSResult<()> {
+    flattening.push(Rc::as_ptr(obj));
+    let length = length_of(&obj.borrow());
+    for i in 0..length {
+        let Some(value) = element_at(obj, i) else {
+            continue;
+        };
+        match value {
+            JSValue::Object(inner) if depth >= 1.0 && inner.borrow().is_array() => {
+                if flattening.contains(&Rc::as_ptr(&inner)) {
+                    return Err(JSError::RangeError(
+                        "Cannot flatten a cyclic array".to_string(),
+                    ));
+                }
+                flatten_into(&inner, depth - 1.0, out, flattening)?;
+            }
+            value => out.push(value),
+        }
+    }
+    flattening.pop();
+    Ok(())
+}
+
+/// from の要素を to に移す（from が穴なら to も穴にする）
+fn move_element(object: &mut JSObject, from: usize, to: usize) {
+    let from = from.to_string();
+    if object.has_property(&from) {
+        let value = object.get(&from);
+        object.set(to.to_string(), value);
+    } else {
+        object.delete(&to.to_string());
+    }
+}
+
+/// Array.prototype.push(...items): 末尾に追加して新しい length を返す
+fn array_push(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "push")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    for (i, value) in args.iter().enumerate() {
+        obj.set((length + i).to_string(), value.clone());
+    }
+    set_length(&mut obj, length + args.len());
+    Ok(JSValue::Number((length + args.len()) as f64))
+}
+
+/// Array.prototype.pop(): 末尾の要素を取り除いて返す（空なら undefined）
+fn array_pop(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "pop")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    if length == 0 {
+        set_length(&mut obj, 0);
+        return Ok(JSValue::Undefined);
+    }
+    let key = (length - 1).to_string();
+    let value = obj.get(&key);
+    obj.delete(&key);
+    set_length(&mut obj, length - 1);
+    Ok(value)
+}
+
+/// Array.prototype.shift(): 先頭の要素を取り除いて返す（空なら undefined）
+fn array_shift(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "shift")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    if length == 0 {
+        set_length(&mut obj, 0);
+        return Ok(JSValue::Undefined);
+    }
+    let first = obj.get("0");
+    for i in 1..length {
+        move_element(&mut obj, i, i - 1);
+    }
+    obj.delete(&(length - 1).to_string());
+    set_length(&mut obj, length - 1);
+    Ok(first)
+}
+
+/// Array.prototype.unshift(...items): 先頭に追加して新しい length を返す
+fn array_unshift(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "unshift")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    // 後ろの要素から順にずらす
+    for i in (0..length).rev() {
+        move_element(&mut obj, i, i + args.len());
+    }
+    for (i, value) in args.iter().enumerate() {
+        obj.set(i.to_string(), value.clone());
+    }
+    set_length(&mut obj, length + args.len());
+    Ok(JSValue::Number((length + args.len()) as f64))
+}
+
+/// Array.prototype.splice(start, deleteCount, ...items)
+///
+/// start から deleteCount 個の要素を取り除いて items を挿入し、取り除いた要素の配列を返す。
+/// deleteCount を省略すると start 以降を全て取り除く。
+fn array_splice(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "splice")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    let start = relative_index(args.first(), length, 0);
+    let delete_count = match args.len() {
+        0 => 0,
+        1 => length - start,
+        _ => args[1]
+            .to_integer_or_infinity()
+            .clamp(0.0, (length - start) as f64) as usize,
+    };
+    let items = args.get(2..).unwrap_or_default();
+
+    let removed: Vec<_> = (start..start + delete_count)
+        .map(|i| {
+            let key = i.to_string();
+            obj.has_property(&key).then(|| obj.get(&key))
+        })
+        .collect();
+
+    // 残りの要素を挿入後の位置にずらす（重ならないよう、詰めるときは前から、広げるときは後ろから）
+    let tail = start + delete_count..length;
+    let moved = |i: usize| i - delete_count + items.len();
+    if items.len() < delete_count {
+        for i in tail {
+            move_element(&mut obj, i, moved(i));
+        }
+        for i in (moved(length)..length).rev() {
+            obj.delete(&i.to_string());
+        }
+    } else if items.len() > delete_count {
+        for i in tail.rev() {
+            move_element(&mut obj, i, moved(i));
+        }
+    }
+    for (i, value) in items.iter().enumerate() {
+        obj.set((start + i).to_string(), value.clone());
+    }
+    set_length(&mut obj, moved(length));
+
+    // 取り除いた範囲の穴は結果の配列でも穴にする
+    let result = vm.create_array(
+        removed
+            .iter()
+            .map(|value| value.clone().unwrap_or(JSValue::Undefined))
+            .collect(),
+    );
+    if let JSValue::Object(array) = &result {
+        for (i, _) in removed
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| value.is_none())
+        {
+            array.borrow_mut().delete(&i.to_string());
+        }
+    }
+    Ok(result)
+}
+
+/// Array.prototype.reverse(): その場で逆順にして this を返す
+fn array_reverse(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let obj = this_object(this, "reverse")?;
+    let mut obj = obj.borrow_mut();
+    let length = length_of(&obj);
+    for lower in 0..length / 2 {
+        let (lower_key, upper_key) = (lower.to_string(), (length - 1 - lower).to_string());
+        let lower_value = obj.has_property(&lower_key).then(|| obj.get(&lower_key));
+        let upper_value = obj.has_property(&upper_key).then(|| obj.get(&upper_key));
+        // 穴は穴のまま入れ替える
+        for (key, value) in [(lower_key, upper_value), (upper_key, lower_value)] {
+            match value {
+                Some(value) => {
+                    obj.set(key, value);
+                }
+                None => {
+                    obj.delete(&key);
+                }
+            }
+        }
+    }
+    Ok(this.clone())
+}
+
+/// Array.prototype.sort(compareFn)
+///
+/// 安定ソートでその場で並べ替え、this を返す。
+/// compareFn を省略すると要素を文字列に変換して UTF-16 コード単位順に比較する。
+/// undefined は常に末尾に、穴はさらにその後ろに移動する。
+fn array_sort(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let compare = match args.first() {
+        None | Some(JSValue::Undefined) => None,
+        Some(f) if f.is_callable() => Some(f.clone()),
+        Some(other) => {
+            return Err(JSError::TypeError(format!(
+                "The comparison function must be either a function or undefined: {}",
+                other
+            )));
+        }
+    };
+    let obj = this_object(this, "sort")?;
+
+    let (mut values, mut undefined_count, length) = {
+        let obj = obj.borrow();
+        let length = length_of(&obj);
+        let mut values = Vec::new();
+        let mut undefined_count = 0;
+        for i in 0..length {
+            let key = i.to_string();
+            if !obj.has_property(&key) {
+                continue;
+            }
+            match obj.get(&key) {
+                JSValue::Undefined => undefined_count += 1,
+                value => values.push(value),
+            }
+        }
+        (values, undefined_count, length)
+    };
+
+    values = match &compare {
+        Some(f) => merge_sort(vm, values, &mut |vm, a, b| {
+            let order = vm
+                .call_value(f, JSValue::Undefined, vec![a.clone(), b.clone()])?
+                .to_number();
+            Ok(if order < 0.0 {
+                Ordering::Less
+            } else if order > 0.0 {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            })
+        })?,
+        None => {
+            // 各要素は比較の前に一度だけ文字列に変換する
+            let keyed = values
+                .into_iter()
+                .map(|value| {
+                    let key: Vec<u16> = vm.to_string(value.clone())?.encode_utf16().collect();
+                    Ok((key, value))
+                })
+                .collect::<JSResult<Vec<_>>>()?;
+            merge_sort(vm, keyed, &mut |_, a, b| Ok(a.0.cmp(&b.0)))?
+                .into_iter()
+                .map(|(_, value)| value)
+                .collect()
+        }
+    };
+
+    let mut obj_mut = obj.borrow_mut();
+    let mut index = 0;
+    for value in values {
+        obj_mut.set(index.to_string(), value);
+        index += 1;
+    }
+    while undefined_count > 0 {
+        obj_mut.set(index.to_string(), JSValue::Undefined);
+        index += 1;
+        undefined_count -= 1;
+    }
+    for hole in index..length {
+        obj_mut.delete(&hole.to_string());
+    }
+    Ok(this.clone())
+}
+
+/// 比較関数のエラーを伝播できる安定なマージソート
+fn merge_sort<T>(
+    vm: &mut VM,
+    mut values: Vec<T>,
+    compare: &mut impl FnMut(&mut VM, &T, &T) -> JSResult<Ordering>,
+) -> JSResult<Vec<T>> {
+    if values.len() <= 1 {
+        return Ok(values);
+    }
+    let right = values.split_off(values.len() / 2);
+    let left = merge_sort(vm, values, compare)?;
+    let right = merge_sort(vm, right, compare)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(a), Some(b)) = (left.peek(), right.peek()) {
+        // 等しい場合は左側を先に取り出して安定性を保つ
+        if compare(vm, a, b)? == Ordering::Greater {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
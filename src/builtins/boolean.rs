@@ -0,0 +1,68 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Boolean.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "toString", boolean_to_string);
+    define_method(&prototype, "valueOf", boolean_value_of);
+    prototype
+}
+
+/// Boolean コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let boolean = native_function("Boolean", boolean_call, Some(boolean_construct));
+    boolean.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.boolean_prototype.clone())),
+    );
+    JSValue::NativeFunction(boolean)
+}
+
+/// 引数を真偽値に変換（省略時は false）
+fn argument_boolean(args: &[JSValue]) -> bool {
+    args.first().is_some_and(JSValue::to_boolean)
+}
+
+/// Boolean(value): 真偽値への変換
+fn boolean_call(_vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Boolean(argument_boolean(args)))
+}
+
+/// new Boolean(value): 真偽値を保持するラッパーオブジェクト
+fn boolean_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let mut object = JSObject::with_prototype(Some(vm.builtins().boolean_prototype.clone()));
+    object.set_internal_slot(InternalSlot::Boolean(argument_boolean(args)));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+/// this の真偽値を取得（プリミティブまたは Boolean オブジェクト）
+fn this_boolean(this: &JSValue, method: &str) -> JSResult<bool> {
+    match this {
+        JSValue::Boolean(b) => return Ok(*b),
+        JSValue::Object(obj) => {
+            if let Some(InternalSlot::Boolean(b)) = obj.borrow().internal_slot() {
+                return Ok(*b);
+            }
+        }
+        _ => {}
+    }
+    Err(JSError::TypeError(format!(
+        "Boolean.prototype.{} requires that 'this' be a Boolean",
+        method
+    )))
+}
+
+/// Boolean.prototype.valueOf()
+fn boolean_value_of(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Boolean(this_boolean(this, "valueOf")?))
+}
+
+/// Boolean.prototype.toString()
+fn boolean_to_string(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::String(this_boolean(this, "toString")?.to_string()))
+}
@@ -0,0 +1,30 @@
+use super::{Builtins, define_method};
+use crate::error::JSResult;
+use crate::value::{JSObject, JSValue};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// console オブジェクトを作成
+pub(super) fn create_console(builtins: &Builtins) -> JSValue {
+    let console = Rc::new(RefCell::new(JSObject::with_prototype(Some(
+        builtins.object_prototype.clone(),
+    ))));
+    define_method(&console, "log", console_log);
+    JSValue::Object(console)
+}
+
+/// console.log(...args): 引数を空白区切りで1行に出力
+fn console_log(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let line = args
+        .iter()
+        .map(|arg| match arg {
+            // 直接渡された文字列はクォートせずに出力する
+            JSValue::String(s) => s.clone(),
+            other => other.inspect(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    vm.print(&line);
+    Ok(JSValue::Undefined)
+}
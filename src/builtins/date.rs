@@ -0,0 +1,57 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Date.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "getTime", date_get_time);
+    prototype
+}
+
+/// Date コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let date = native_function("Date", date_call, Some(date_construct));
+    define_method(&date.properties, "now", date_now);
+    date.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.date_prototype.clone())),
+    );
+    JSValue::NativeFunction(date)
+}
+
+/// Date(): 現時点では new なしの呼び出しは未対応
+fn date_call(_vm: &mut VM, _this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Err(JSError::TypeError(
+        "Date() without 'new' is not supported yet".to_string(),
+    ))
+}
+
+/// new Date() / new Date(ms)
+fn date_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let time = match args.first() {
+        Some(value) => value.to_number(),
+        None => vm.now(),
+    };
+    let mut object = JSObject::with_prototype(Some(vm.builtins().date_prototype.clone()));
+    object.set_internal_slot(InternalSlot::Date(time));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+/// Date.now(): Unix エポックからのミリ秒
+fn date_now(vm: &mut VM, _this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Number(vm.now()))
+}
+
+/// Date.prototype.getTime()
+fn date_get_time(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    if let JSValue::Object(obj) = this
+        && let Some(InternalSlot::Date(time)) = obj.borrow().internal_slot()
+    {
+        return Ok(JSValue::Number(*time));
+    }
+    Err(JSError::TypeError("this is not a Date object".to_string()))
+}
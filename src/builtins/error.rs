@@ -0,0 +1,171 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// 組み込みのエラーの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Error,
+    TypeError,
+    RangeError,
+    SyntaxError,
+    ReferenceError,
+}
+
+impl ErrorKind {
+    /// コンストラクタ名（name プロパティの値）
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Error => "Error",
+            ErrorKind::TypeError => "TypeError",
+            ErrorKind::RangeError => "RangeError",
+            ErrorKind::SyntaxError => "SyntaxError",
+            ErrorKind::ReferenceError => "ReferenceError",
+        }
+    }
+
+    /// 対応するプロトタイプを取得
+    fn prototype(self, builtins: &Builtins) -> &Rc<RefCell<JSObject>> {
+        match self {
+            ErrorKind::Error => &builtins.error_prototype,
+            ErrorKind::TypeError => &builtins.type_error_prototype,
+            ErrorKind::RangeError => &builtins.range_error_prototype,
+            ErrorKind::SyntaxError => &builtins.syntax_error_prototype,
+            ErrorKind::ReferenceError => &builtins.reference_error_prototype,
+        }
+    }
+}
+
+/// Error.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_name_and_message(&prototype, ErrorKind::Error);
+    define_method(&prototype, "toString", error_to_string);
+    prototype
+}
+
+/// TypeError.prototype 等、Error.prototype を継承するプロトタイプを作成
+pub(super) fn create_native_prototype(
+    error_prototype: &Rc<RefCell<JSObject>>,
+    kind: ErrorKind,
+) -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::with_prototype(Some(
+        error_prototype.clone(),
+    ))));
+    define_name_and_message(&prototype, kind);
+    prototype
+}
+
+/// プロトタイプに name と空の message を定義
+fn define_name_and_message(prototype: &Rc<RefCell<JSObject>>, kind: ErrorKind) {
+    let mut prototype = prototype.borrow_mut();
+    prototype.define_property(
+        "name".to_string(),
+        Property::non_enumerable(JSValue::String(kind.name().to_string())),
+    );
+    prototype.define_property(
+        "message".to_string(),
+        Property::non_enumerable(JSValue::String(String::new())),
+    );
+}
+
+/// エラーコンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins, kind: ErrorKind) -> JSValue {
+    let construct = match kind {
+        ErrorKind::Error => error_construct,
+        ErrorKind::TypeError => type_error_construct,
+        ErrorKind::RangeError => range_error_construct,
+        ErrorKind::SyntaxError => syntax_error_construct,
+        ErrorKind::ReferenceError => reference_error_construct,
+    };
+    // new なしの呼び出しも新しいエラーを作成する
+    let constructor = native_function(kind.name(), construct, Some(construct));
+    constructor.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(kind.prototype(builtins).clone())),
+    );
+    JSValue::NativeFunction(constructor)
+}
+
+impl Builtins {
+    /// エラーオブジェクトを作成
+    pub fn create_error(&self, kind: ErrorKind, message: &str) -> JSValue {
+        let mut object = JSObject::with_prototype(Some(kind.prototype(self).clone()));
+        object.set_internal_slot(InternalSlot::Error);
+        object.define_property(
+            "message".to_string(),
+            Property::non_enumerable(JSValue::String(message.to_string())),
+        );
+        JSValue::Object(Rc::new(RefCell::new(object)))
+    }
+}
+
+/// new XxxError(message) の共通処理
+fn construct_error(vm: &mut VM, kind: ErrorKind, args: &[JSValue]) -> JSResult<JSValue> {
+    let mut object = JSObject::with_prototype(Some(kind.prototype(vm.builtins()).clone()));
+    object.set_internal_slot(InternalSlot::Error);
+    // message を省略した場合はプロトタイプの空文字列を継承する
+    if let Some(message) = args.first()
+        && !matches!(message, JSValue::Undefined)
+    {
+        let message = vm.to_string(message.clone())?;
+        object.define_property(
+            "message".to_string(),
+            Property::non_enumerable(JSValue::String(message)),
+        );
+    }
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+fn error_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    construct_error(vm, ErrorKind::Error, args)
+}
+
+fn type_error_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    construct_error(vm, ErrorKind::TypeError, args)
+}
+
+fn range_error_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    construct_error(vm, ErrorKind::RangeError, args)
+}
+
+fn syntax_error_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    construct_error(vm, ErrorKind::SyntaxError, args)
+}
+
+fn reference_error_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    construct_error(vm, ErrorKind::ReferenceError, args)
+}
+
+/// Error.prototype.toString(): "name: message"
+///
+/// name と message のどちらかが空の場合はもう一方だけを返す。
+fn error_to_string(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let JSValue::Object(obj) = this else {
+        return Err(JSError::TypeError(
+            "Error.prototype.toString called on non-object".to_string(),
+        ));
+    };
+    let (name, message) = {
+        let obj = obj.borrow();
+        (obj.get("name"), obj.get("message"))
+    };
+    let name = match name {
+        JSValue::Undefined => "Error".to_string(),
+        name => vm.to_string(name)?,
+    };
+    let message = match message {
+        JSValue::Undefined => String::new(),
+        message => vm.to_string(message)?,
+    };
+    Ok(JSValue::String(
+        match (name.is_empty(), message.is_empty()) {
+            (true, _) => message,
+            (_, true) => name,
+            _ => format!("{}: {}", name, message),
+        },
+    ))
+}
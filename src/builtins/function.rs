@@ -0,0 +1,29 @@
+use super::define_method;
+use crate::error::{JSError, JSResult};
+use crate::value::{BoundFunction, JSObject, JSValue};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Function.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "bind", function_bind);
+    prototype
+}
+
+/// Function.prototype.bind(thisArg, ...args)
+///
+/// this と先頭の引数を固定した関数を返す。
+fn function_bind(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    if !this.is_callable() {
+        return Err(JSError::TypeError(
+            "Bind must be called on a function".to_string(),
+        ));
+    }
+    Ok(JSValue::BoundFunction(Rc::new(BoundFunction {
+        target: this.clone(),
+        this: args.first().cloned().unwrap_or(JSValue::Undefined),
+        args: args.get(1..).unwrap_or_default().to_vec(),
+    })))
+}
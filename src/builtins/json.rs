@@ -0,0 +1,151 @@
+use super::{Builtins, define_method};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// JSON オブジェクトを作成
+pub(super) fn create_json(builtins: &Builtins) -> JSValue {
+    let json = Rc::new(RefCell::new(JSObject::with_prototype(Some(
+        builtins.object_prototype.clone(),
+    ))));
+    define_method(&json, "stringify", json_stringify);
+    JSValue::Object(json)
+}
+
+/// JSON.stringify(value, replacer)
+///
+/// toJSON メソッドを持つ値はその結果を、replacer 関数があれば
+/// replacer(key, value) の結果を文字列化する。undefined・関数は省略する。
+fn json_stringify(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let value = args.first().cloned().unwrap_or(JSValue::Undefined);
+    let mut serializer = Serializer {
+        replacer: args.get(1).filter(|f| f.is_callable()).cloned(),
+        stack: Vec::new(),
+    };
+    // 最上位の値は空文字列のキーで保持するオブジェクトのプロパティとして扱う
+    let mut holder = JSObject::new();
+    holder.set(String::new(), value.clone());
+    let holder = JSValue::Object(Rc::new(RefCell::new(holder)));
+    Ok(match serializer.property(vm, &holder, "", value)? {
+        Some(json) => JSValue::String(json),
+        None => JSValue::Undefined,
+    })
+}
+
+/// JSON 文字列化の状態
+struct Serializer {
+    /// replacer 関数
+    replacer: Option<JSValue>,
+    /// 文字列化中のオブジェクト（循環の検出用）
+    stack: Vec<*const RefCell<JSObject>>,
+}
+
+impl Serializer {
+    /// holder の key のプロパティ value を文字列化（省略する値は None）
+    fn property(
+        &mut self,
+        vm: &mut VM,
+        holder: &JSValue,
+        key: &str,
+        mut value: JSValue,
+    ) -> JSResult<Option<String>> {
+        let key_value = || JSValue::String(key.to_string());
+        if let JSValue::Object(_) = value {
+            let to_json = vm.get_property(&value, "toJSON")?;
+            if to_json.is_callable() {
+                value = vm.call_value(&to_json, value.clone(), vec![key_value()])?;
+            }
+        }
+        if let Some(replacer) = &self.replacer {
+            value = vm.call_value(replacer, holder.clone(), vec![key_value(), value])?;
+        }
+
+        // ラッパーオブジェクトはプリミティブ値として扱う
+        let primitive = match &value {
+            JSValue::Object(obj) => match obj.borrow().internal_slot() {
+                Some(InternalSlot::Number(n)) => Some(JSValue::Number(*n)),
+                Some(InternalSlot::String(s)) => Some(JSValue::String(s.clone())),
+                Some(InternalSlot::Boolean(b)) => Some(JSValue::Boolean(*b)),
+                _ => None,
+            },
+            _ => None,
+        };
+        let value = primitive.unwrap_or(value);
+
+        Ok(match &value {
+            JSValue::Null => Some("null".to_string()),
+            JSValue::Boolean(b) => Some(b.to_string()),
+            JSValue::Number(n) if n.is_finite() => Some(value.to_console_string()),
+            JSValue::Number(_) => Some("null".to_string()),
+            JSValue::String(s) => Some(quote(s)),
+            JSValue::Object(obj) => Some(self.object(vm, &value, obj)?),
+            _ => None,
+        })
+    }
+
+    /// 配列・オブジェクトを文字列化
+    fn object(
+        &mut self,
+        vm: &mut VM,
+        value: &JSValue,
+        obj: &Rc<RefCell<JSObject>>,
+    ) -> JSResult<String> {
+        let ptr = Rc::as_ptr(obj);
+        if self.stack.contains(&ptr) {
+            return Err(JSError::TypeError(
+                "Converting circular structure to JSON".to_string(),
+            ));
+        }
+        self.stack.push(ptr);
+
+        let is_array = obj.borrow().is_array();
+        let json = if is_array {
+            let length = vm.get_property(value, "length")?.to_number() as usize;
+            let mut elements = Vec::with_capacity(length);
+            for i in 0..length {
+                let key = i.to_string();
+                let element = vm.get_property(value, &key)?;
+                // 省略する値は null にする
+                let json = self.property(vm, value, &key, element)?;
+                elements.push(json.unwrap_or_else(|| "null".to_string()));
+            }
+            format!("[{}]", elements.join(","))
+        } else {
+            let keys = obj.borrow().keys();
+            let mut members = Vec::with_capacity(keys.len());
+            for key in keys {
+                let member = vm.get_property(value, &key)?;
+                if let Some(json) = self.property(vm, value, &key, member)? {
+                    members.push(format!("{}:{}", quote(&key), json));
+                }
+            }
+            format!("{{{}}}", members.join(","))
+        };
+
+        self.stack.pop();
+        Ok(json)
+    }
+}
+
+/// 文字列を JSON の文字列リテラルにする
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            ch if ch < ' ' => out.push_str(&format!("\\u{:04x}", ch as u32)),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
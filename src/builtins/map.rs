@@ -0,0 +1,248 @@
+use super::{Builtins, define_method, iterator_result, native_function, update_size};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, IterationKind, JSMap, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Map.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "get", map_get);
+    define_method(&prototype, "set", map_set);
+    define_method(&prototype, "has", map_has);
+    define_method(&prototype, "delete", map_delete);
+    define_method(&prototype, "clear", map_clear);
+    define_method(&prototype, "entries", map_entries);
+    define_method(&prototype, "forEach", map_for_each);
+    define_method(&prototype, "keys", map_keys);
+    define_method(&prototype, "values", map_values);
+    prototype
+}
+
+/// Map / Set イテレータのプロトタイプを作成
+pub(super) fn create_iterator_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "next", map_iterator_next);
+    prototype
+}
+
+impl Builtins {
+    /// data を挿入順に走査する Map / Set イテレータを作成
+    pub fn create_map_iterator(&self, data: Rc<RefCell<JSMap>>, kind: IterationKind) -> JSValue {
+        let mut iterator = JSObject::with_prototype(Some(self.map_iterator_prototype.clone()));
+        iterator.set_internal_slot(InternalSlot::MapIterator {
+            data: Some(data),
+            kind,
+            position: 0,
+        });
+        JSValue::Object(Rc::new(RefCell::new(iterator)))
+    }
+}
+
+/// Map / Set イテレータを1つ進め、次の値を返す（走査し終えていれば None）
+///
+/// 走査中に追加された要素も返す。一度走査し終えたイテレータは以後も None を返す。
+pub fn map_iterator_step(vm: &mut VM, iterator: &RefCell<JSObject>) -> JSResult<Option<JSValue>> {
+    let (data, kind, position) = match iterator.borrow().internal_slot() {
+        Some(InternalSlot::MapIterator {
+            data,
+            kind,
+            position,
+        }) => (data.clone(), *kind, *position),
+        _ => {
+            return Err(JSError::TypeError(
+                "next method called on incompatible receiver".to_string(),
+            ));
+        }
+    };
+    let entry = data
+        .as_ref()
+        .and_then(|data| data.borrow().entry_from(position));
+    let Some((next, key, value)) = entry else {
+        iterator
+            .borrow_mut()
+            .set_internal_slot(InternalSlot::MapIterator {
+                data: None,
+                kind,
+                position,
+            });
+        return Ok(None);
+    };
+
+    let value = match kind {
+        IterationKind::Keys => key,
+        IterationKind::Values => value,
+        IterationKind::Entries => vm.create_array(vec![key, value]),
+    };
+    iterator
+        .borrow_mut()
+        .set_internal_slot(InternalSlot::MapIterator {
+            data,
+            kind,
+            position: next,
+        });
+    Ok(Some(value))
+}
+
+/// Map / Set イテレータの next(): { value, done } を返す
+fn map_iterator_next(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let JSValue::Object(iterator) = this else {
+        return Err(JSError::TypeError(
+            "next method called on incompatible receiver".to_string(),
+        ));
+    };
+    let step = map_iterator_step(vm, iterator)?;
+    Ok(iterator_result(vm, step))
+}
+
+/// Map コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let map = native_function("Map", map_call, Some(map_construct));
+    map.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.map_prototype.clone())),
+    );
+    JSValue::NativeFunction(map)
+}
+
+/// Map(): new なしの呼び出しはエラー
+fn map_call(_vm: &mut VM, _this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Err(JSError::TypeError(
+        "Constructor Map requires 'new'".to_string(),
+    ))
+}
+
+/// new Map() / new Map(entries)
+///
+/// entries は `[key, value]` を返す反復可能オブジェクト（配列や Map など）。
+fn map_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let mut data = JSMap::new();
+    match args.first() {
+        None | Some(JSValue::Undefined | JSValue::Null) => {}
+        Some(entries) => {
+            let iterator = vm.get_iterator(entries.clone())?;
+            while let Some(entry) = vm.iterator_next(&iterator)? {
+                if !matches!(entry, JSValue::Object(_)) {
+                    return Err(JSError::TypeError(format!(
+                        "Iterator value {} is not an entry object",
+                        entry
+                    )));
+                }
+                let key = vm.get_property(&entry, "0")?;
+                let value = vm.get_property(&entry, "1")?;
+                data.set(key, value);
+            }
+        }
+    }
+
+    let mut object = JSObject::with_prototype(Some(vm.builtins().map_prototype.clone()));
+    update_size(&mut object, data.len());
+    object.set_internal_slot(InternalSlot::Map(Rc::new(RefCell::new(data))));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+type ObjectRef = Rc<RefCell<JSObject>>;
+
+/// this の Map オブジェクトとその内部表現を取得
+fn this_map(this: &JSValue, method: &str) -> JSResult<(ObjectRef, Rc<RefCell<JSMap>>)> {
+    if let JSValue::Object(obj) = this
+        && let Some(InternalSlot::Map(data)) = obj.borrow().internal_slot()
+    {
+        return Ok((obj.clone(), data.clone()));
+    }
+    Err(JSError::TypeError(format!(
+        "Method Map.prototype.{} called on incompatible receiver",
+        method
+    )))
+}
+
+/// Map.prototype.get(key)
+fn map_get(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "get")?;
+    let key = args.first().unwrap_or(&JSValue::Undefined);
+    Ok(data.borrow().get(key).unwrap_or(JSValue::Undefined))
+}
+
+/// Map.prototype.set(key, value): this を返す
+fn map_set(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_map(this, "set")?;
+    let key = args.first().cloned().unwrap_or(JSValue::Undefined);
+    let value = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+    data.borrow_mut().set(key, value);
+    update_size(&mut obj.borrow_mut(), data.borrow().len());
+    Ok(this.clone())
+}
+
+/// Map.prototype.has(key)
+fn map_has(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "has")?;
+    let key = args.first().unwrap_or(&JSValue::Undefined);
+    Ok(JSValue::Boolean(data.borrow().has(key)))
+}
+
+/// Map.prototype.delete(key)
+fn map_delete(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_map(this, "delete")?;
+    let key = args.first().unwrap_or(&JSValue::Undefined);
+    let deleted = data.borrow_mut().delete(key);
+    update_size(&mut obj.borrow_mut(), data.borrow().len());
+    Ok(JSValue::Boolean(deleted))
+}
+
+/// Map.prototype.clear()
+fn map_clear(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_map(this, "clear")?;
+    data.borrow_mut().clear();
+    update_size(&mut obj.borrow_mut(), 0);
+    Ok(JSValue::Undefined)
+}
+
+/// Map.prototype.keys(): キーを挿入順に返すイテレータ
+fn map_keys(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "keys")?;
+    Ok(vm.builtins().create_map_iterator(data, IterationKind::Keys))
+}
+
+/// Map.prototype.values(): 値を挿入順に返すイテレータ
+fn map_values(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "values")?;
+    Ok(vm
+        .builtins()
+        .create_map_iterator(data, IterationKind::Values))
+}
+
+/// Map.prototype.entries(): [キー, 値] を挿入順に返すイテレータ
+fn map_entries(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "entries")?;
+    Ok(vm
+        .builtins()
+        .create_map_iterator(data, IterationKind::Entries))
+}
+
+/// Map.prototype.forEach(callback, thisArg)
+///
+/// 挿入順に callback(value, key, map) を呼び出す。
+/// 反復中に追加された要素も訪問する。
+fn map_for_each(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_map(this, "forEach")?;
+    let callback = args.first().cloned().unwrap_or(JSValue::Undefined);
+    if !callback.is_callable() {
+        return Err(JSError::TypeError(format!(
+            "{} is not a function",
+            callback
+        )));
+    }
+    let this_arg = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+
+    let mut position = 0;
+    loop {
+        // コールバックが Map を変更できるよう、借用は1要素ごとに解放する
+        let Some((next, key, value)) = data.borrow().entry_from(position) else {
+            break;
+        };
+        vm.call_value(&callback, this_arg.clone(), vec![value, key, this.clone()])?;
+        position = next;
+    }
+    Ok(JSValue::Undefined)
+}
@@ -0,0 +1,54 @@
+use super::{Builtins, define_method};
+use crate::error::JSResult;
+use crate::value::{JSObject, JSValue};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Math.random 用の疑似乱数生成器（xorshift64*）
+#[derive(Debug, Clone)]
+pub struct XorShift64 {
+    state: u64,
+}
+
+impl XorShift64 {
+    /// シードを指定して作成（状態が 0 だと常に 0 を返すため置き換える）
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: if seed == 0 {
+                0x9E37_79B9_7F4A_7C15
+            } else {
+                seed
+            },
+        }
+    }
+
+    /// 次の 64bit 乱数を生成
+    pub fn next_u64(&mut self) -> u64 {
+        let mut x = self.state;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.state = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// [0, 1) の浮動小数点数を生成（上位 53bit を使用）
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Math オブジェクトを作成
+pub(super) fn create_math(builtins: &Builtins) -> JSValue {
+    let math = Rc::new(RefCell::new(JSObject::with_prototype(Some(
+        builtins.object_prototype.clone(),
+    ))));
+    define_method(&math, "random", math_random);
+    JSValue::Object(math)
+}
+
+/// Math.random(): [0, 1) の乱数
+fn math_random(vm: &mut VM, _this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Number(vm.next_random()))
+}
@@ -1,13 +1,136 @@
 // Built-in Objects & Functions - ECMAScript標準の組み込み機能
-// TODO: Phase 2 で実装
 
+mod array;
+mod boolean;
+mod console;
+mod date;
+mod error;
+mod function;
+mod json;
+mod map;
+mod math;
+mod number;
+mod object;
+mod set;
+mod string;
+
+pub use array::array_iterator_step;
+pub use error::ErrorKind;
+pub use map::map_iterator_step;
+pub use math::XorShift64;
+
+use crate::runtime::Environment;
+use crate::value::{JSObject, JSValue, NativeFn, NativeFunction, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// 組み込みオブジェクト（コンストラクタが参照するプロトタイプ等）
 pub struct Builtins {
-    // TODO: 実装
+    /// Array.prototype
+    pub array_prototype: Rc<RefCell<JSObject>>,
+    /// 配列イテレータのプロトタイプ（next を持つ）
+    pub array_iterator_prototype: Rc<RefCell<JSObject>>,
+    /// Boolean.prototype
+    pub boolean_prototype: Rc<RefCell<JSObject>>,
+    /// Date.prototype
+    pub date_prototype: Rc<RefCell<JSObject>>,
+    /// Error.prototype
+    pub error_prototype: Rc<RefCell<JSObject>>,
+    /// TypeError.prototype
+    pub type_error_prototype: Rc<RefCell<JSObject>>,
+    /// RangeError.prototype
+    pub range_error_prototype: Rc<RefCell<JSObject>>,
+    /// SyntaxError.prototype
+    pub syntax_error_prototype: Rc<RefCell<JSObject>>,
+    /// ReferenceError.prototype
+    pub reference_error_prototype: Rc<RefCell<JSObject>>,
+    /// Function.prototype
+    pub function_prototype: Rc<RefCell<JSObject>>,
+    /// Map.prototype
+    pub map_prototype: Rc<RefCell<JSObject>>,
+    /// Map / Set イテレータのプロトタイプ（next を持つ）
+    pub map_iterator_prototype: Rc<RefCell<JSObject>>,
+    /// Number.prototype
+    pub number_prototype: Rc<RefCell<JSObject>>,
+    /// Object.prototype（他のプロトタイプとオブジェクトリテラルが継承する）
+    pub object_prototype: Rc<RefCell<JSObject>>,
+    /// Set.prototype
+    pub set_prototype: Rc<RefCell<JSObject>>,
+    /// String.prototype（文字列プリミティブのメソッド）
+    pub string_prototype: Rc<RefCell<JSObject>>,
 }
 
 impl Builtins {
     pub fn new() -> Self {
-        Self {}
+        let error_prototype = error::create_prototype();
+        let native_error = |kind| error::create_native_prototype(&error_prototype, kind);
+        let builtins = Self {
+            type_error_prototype: native_error(ErrorKind::TypeError),
+            range_error_prototype: native_error(ErrorKind::RangeError),
+            syntax_error_prototype: native_error(ErrorKind::SyntaxError),
+            reference_error_prototype: native_error(ErrorKind::ReferenceError),
+            error_prototype,
+            array_prototype: array::create_prototype(),
+            array_iterator_prototype: array::create_iterator_prototype(),
+            boolean_prototype: boolean::create_prototype(),
+            date_prototype: date::create_prototype(),
+            function_prototype: function::create_prototype(),
+            map_prototype: map::create_prototype(),
+            map_iterator_prototype: map::create_iterator_prototype(),
+            number_prototype: number::create_prototype(),
+            set_prototype: set::create_prototype(),
+            object_prototype: object::create_prototype(),
+            string_prototype: string::create_prototype(),
+        };
+        // 各プロトタイプは Object.prototype を継承する（Error のサブクラスは Error.prototype 経由）
+        for prototype in [
+            &builtins.array_prototype,
+            &builtins.array_iterator_prototype,
+            &builtins.boolean_prototype,
+            &builtins.date_prototype,
+            &builtins.error_prototype,
+            &builtins.function_prototype,
+            &builtins.map_prototype,
+            &builtins.map_iterator_prototype,
+            &builtins.number_prototype,
+            &builtins.set_prototype,
+            &builtins.string_prototype,
+        ] {
+            prototype
+                .borrow_mut()
+                .set_prototype(Some(builtins.object_prototype.clone()));
+        }
+        builtins
+    }
+
+    /// グローバル環境に組み込みオブジェクトを定義
+    pub fn install(&self, global: &Environment) {
+        global.define("Array".to_string(), array::create_constructor(self));
+        global.define("Boolean".to_string(), boolean::create_constructor(self));
+        global.define("console".to_string(), console::create_console(self));
+        global.define("Date".to_string(), date::create_constructor(self));
+        for kind in [
+            ErrorKind::Error,
+            ErrorKind::TypeError,
+            ErrorKind::RangeError,
+            ErrorKind::SyntaxError,
+            ErrorKind::ReferenceError,
+        ] {
+            global.define(
+                kind.name().to_string(),
+                error::create_constructor(self, kind),
+            );
+        }
+        global.define("Infinity".to_string(), JSValue::Number(f64::INFINITY));
+        global.define("JSON".to_string(), json::create_json(self));
+        global.define("Map".to_string(), map::create_constructor(self));
+        global.define("Math".to_string(), math::create_math(self));
+        global.define("NaN".to_string(), JSValue::Number(f64::NAN));
+        global.define("Number".to_string(), number::create_constructor(self));
+        global.define("Object".to_string(), object::create_constructor(self));
+        global.define("Set".to_string(), set::create_constructor(self));
+        global.define("String".to_string(), string::create_constructor(self));
     }
 }
 
@@ -16,3 +139,39 @@ impl Default for Builtins {
         Self::new()
     }
 }
+
+/// ネイティブ関数を作成
+fn native_function(name: &str, call: NativeFn, construct: Option<NativeFn>) -> Rc<NativeFunction> {
+    Rc::new(NativeFunction::new(name, call, construct))
+}
+
+/// オブジェクトに列挙不可のネイティブメソッドを定義
+fn define_method(object: &Rc<RefCell<JSObject>>, name: &str, call: NativeFn) {
+    let method = JSValue::NativeFunction(native_function(name, call, None));
+    object
+        .borrow_mut()
+        .define_property(name.to_string(), Property::non_enumerable(method));
+}
+
+/// イテレータの next() が返す { value, done } を作成（step が None なら終了）
+fn iterator_result(vm: &VM, step: Option<JSValue>) -> JSValue {
+    let done = step.is_none();
+    let mut result = vm.create_object();
+    result.set("value".to_string(), step.unwrap_or(JSValue::Undefined));
+    result.set("done".to_string(), JSValue::Boolean(done));
+    JSValue::Object(Rc::new(RefCell::new(result)))
+}
+
+/// Map / Set の size プロパティを要素数に合わせて更新
+fn update_size(object: &mut JSObject, size: usize) {
+    object.define_property(
+        "size".to_string(),
+        Property {
+            value: JSValue::Number(size as f64),
+            enumerable: false,
+            writable: false,
+            configurable: true,
+            accessor: None,
+        },
+    );
+}
@@ -0,0 +1,155 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Number.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "toString", number_to_string);
+    define_method(&prototype, "valueOf", number_value_of);
+    prototype
+}
+
+/// Number コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let number = native_function("Number", number_call, Some(number_construct));
+    number.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.number_prototype.clone())),
+    );
+    JSValue::NativeFunction(number)
+}
+
+/// 引数を数値に変換（省略時は 0）
+fn argument_number(vm: &mut VM, args: &[JSValue]) -> JSResult<f64> {
+    match args.first() {
+        Some(value) => vm.to_number(value.clone()),
+        None => Ok(0.0),
+    }
+}
+
+/// Number(value): 数値への変換
+fn number_call(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Number(argument_number(vm, args)?))
+}
+
+/// new Number(value): 数値を保持するラッパーオブジェクト
+fn number_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let n = argument_number(vm, args)?;
+    let mut object = JSObject::with_prototype(Some(vm.builtins().number_prototype.clone()));
+    object.set_internal_slot(InternalSlot::Number(n));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+/// this の数値を取得（プリミティブまたは Number オブジェクト）
+fn this_number(this: &JSValue, method: &str) -> JSResult<f64> {
+    match this {
+        JSValue::Number(n) => return Ok(*n),
+        JSValue::Object(obj) => {
+            if let Some(InternalSlot::Number(n)) = obj.borrow().internal_slot() {
+                return Ok(*n);
+            }
+        }
+        _ => {}
+    }
+    Err(JSError::TypeError(format!(
+        "Number.prototype.{} requires that 'this' be a Number",
+        method
+    )))
+}
+
+/// Number.prototype.valueOf()
+fn number_value_of(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::Number(this_number(this, "valueOf")?))
+}
+
+/// Number.prototype.toString(radix)
+///
+/// radix は 2〜36（省略時は 10）。範囲外なら RangeError。
+fn number_to_string(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let n = this_number(this, "toString")?;
+    let radix = match args.first() {
+        None | Some(JSValue::Undefined) => 10.0,
+        Some(value) => vm.to_number(value.clone())?.trunc(),
+    };
+    if !(2.0..=36.0).contains(&radix) {
+        return Err(JSError::RangeError(
+            "toString() radix must be between 2 and 36".to_string(),
+        ));
+    }
+    if radix == 10.0 || !n.is_finite() || n == 0.0 {
+        return Ok(JSValue::String(JSValue::Number(n).to_console_string()));
+    }
+    Ok(JSValue::String(to_radix_string(n, radix as u32)))
+}
+
+/// 有限の数値を radix 進数の文字列に変換
+///
+/// 小数部は元の数値と区別できる最短の桁数まで出力する。
+fn to_radix_string(n: f64, radix: u32) -> String {
+    if n < 0.0 {
+        return format!("-{}", to_radix_string(-n, radix));
+    }
+    let digit_char = |digit: u32| char::from_digit(digit, radix).unwrap_or('0');
+    let radix_f = f64::from(radix);
+
+    let mut integer = n.floor();
+    let mut fraction = n - integer;
+    // 隣の表現可能な数値との距離の半分より細かい桁は出力しない
+    let mut delta = (0.5 * (n.next_up() - n)).max(0.0_f64.next_up());
+    let mut fraction_digits = Vec::new();
+    if fraction >= delta {
+        loop {
+            fraction *= radix_f;
+            delta *= radix_f;
+            let digit = fraction as u32;
+            fraction_digits.push(digit);
+            fraction -= f64::from(digit);
+            if (fraction > 0.5 || (fraction == 0.5 && digit & 1 == 1)) && fraction + delta > 1.0 {
+                // 切り上げて終了（桁上がりは整数部まで伝播しうる）
+                loop {
+                    match fraction_digits.pop() {
+                        Some(digit) if digit + 1 < radix => {
+                            fraction_digits.push(digit + 1);
+                            break;
+                        }
+                        Some(_) => {}
+                        None => {
+                            integer += 1.0;
+                            break;
+                        }
+                    }
+                }
+                break;
+            }
+            if fraction < delta {
+                break;
+            }
+        }
+    }
+
+    // 整数部: 2^53 以上の桁は正確に表せないため 0 とする
+    let mut integer_digits = Vec::new();
+    while integer / radix_f >= 9_007_199_254_740_992.0 {
+        integer /= radix_f;
+        integer_digits.push('0');
+    }
+    loop {
+        let remainder = integer % radix_f;
+        integer_digits.push(digit_char(remainder as u32));
+        integer = (integer - remainder) / radix_f;
+        if integer <= 0.0 {
+            break;
+        }
+    }
+
+    let mut result: String = integer_digits.into_iter().rev().collect();
+    if !fraction_digits.is_empty() {
+        result.push('.');
+        result.extend(fraction_digits.into_iter().map(digit_char));
+    }
+    result
+}
@@ -0,0 +1,344 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{Accessor, InternalSlot, JSObject, JSValue, Property, array_index};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Object.prototype を作成（プロトタイプチェーンの終端）
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "hasOwnProperty", object_has_own_property);
+    define_method(&prototype, "isPrototypeOf", object_is_prototype_of);
+    define_method(&prototype, "toString", object_to_string);
+    define_method(&prototype, "valueOf", object_value_of);
+    prototype
+}
+
+/// Object コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let object = native_function("Object", object_construct, Some(object_construct));
+    object.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.object_prototype.clone())),
+    );
+    define_method(&object.properties, "create", object_create);
+    define_method(&object.properties, "defineProperty", object_define_property);
+    define_method(
+        &object.properties,
+        "getOwnPropertyDescriptor",
+        object_get_own_property_descriptor,
+    );
+    define_method(&object.properties, "is", object_is);
+    define_method(&object.properties, "keys", object_keys);
+    JSValue::NativeFunction(object)
+}
+
+/// Object(value) / new Object(value)
+///
+/// オブジェクトはそのまま返し、それ以外は新しい空のオブジェクトを返す。
+fn object_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    match args.first() {
+        Some(value @ (JSValue::Object(_) | JSValue::Function(_) | JSValue::NativeFunction(_))) => {
+            Ok(value.clone())
+        }
+        _ => Ok(JSValue::Object(Rc::new(RefCell::new(vm.create_object())))),
+    }
+}
+
+/// Object.prototype.hasOwnProperty(key): 継承したプロパティは含まない
+fn object_has_own_property(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let key = vm.to_property_key(args.first().cloned().unwrap_or(JSValue::Undefined))?;
+    let has_own = match this {
+        JSValue::Undefined | JSValue::Null => {
+            return Err(JSError::TypeError(
+                "Cannot convert undefined or null to object".to_string(),
+            ));
+        }
+        JSValue::Object(obj) => obj.borrow().has_own_property(&key),
+        JSValue::NativeFunction(native) => native.properties.borrow().has_own_property(&key),
+        JSValue::Function(func) => func
+            .properties(&vm.builtins().object_prototype)
+            .borrow()
+            .has_own_property(&key),
+        // 文字列は length と各文字のインデックスを自身のプロパティとして持つ
+        JSValue::String(s) => {
+            key == "length" || array_index(&key).is_some_and(|i| (i as usize) < s.chars().count())
+        }
+        _ => false,
+    };
+    Ok(JSValue::Boolean(has_own))
+}
+
+/// Object.prototype.toString(): "[object 種類]"
+fn object_to_string(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let tag = match this {
+        JSValue::Undefined => "Undefined",
+        JSValue::Null => "Null",
+        JSValue::Boolean(_) => "Boolean",
+        JSValue::Number(_) => "Number",
+        JSValue::String(_) => "String",
+        JSValue::Object(obj) => match obj.borrow().internal_slot() {
+            Some(InternalSlot::Array) => "Array",
+            Some(InternalSlot::Error) => "Error",
+            Some(InternalSlot::Date(_)) => "Date",
+            Some(InternalSlot::Boolean(_)) => "Boolean",
+            Some(InternalSlot::Number(_)) => "Number",
+            Some(InternalSlot::String(_)) => "String",
+            _ => "Object",
+        },
+        _ if this.is_callable() => "Function",
+        _ => "Object",
+    };
+    Ok(JSValue::String(format!("[object {}]", tag)))
+}
+
+/// Object.prototype.valueOf(): this をそのまま返す
+fn object_value_of(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    if matches!(this, JSValue::Undefined | JSValue::Null) {
+        return Err(JSError::TypeError(
+            "Cannot convert undefined or null to object".to_string(),
+        ));
+    }
+    Ok(this.clone())
+}
+
+/// Object.prototype.isPrototypeOf(value): this が value のプロトタイプチェーン上にあるか
+fn object_is_prototype_of(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let Some(JSValue::Object(value)) = args.first() else {
+        return Ok(JSValue::Boolean(false));
+    };
+    let Some(prototype) = to_object(Some(this))? else {
+        return Ok(JSValue::Boolean(false));
+    };
+    let mut current = value.borrow().get_prototype();
+    while let Some(obj) = current {
+        if Rc::ptr_eq(&obj, &prototype) {
+            return Ok(JSValue::Boolean(true));
+        }
+        current = obj.borrow().get_prototype();
+    }
+    Ok(JSValue::Boolean(false))
+}
+
+/// Object.is(a, b): SameValue 比較
+fn object_is(_vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let a = args.first().unwrap_or(&JSValue::Undefined);
+    let b = args.get(1).unwrap_or(&JSValue::Undefined);
+    Ok(JSValue::Boolean(a.same_value(b)))
+}
+
+/// 引数をオブジェクトとして取得（undefined / null は TypeError、他のプリミティブは None）
+fn to_object(value: Option<&JSValue>) -> JSResult<Option<Rc<RefCell<JSObject>>>> {
+    match value {
+        Some(JSValue::Object(obj)) => Ok(Some(obj.clone())),
+        None | Some(JSValue::Undefined | JSValue::Null) => Err(JSError::TypeError(
+            "Cannot convert undefined or null to object".to_string(),
+        )),
+        Some(_) => Ok(None),
+    }
+}
+
+/// Object.keys(obj): 列挙可能な自身のプロパティキーの配列
+fn object_keys(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let keys = match to_object(args.first())? {
+        Some(obj) => obj
+            .borrow()
+            .keys()
+            .into_iter()
+            .map(JSValue::String)
+            .collect(),
+        None => Vec::new(),
+    };
+    Ok(vm.create_array(keys))
+}
+
+/// Object.getOwnPropertyDescriptor(obj, key)
+///
+/// 自身のプロパティの value / writable（アクセサプロパティは get / set）と enumerable /
+/// configurable を持つオブジェクトを返す。
+/// プロパティがなければ undefined。
+fn object_get_own_property_descriptor(
+    vm: &mut VM,
+    _this: &JSValue,
+    args: &[JSValue],
+) -> JSResult<JSValue> {
+    let Some(obj) = to_object(args.first())? else {
+        return Ok(JSValue::Undefined);
+    };
+    let key = vm.to_property_key(args.get(1).cloned().unwrap_or(JSValue::Undefined))?;
+    let Some(property) = obj.borrow().get_property_descriptor(&key) else {
+        return Ok(JSValue::Undefined);
+    };
+
+    let mut descriptor = vm.create_object();
+    match property.accessor {
+        Some(accessor) => {
+            descriptor.set("get".to_string(), accessor.get);
+            descriptor.set("set".to_string(), accessor.set);
+        }
+        None => {
+            descriptor.set("value".to_string(), property.value);
+            descriptor.set("writable".to_string(), JSValue::Boolean(property.writable));
+        }
+    }
+    for (name, flag) in [
+        ("enumerable", property.enumerable),
+        ("configurable", property.configurable),
+    ] {
+        descriptor.set(name.to_string(), JSValue::Boolean(flag));
+    }
+    Ok(JSValue::Object(Rc::new(RefCell::new(descriptor))))
+}
+
+/// Object.defineProperty(obj, key, descriptor)
+///
+/// ディスクリプタの value / writable / get / set / enumerable / configurable でプロパティを
+/// 定義し、obj を返す。get / set を指定するとアクセサプロパティになる。省略した属性は
+/// 既存のプロパティの値を引き継ぎ、新しいプロパティでは undefined / false とする。
+/// 設定変更不可のプロパティは再定義できない。
+fn object_define_property(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let Some(JSValue::Object(obj)) = args.first() else {
+        return Err(JSError::TypeError(
+            "Object.defineProperty called on non-object".to_string(),
+        ));
+    };
+    let key = vm.to_property_key(args.get(1).cloned().unwrap_or(JSValue::Undefined))?;
+    define_from_descriptor(obj, key, args.get(2).unwrap_or(&JSValue::Undefined))?;
+    Ok(args[0].clone())
+}
+
+/// Object.create(proto, properties)
+///
+/// proto（オブジェクトまたは null）をプロトタイプとする新しいオブジェクトを返す。
+/// properties を指定すると、その列挙可能な自身のプロパティをディスクリプタとして定義する。
+fn object_create(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let prototype = match args.first() {
+        Some(JSValue::Object(proto)) => Some(proto.clone()),
+        Some(JSValue::Null) => None,
+        other => {
+            return Err(JSError::TypeError(format!(
+                "Object prototype may only be an Object or null: {}",
+                other.unwrap_or(&JSValue::Undefined)
+            )));
+        }
+    };
+    let obj = Rc::new(RefCell::new(JSObject::with_prototype(prototype)));
+    match args.get(1) {
+        None | Some(JSValue::Undefined) => {}
+        Some(properties) => {
+            let Some(properties) = to_object(Some(properties))? else {
+                return Ok(JSValue::Object(obj));
+            };
+            let keys = properties.borrow().keys();
+            let properties = JSValue::Object(properties);
+            for key in keys {
+                let descriptor = vm.get_property(&properties, &key)?;
+                define_from_descriptor(&obj, key, &descriptor)?;
+            }
+        }
+    }
+    Ok(JSValue::Object(obj))
+}
+
+/// ディスクリプタ（Object.defineProperty の第3引数の形式）でプロパティを定義
+fn define_from_descriptor(
+    obj: &Rc<RefCell<JSObject>>,
+    key: String,
+    descriptor: &JSValue,
+) -> JSResult<()> {
+    let JSValue::Object(descriptor) = descriptor else {
+        return Err(JSError::TypeError(
+            "Property description must be an object".to_string(),
+        ));
+    };
+
+    // obj 自身がディスクリプタの場合もあるため、先に属性を読み出しておく
+    let (value, get, set, [writable, enumerable, configurable]) = {
+        let descriptor = descriptor.borrow();
+        let field = |name: &str| descriptor.has_property(name).then(|| descriptor.get(name));
+        let flag = |name: &str| field(name).map(|value| value.to_boolean());
+        (
+            field("value"),
+            field("get"),
+            field("set"),
+            [flag("writable"), flag("enumerable"), flag("configurable")],
+        )
+    };
+    let is_accessor = get.is_some() || set.is_some();
+    if is_accessor && (value.is_some() || writable.is_some()) {
+        return Err(JSError::TypeError(
+            "Invalid property descriptor. Cannot both specify accessors and a value or writable attribute"
+                .to_string(),
+        ));
+    }
+    for (kind, function) in [("Getter", &get), ("Setter", &set)] {
+        if let Some(function) = function
+            && !function.is_callable()
+            && !matches!(function, JSValue::Undefined)
+        {
+            return Err(JSError::TypeError(format!(
+                "{} must be a function: {}",
+                kind, function
+            )));
+        }
+    }
+
+    let mut obj = obj.borrow_mut();
+    let current = obj.get_property_descriptor(&key);
+    if current
+        .as_ref()
+        .is_some_and(|property| !property.configurable)
+    {
+        return Err(JSError::TypeError(format!(
+            "Cannot redefine property: {}",
+            key
+        )));
+    }
+    let current_accessor = current
+        .as_ref()
+        .and_then(|property| property.accessor.clone());
+    let enumerable = enumerable
+        .or(current.as_ref().map(|property| property.enumerable))
+        .unwrap_or(false);
+    let configurable = configurable
+        .or(current.as_ref().map(|property| property.configurable))
+        .unwrap_or(false);
+
+    let property = match current_accessor {
+        // アクセサプロパティの定義と、value / writable を含まない既存のアクセサプロパティの更新
+        Some(accessor) if value.is_none() && writable.is_none() => Property::accessor(
+            Accessor {
+                get: get.unwrap_or(accessor.get),
+                set: set.unwrap_or(accessor.set),
+            },
+            enumerable,
+            configurable,
+        ),
+        _ if is_accessor => Property::accessor(
+            Accessor {
+                get: get.unwrap_or(JSValue::Undefined),
+                set: set.unwrap_or(JSValue::Undefined),
+            },
+            enumerable,
+            configurable,
+        ),
+        // データプロパティ（アクセサプロパティからの変更では value / writable を引き継がない）
+        accessor => {
+            let current = current.filter(|_| accessor.is_none());
+            Property {
+                value: value
+                    .or(current.as_ref().map(|property| property.value.clone()))
+                    .unwrap_or(JSValue::Undefined),
+                enumerable,
+                writable: writable
+                    .or(current.as_ref().map(|property| property.writable))
+                    .unwrap_or(false),
+                configurable,
+                accessor: None,
+            }
+        }
+    };
+    obj.define_property(key, property);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use super::{Builtins, define_method, native_function, update_size};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, IterationKind, JSMap, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Set.prototype を作成
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "add", set_add);
+    define_method(&prototype, "has", set_has);
+    define_method(&prototype, "delete", set_delete);
+    define_method(&prototype, "clear", set_clear);
+    define_method(&prototype, "entries", set_entries);
+    define_method(&prototype, "forEach", set_for_each);
+    define_method(&prototype, "keys", set_values);
+    define_method(&prototype, "values", set_values);
+    prototype
+}
+
+/// Set コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let set = native_function("Set", set_call, Some(set_construct));
+    set.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.set_prototype.clone())),
+    );
+    JSValue::NativeFunction(set)
+}
+
+/// Set(): new なしの呼び出しはエラー
+fn set_call(_vm: &mut VM, _this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    Err(JSError::TypeError(
+        "Constructor Set requires 'new'".to_string(),
+    ))
+}
+
+/// new Set() / new Set(values)
+///
+/// values は反復可能オブジェクト（配列・文字列・Set など）。重複する値は1つにまとめる。
+fn set_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let mut data = JSMap::new();
+    match args.first() {
+        None | Some(JSValue::Undefined | JSValue::Null) => {}
+        Some(values) => {
+            let iterator = vm.get_iterator(values.clone())?;
+            while let Some(value) = vm.iterator_next(&iterator)? {
+                data.set(value.clone(), value);
+            }
+        }
+    }
+
+    let mut object = JSObject::with_prototype(Some(vm.builtins().set_prototype.clone()));
+    update_size(&mut object, data.len());
+    object.set_internal_slot(InternalSlot::Set(Rc::new(RefCell::new(data))));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+type ObjectRef = Rc<RefCell<JSObject>>;
+
+/// this の Set オブジェクトとその内部表現を取得
+fn this_set(this: &JSValue, method: &str) -> JSResult<(ObjectRef, Rc<RefCell<JSMap>>)> {
+    if let JSValue::Object(obj) = this
+        && let Some(InternalSlot::Set(data)) = obj.borrow().internal_slot()
+    {
+        return Ok((obj.clone(), data.clone()));
+    }
+    Err(JSError::TypeError(format!(
+        "Method Set.prototype.{} called on incompatible receiver",
+        method
+    )))
+}
+
+/// Set.prototype.add(value): this を返す
+fn set_add(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_set(this, "add")?;
+    let value = args.first().cloned().unwrap_or(JSValue::Undefined);
+    data.borrow_mut().set(value.clone(), value);
+    update_size(&mut obj.borrow_mut(), data.borrow().len());
+    Ok(this.clone())
+}
+
+/// Set.prototype.has(value)
+fn set_has(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_set(this, "has")?;
+    let value = args.first().unwrap_or(&JSValue::Undefined);
+    Ok(JSValue::Boolean(data.borrow().has(value)))
+}
+
+/// Set.prototype.delete(value)
+fn set_delete(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_set(this, "delete")?;
+    let value = args.first().unwrap_or(&JSValue::Undefined);
+    let deleted = data.borrow_mut().delete(value);
+    update_size(&mut obj.borrow_mut(), data.borrow().len());
+    Ok(JSValue::Boolean(deleted))
+}
+
+/// Set.prototype.clear()
+fn set_clear(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (obj, data) = this_set(this, "clear")?;
+    data.borrow_mut().clear();
+    update_size(&mut obj.borrow_mut(), 0);
+    Ok(JSValue::Undefined)
+}
+
+/// Set.prototype.values() / keys(): 値を挿入順に返すイテレータ
+fn set_values(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_set(this, "values")?;
+    Ok(vm
+        .builtins()
+        .create_map_iterator(data, IterationKind::Values))
+}
+
+/// Set.prototype.entries(): [値, 値] を挿入順に返すイテレータ
+fn set_entries(vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_set(this, "entries")?;
+    Ok(vm
+        .builtins()
+        .create_map_iterator(data, IterationKind::Entries))
+}
+
+/// Set.prototype.forEach(callback, thisArg)
+///
+/// 挿入順に callback(value, value, set) を呼び出す。
+fn set_for_each(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let (_, data) = this_set(this, "forEach")?;
+    let callback = args.first().cloned().unwrap_or(JSValue::Undefined);
+    if !callback.is_callable() {
+        return Err(JSError::TypeError(format!(
+            "{} is not a function",
+            callback
+        )));
+    }
+    let this_arg = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+
+    let mut position = 0;
+    loop {
+        let Some((next, value, _)) = data.borrow().entry_from(position) else {
+            break;
+        };
+        vm.call_value(
+            &callback,
+            this_arg.clone(),
+            vec![value.clone(), value, this.clone()],
+        )?;
+        position = next;
+    }
+    Ok(JSValue::Undefined)
+}
@@ -0,0 +1,339 @@
+use super::{Builtins, define_method, native_function};
+use crate::error::{JSError, JSResult};
+use crate::value::{InternalSlot, JSObject, JSValue, Property};
+use crate::vm::VM;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// 作成できる文字列の長さの上限（char 単位）
+const MAX_STRING_LENGTH: f64 = (1u64 << 30) as f64;
+
+/// 結果の長さが上限を超える場合の RangeError
+fn invalid_string_length() -> JSError {
+    JSError::RangeError("Invalid string length".to_string())
+}
+
+/// String.prototype を作成
+///
+/// 文字列のインデックスと長さは Unicode スカラー値（char）単位で扱う。
+pub(super) fn create_prototype() -> Rc<RefCell<JSObject>> {
+    let prototype = Rc::new(RefCell::new(JSObject::new()));
+    define_method(&prototype, "at", string_at);
+    define_method(&prototype, "charAt", string_char_at);
+    define_method(&prototype, "endsWith", string_ends_with);
+    define_method(&prototype, "includes", string_includes);
+    define_method(&prototype, "padEnd", string_pad_end);
+    define_method(&prototype, "padStart", string_pad_start);
+    define_method(&prototype, "repeat", string_repeat);
+    define_method(&prototype, "replace", string_replace);
+    define_method(&prototype, "startsWith", string_starts_with);
+    define_method(&prototype, "toString", string_to_string);
+    define_method(&prototype, "trim", string_trim);
+    define_method(&prototype, "trimEnd", string_trim_end);
+    define_method(&prototype, "trimStart", string_trim_start);
+    define_method(&prototype, "valueOf", string_value_of);
+    prototype
+}
+
+/// String コンストラクタを作成
+pub(super) fn create_constructor(builtins: &Builtins) -> JSValue {
+    let string = native_function("String", string_call, Some(string_construct));
+    string.properties.borrow_mut().define_property(
+        "prototype".to_string(),
+        Property::read_only(JSValue::Object(builtins.string_prototype.clone())),
+    );
+    JSValue::NativeFunction(string)
+}
+
+/// 引数を文字列に変換（省略時は空文字列）
+fn argument_string(vm: &mut VM, args: &[JSValue]) -> JSResult<String> {
+    match args.first() {
+        Some(value) => vm.to_string(value.clone()),
+        None => Ok(String::new()),
+    }
+}
+
+/// String(value): 文字列への変換
+fn string_call(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    Ok(JSValue::String(argument_string(vm, args)?))
+}
+
+/// new String(value): 文字列を保持するラッパーオブジェクト
+///
+/// length と各文字のインデックスは読み取り専用のプロパティとして持つ。
+fn string_construct(vm: &mut VM, _this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = argument_string(vm, args)?;
+    let mut object = JSObject::with_prototype(Some(vm.builtins().string_prototype.clone()));
+    for (i, ch) in s.chars().enumerate() {
+        object.define_property(
+            i.to_string(),
+            Property::read_only(JSValue::String(ch.to_string())),
+        );
+    }
+    object.define_property(
+        "length".to_string(),
+        Property {
+            value: JSValue::Number(s.chars().count() as f64),
+            enumerable: false,
+            writable: false,
+            configurable: false,
+            accessor: None,
+        },
+    );
+    object.set_internal_slot(InternalSlot::String(s));
+    Ok(JSValue::Object(Rc::new(RefCell::new(object))))
+}
+
+/// this を文字列に変換（undefined / null は TypeError）
+fn this_string(this: &JSValue, method: &str) -> JSResult<String> {
+    match this {
+        JSValue::Undefined | JSValue::Null => Err(JSError::TypeError(format!(
+            "String.prototype.{} called on null or undefined",
+            method
+        ))),
+        JSValue::String(s) => Ok(s.clone()),
+        JSValue::Object(obj) => match obj.borrow().internal_slot() {
+            Some(InternalSlot::String(s)) => Ok(s.clone()),
+            _ => Ok(this.to_console_string()),
+        },
+        other => Ok(other.to_console_string()),
+    }
+}
+
+/// 引数を文字列に変換（省略時は "undefined"）
+fn string_arg(args: &[JSValue], index: usize) -> String {
+    args.get(index)
+        .unwrap_or(&JSValue::Undefined)
+        .to_console_string()
+}
+
+/// JavaScript の空白文字（WhiteSpace と LineTerminator）
+fn is_js_whitespace(ch: char) -> bool {
+    ch.is_whitespace() || ch == '\u{FEFF}'
+}
+
+/// String.prototype.valueOf()
+fn string_value_of(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    string_primitive(this, "valueOf")
+}
+
+/// String.prototype.toString()
+fn string_to_string(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    string_primitive(this, "toString")
+}
+
+/// this の文字列を取得（プリミティブまたは String オブジェクトのみ）
+fn string_primitive(this: &JSValue, method: &str) -> JSResult<JSValue> {
+    match this {
+        JSValue::String(s) => return Ok(JSValue::String(s.clone())),
+        JSValue::Object(obj) => {
+            if let Some(InternalSlot::String(s)) = obj.borrow().internal_slot() {
+                return Ok(JSValue::String(s.clone()));
+            }
+        }
+        _ => {}
+    }
+    Err(JSError::TypeError(format!(
+        "String.prototype.{} requires that 'this' be a String",
+        method
+    )))
+}
+
+/// String.prototype.charAt(pos)
+fn string_char_at(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "charAt")?;
+    let pos = args
+        .first()
+        .unwrap_or(&JSValue::Undefined)
+        .to_integer_or_infinity();
+    let ch = if pos < 0.0 {
+        None
+    } else {
+        s.chars().nth(pos as usize)
+    };
+    Ok(JSValue::String(ch.map(String::from).unwrap_or_default()))
+}
+
+/// String.prototype.at(index)
+///
+/// 負の index は末尾から数える。範囲外なら undefined。
+fn string_at(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "at")?;
+    let length = s.chars().count() as f64;
+    let relative = args
+        .first()
+        .unwrap_or(&JSValue::Undefined)
+        .to_integer_or_infinity();
+    let index = if relative < 0.0 {
+        length + relative
+    } else {
+        relative
+    };
+    if index < 0.0 || index >= length {
+        return Ok(JSValue::Undefined);
+    }
+    Ok(s.chars()
+        .nth(index as usize)
+        .map_or(JSValue::Undefined, |ch| JSValue::String(ch.to_string())))
+}
+
+/// 文字位置の引数を 0..=文字数 に丸めてバイト位置に変換（省略時は default）
+fn byte_position(s: &str, value: Option<&JSValue>, default: usize) -> usize {
+    let length = s.chars().count();
+    let position = match value {
+        None | Some(JSValue::Undefined) => default,
+        Some(value) => value.to_integer_or_infinity().clamp(0.0, length as f64) as usize,
+    };
+    s.char_indices()
+        .nth(position)
+        .map_or(s.len(), |(offset, _)| offset)
+}
+
+/// 検索文字列の引数を取得（正規表現は未対応のため文字列に変換する）
+fn search_string(args: &[JSValue]) -> String {
+    string_arg(args, 0)
+}
+
+/// String.prototype.includes(searchString, position)
+fn string_includes(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "includes")?;
+    let start = byte_position(&s, args.get(1), 0);
+    Ok(JSValue::Boolean(s[start..].contains(&search_string(args))))
+}
+
+/// String.prototype.startsWith(searchString, position)
+fn string_starts_with(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "startsWith")?;
+    let start = byte_position(&s, args.get(1), 0);
+    Ok(JSValue::Boolean(
+        s[start..].starts_with(&search_string(args)),
+    ))
+}
+
+/// String.prototype.endsWith(searchString, endPosition)
+fn string_ends_with(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "endsWith")?;
+    let end = byte_position(&s, args.get(1), usize::MAX);
+    Ok(JSValue::Boolean(s[..end].ends_with(&search_string(args))))
+}
+
+/// String.prototype.trim()
+fn string_trim(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "trim")?;
+    Ok(JSValue::String(
+        s.trim_matches(is_js_whitespace).to_string(),
+    ))
+}
+
+/// String.prototype.trimStart()
+fn string_trim_start(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "trimStart")?;
+    Ok(JSValue::String(
+        s.trim_start_matches(is_js_whitespace).to_string(),
+    ))
+}
+
+/// String.prototype.trimEnd()
+fn string_trim_end(_vm: &mut VM, this: &JSValue, _args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "trimEnd")?;
+    Ok(JSValue::String(
+        s.trim_end_matches(is_js_whitespace).to_string(),
+    ))
+}
+
+/// String.prototype.repeat(count)
+fn string_repeat(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "repeat")?;
+    let count = args
+        .first()
+        .unwrap_or(&JSValue::Undefined)
+        .to_integer_or_infinity();
+    if count < 0.0 || count.is_infinite() {
+        return Err(JSError::RangeError(format!(
+            "Invalid count value: {}",
+            args[0]
+        )));
+    }
+    // 確保する前に結果の長さを確認する
+    if !s.is_empty() && s.chars().count() as f64 * count > MAX_STRING_LENGTH {
+        return Err(invalid_string_length());
+    }
+    Ok(JSValue::String(s.repeat(count as usize)))
+}
+
+/// String.prototype.replace(search, replacement)
+///
+/// 最初に一致した部分だけを置換する。replacement が関数の場合は
+/// (一致した文字列, 位置, 元の文字列) を渡して呼び出し、その結果で置換する。
+/// 正規表現と `$&` 等の置換パターンは未対応。
+fn string_replace(vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "replace")?;
+    let search = string_arg(args, 0);
+    let replacement = args.get(1).cloned().unwrap_or(JSValue::Undefined);
+
+    let Some(start) = s.find(&search) else {
+        return Ok(JSValue::String(s));
+    };
+    let replacement = if replacement.is_callable() {
+        let position = s[..start].chars().count() as f64;
+        vm.call_value(
+            &replacement,
+            JSValue::Undefined,
+            vec![
+                JSValue::String(search.clone()),
+                JSValue::Number(position),
+                JSValue::String(s.clone()),
+            ],
+        )?
+        .to_console_string()
+    } else {
+        replacement.to_console_string()
+    };
+
+    let end = start + search.len();
+    Ok(JSValue::String(format!(
+        "{}{}{}",
+        &s[..start],
+        replacement,
+        &s[end..]
+    )))
+}
+
+/// padStart / padEnd で追加する埋め文字列を作成（None なら埋める必要なし）
+fn padding(s: &str, args: &[JSValue]) -> JSResult<Option<String>> {
+    let max_length = args
+        .first()
+        .unwrap_or(&JSValue::Undefined)
+        .to_integer_or_infinity();
+    let fill = match args.get(1) {
+        None | Some(JSValue::Undefined) => " ".to_string(),
+        Some(value) => value.to_console_string(),
+    };
+    let length = s.chars().count() as f64;
+    if max_length <= length || fill.is_empty() {
+        return Ok(None);
+    }
+    if max_length > MAX_STRING_LENGTH {
+        return Err(invalid_string_length());
+    }
+    let fill_length = (max_length - length) as usize;
+    Ok(Some(fill.chars().cycle().take(fill_length).collect()))
+}
+
+/// String.prototype.padStart(maxLength, fillString)
+fn string_pad_start(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "padStart")?;
+    Ok(JSValue::String(match padding(&s, args)? {
+        Some(pad) => pad + &s,
+        None => s,
+    }))
+}
+
+/// String.prototype.padEnd(maxLength, fillString)
+fn string_pad_end(_vm: &mut VM, this: &JSValue, args: &[JSValue]) -> JSResult<JSValue> {
+    let s = this_string(this, "padEnd")?;
+    Ok(JSValue::String(match padding(&s, args)? {
+        Some(pad) => s + &pad,
+        None => s,
+    }))
+}
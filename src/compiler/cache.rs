@@ -0,0 +1,76 @@
+use super::BytecodeChunk;
+use rustc_hash::FxHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::rc::Rc;
+
+/// ソースコードのハッシュをキーにしたコンパイル結果の LRU キャッシュ
+///
+/// 同じソースの再評価で字句解析・構文解析・コンパイルを省く。キーは内容の
+/// ハッシュなので無効化は不要だが、衝突に備えてソース自体も照合する。
+#[derive(Debug)]
+pub struct CompileCache {
+    /// 保持するチャンクの上限
+    capacity: usize,
+    /// 最近使った順（先頭が最新）のエントリ
+    entries: VecDeque<CacheEntry>,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    hash: u64,
+    source: String,
+    chunk: Rc<BytecodeChunk>,
+}
+
+impl CompileCache {
+    /// 最大 capacity 個のチャンクを保持するキャッシュを作成
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// キャッシュ済みのチャンクを取得し、最近使ったものとして記録
+    pub fn get(&mut self, source: &str) -> Option<Rc<BytecodeChunk>> {
+        let hash = Self::hash(source);
+        let position = self
+            .entries
+            .iter()
+            .position(|entry| entry.hash == hash && entry.source == source)?;
+        let entry = self.entries.remove(position)?;
+        let chunk = entry.chunk.clone();
+        self.entries.push_front(entry);
+        Some(chunk)
+    }
+
+    /// チャンクを追加（上限を超えたら最も古いものを捨てる）
+    pub fn insert(&mut self, source: &str, chunk: Rc<BytecodeChunk>) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.entries.truncate(self.capacity - 1);
+        self.entries.push_front(CacheEntry {
+            hash: Self::hash(source),
+            source: source.to_string(),
+            chunk,
+        });
+    }
+
+    /// 保持しているチャンクの数
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn hash(source: &str) -> u64 {
+        let mut hasher = FxHasher::default();
+        source.hash(&mut hasher);
+        hasher.finish()
+    }
+}
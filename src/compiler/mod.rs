@@ -1,15 +1,34 @@
+mod cache;
+
+pub use cache::CompileCache;
+
 use crate::error::{JSError, JSResult};
-use crate::parser::{BinaryOp, Expression, Literal, Program, Statement, UnaryOp};
-use crate::value::JSValue;
+use crate::parser::{
+    BinaryOp, Expression, ExpressionKind, Literal, Pattern, Program, Statement, StatementKind,
+    UnaryOp, VarKind,
+};
+use crate::value::{JSFunction, JSValue};
+use rustc_hash::{FxHashMap, FxHashSet};
+use std::cell::Cell;
+use std::rc::Rc;
 
 /// バイトコード命令
 #[derive(Debug, Clone, PartialEq)]
 pub enum Opcode {
     // スタック操作
-    LoadConst(usize), // 定数をスタックにロード
-    LoadVar(String),  // 変数をスタックにロード
-    StoreVar(String), // スタックトップを変数に格納
-    Pop,              // スタックトップを削除
+    LoadConst(usize),       // 定数をスタックにロード
+    LoadUndefined,          // undefined をスタックにロード
+    LoadNull,               // null をスタックにロード
+    LoadBool(bool),         // 真偽値をスタックにロード
+    LoadVar(String),        // 変数をスタックにロード
+    LoadThis,               // 現在の関数の this をスタックにロード
+    StoreVar(String),       // スタックトップを変数に格納
+    DefineVar(String),      // スタックトップで現在のスコープに変数を宣言
+    DeclareVar(String),     // var の巻き上げ（未宣言なら undefined で宣言）
+    DeclareLexical(String), // let / const の巻き上げ（未宣言なら TDZ 状態で宣言）
+    Pop,                    // スタックトップを削除
+    Dup,                    // スタックトップを複製
+    Swap,                   // スタックトップの2つを入れ替える
 
     // 算術演算
     Add,
@@ -33,10 +52,8 @@ pub enum Opcode {
     Gt,
     LtEq,
     GtEq,
-
-    // 論理演算
-    And,
-    Or,
+    In,         // key in obj - スタックから obj, key をポップ
+    Instanceof, // value instanceof constructor
 
     // ビット演算
     BitAnd,
@@ -47,27 +64,128 @@ pub enum Opcode {
     UnsignedRightShift,
 
     // 配列・オブジェクト操作
-    NewArray(usize),   // 空の配列を作成（サイズ指定）
-    NewObject,         // 空のオブジェクトを作成
-    GetProperty,       // obj[key] - スタックから key, obj をポップ、結果をプッシュ
-    SetProperty,       // obj[key] = value - スタックから value, key, obj をポップ
-    ArrayPush,         // arr.push(value) - スタックから index, value をポップ、arr は残る
+    NewArray(usize),             // 空の配列を作成（サイズ指定）
+    NewObject,                   // 空のオブジェクトを作成
+    BuildArray(usize),           // スタックから n 個の値をポップして配列を作成
+    BuildSpreadArray(Vec<bool>), // BuildArray と同じだが true の位置の値はイテラブルとして展開する
+    BuildObject(usize),          // スタックから n 組の key, value をポップしてオブジェクトを作成
+    GetProperty,                 // obj[key] - スタックから key, obj をポップ、結果をプッシュ
+    SetProperty, // obj[key] = value - スタックから value, key, obj をポップし value を積む
+    StoreProperty, // SetProperty と同じだが値を積まない（値を使わない代入）
+    GetPropertyByName(String), // obj.name - スタックから obj をポップ、結果をプッシュ
+    SetPropertyByName(String), // obj.name = value - スタックから value, obj をポップし value を積む
+    StorePropertyByName(String), // SetPropertyByName と同じだが値を積まない
+    ArrayPush,   // arr.push(value) - スタックから index, value をポップ、arr は残る
     ObjectSetProperty, // obj[key] = value - スタックから key, value をポップ、obj は残る
 
     // 関数操作
-    CreateFunction(usize), // 定数プール内の関数オブジェクトを生成してプッシュ（func chunk idx）
+    CreateFunction(usize), // 定数プール内の関数テンプレートから現在の環境を捕捉したクロージャを生成してプッシュ
     CallFunction(usize),   // 呼び出し（引数個数） - スタックから argN..arg1, func を使う
+    TailCall(usize), // 末尾位置の呼び出し（引数個数） - CallFunction と同じだが現在のフレームを置き換える
+    CallMethod(usize), // メソッド呼び出し（引数個数） - スタックから argN..arg1, key, obj を使い obj を this とする
+    New(usize),        // new 演算子（引数個数） - スタックから argN..arg1, constructor を使う
+    CallFunctionSpread, // 引数配列での呼び出し - スタックから args（配列）, this, func を使う
+    NewSpread,         // 引数配列での new 演算子 - スタックから args（配列）, constructor を使う
 
     // 制御フロー
     Jump(usize),        // 無条件ジャンプ
     JumpIfFalse(usize), // false の場合ジャンプ
+    JumpIfTrue(usize),  // true の場合ジャンプ
     Return,             // 関数から戻る
 
+    // イテレーション
+    GetIterator,         // スタックトップの値をイテレータに置き換える
+    IteratorNext(usize), // スタックトップのイテレータを進めて値を積む（終了していればジャンプ）
+
+    // 例外処理
+    Throw,           // スタックトップの値を例外として投げる
+    EnterTry(usize), // 例外ハンドラを登録（catch 節の開始位置）
+    LeaveTry,        // 例外ハンドラを解除
+    PushScope,       // 現在の環境を外側とする新しいスコープに入る
+    PopScope,        // 現在のスコープを抜けて外側の環境に戻る
+
     // その他
     Typeof,
     Void,
 }
 
+impl Opcode {
+    /// スタックから取り出す値の数と積む値の数
+    fn stack_effect(&self) -> (usize, usize) {
+        match self {
+            Opcode::LoadConst(_)
+            | Opcode::LoadUndefined
+            | Opcode::LoadNull
+            | Opcode::LoadBool(_)
+            | Opcode::LoadVar(_)
+            | Opcode::LoadThis
+            | Opcode::NewArray(_)
+            | Opcode::NewObject
+            | Opcode::CreateFunction(_) => (0, 1),
+            Opcode::DeclareVar(_)
+            | Opcode::DeclareLexical(_)
+            | Opcode::Jump(_)
+            | Opcode::EnterTry(_)
+            | Opcode::LeaveTry
+            | Opcode::PushScope
+            | Opcode::PopScope => (0, 0),
+            Opcode::StoreVar(_)
+            | Opcode::DefineVar(_)
+            | Opcode::Pop
+            | Opcode::JumpIfFalse(_)
+            | Opcode::JumpIfTrue(_)
+            | Opcode::Return
+            | Opcode::Throw => (1, 0),
+            Opcode::Dup => (1, 2),
+            Opcode::Swap => (2, 2),
+            // 終了時のジャンプ先ではイテレータだけが残る（validate で扱う）
+            Opcode::IteratorNext(_) => (1, 2),
+            Opcode::Neg
+            | Opcode::Not
+            | Opcode::BitNot
+            | Opcode::Typeof
+            | Opcode::Void
+            | Opcode::GetIterator
+            | Opcode::GetPropertyByName(_) => (1, 1),
+            Opcode::Add
+            | Opcode::Sub
+            | Opcode::Mul
+            | Opcode::Div
+            | Opcode::Mod
+            | Opcode::Power
+            | Opcode::Eq
+            | Opcode::NotEq
+            | Opcode::StrictEq
+            | Opcode::StrictNotEq
+            | Opcode::Lt
+            | Opcode::Gt
+            | Opcode::LtEq
+            | Opcode::GtEq
+            | Opcode::In
+            | Opcode::Instanceof
+            | Opcode::BitAnd
+            | Opcode::BitOr
+            | Opcode::BitXor
+            | Opcode::LeftShift
+            | Opcode::RightShift
+            | Opcode::UnsignedRightShift
+            | Opcode::GetProperty
+            | Opcode::SetPropertyByName(_) => (2, 1),
+            // ArrayPush / ObjectSetProperty は対象の配列・オブジェクトを残す
+            Opcode::SetProperty | Opcode::ArrayPush | Opcode::ObjectSetProperty => (3, 1),
+            Opcode::StoreProperty => (3, 0),
+            Opcode::StorePropertyByName(_) => (2, 0),
+            Opcode::BuildArray(n) => (*n, 1),
+            Opcode::BuildSpreadArray(spreads) => (spreads.len(), 1),
+            Opcode::BuildObject(n) => (n * 2, 1),
+            Opcode::CallFunction(n) | Opcode::TailCall(n) | Opcode::New(n) => (n + 1, 1),
+            Opcode::CallMethod(n) => (n + 2, 1),
+            Opcode::CallFunctionSpread => (3, 1),
+            Opcode::NewSpread => (2, 1),
+        }
+    }
+}
+
 /// バイトコードチャンク
 #[derive(Debug, Clone)]
 pub struct BytecodeChunk {
@@ -75,6 +193,29 @@ pub struct BytecodeChunk {
     pub code: Vec<Opcode>,
     /// 定数プール
     pub constants: Vec<JSValue>,
+    /// GetPropertyByName の位置ごとのインラインキャッシュ
+    property_caches: Vec<PropertyCache>,
+    /// 呼び出し命令の位置ごとの呼び出し対象の表記（エラーメッセージ用）
+    callee_names: FxHashMap<usize, String>,
+}
+
+/// 単相のインラインキャッシュ（直前に読んだオブジェクトのシェイプとプロパティの位置）
+#[derive(Debug, Clone, Default)]
+pub struct PropertyCache(Cell<Option<(u64, usize)>>);
+
+impl PropertyCache {
+    /// シェイプが一致すればキャッシュした位置を返す
+    pub fn lookup(&self, shape: u64) -> Option<usize> {
+        match self.0.get() {
+            Some((cached, slot)) if cached == shape => Some(slot),
+            _ => None,
+        }
+    }
+
+    /// 読み出したシェイプと位置を記録
+    pub fn update(&self, shape: u64, slot: usize) {
+        self.0.set(Some((shape, slot)));
+    }
 }
 
 impl BytecodeChunk {
@@ -83,14 +224,41 @@ impl BytecodeChunk {
         Self {
             code: Vec::new(),
             constants: Vec::new(),
+            property_caches: Vec::new(),
+            callee_names: FxHashMap::default(),
         }
     }
 
+    /// 指定位置の GetPropertyByName のインラインキャッシュを取得
+    pub fn property_cache(&self, at: usize) -> Option<&PropertyCache> {
+        self.property_caches.get(at)
+    }
+
+    /// 指定位置の呼び出し命令の呼び出し対象の表記（`foo`・`obj.method` 等）を取得
+    pub fn callee_name(&self, at: usize) -> Option<&str> {
+        self.callee_names.get(&at).map(String::as_str)
+    }
+
+    /// 呼び出し命令を追加し、呼び出し対象の表記があれば記録
+    pub fn emit_call(&mut self, opcode: Opcode, callee_name: Option<String>) {
+        if let Some(name) = callee_name {
+            self.callee_names.insert(self.code.len(), name);
+        }
+        self.emit(opcode);
+    }
+
     /// 定数プールに値を追加し、そのインデックスを返す
+    ///
+    /// 同じ値の定数は再利用する。ただし関数テンプレートはそれぞれ別のチャンクを
+    /// 持つため重複排除せず、常に新しいインデックスを割り当てる。
     pub fn add_constant(&mut self, value: JSValue) -> usize {
-        // 既存の定数を探す
-        for (i, constant) in self.constants.iter().enumerate() {
-            if constant == &value {
+        if !matches!(value, JSValue::Function(_)) {
+            // 既存の定数を探す
+            if let Some(i) = self
+                .constants
+                .iter()
+                .position(|constant| Self::same_constant(constant, &value))
+            {
                 return i;
             }
         }
@@ -101,10 +269,142 @@ impl BytecodeChunk {
         index
     }
 
+    /// 定数として同一か（数値はビット列で比較し、0 と -0 を区別する）
+    ///
+    /// `==`（strict_equals）では NaN が自身と一致せず、NaN の定数が毎回追加されてしまう。
+    fn same_constant(a: &JSValue, b: &JSValue) -> bool {
+        match (a, b) {
+            (JSValue::Number(x), JSValue::Number(y)) => x.to_bits() == y.to_bits(),
+            (JSValue::Function(_), _) | (_, JSValue::Function(_)) => false,
+            _ => a == b,
+        }
+    }
+
     /// バイトコード命令を追加
     pub fn emit(&mut self, opcode: Opcode) {
+        if matches!(opcode, Opcode::GetPropertyByName(_)) {
+            self.property_caches
+                .resize_with(self.code.len() + 1, PropertyCache::default);
+        }
         self.code.push(opcode);
     }
+
+    /// 次に追加される命令の位置を取得
+    pub fn current_offset(&self) -> usize {
+        self.code.len()
+    }
+
+    /// 指定位置のジャンプ系命令の飛び先を書き換える
+    pub fn patch_jump(&mut self, at: usize, target: usize) {
+        match &mut self.code[at] {
+            Opcode::Jump(offset)
+            | Opcode::JumpIfFalse(offset)
+            | Opcode::JumpIfTrue(offset)
+            | Opcode::EnterTry(offset)
+            | Opcode::IteratorNext(offset) => {
+                *offset = target;
+            }
+            other => panic!("patch_jump: not a jump instruction: {:?}", other),
+        }
+    }
+
+    /// 命令列の整合性を検証する
+    ///
+    /// 定数のインデックスとジャンプ先が範囲内にあること、どの経路でも
+    /// スタックが不足せず合流点で深さが一致することを確かめる。
+    pub fn validate(&self) -> JSResult<()> {
+        let invalid = |pc: usize, message: String| {
+            Err(JSError::InternalError(format!(
+                "Invalid bytecode at {} ({:?}): {}",
+                pc, self.code[pc], message
+            )))
+        };
+
+        // 各位置に到達した時点のスタックの深さ（終端を含む）
+        let mut depths: Vec<Option<usize>> = vec![None; self.code.len() + 1];
+        let mut pending = vec![(0, 0)];
+        while let Some((pc, depth)) = pending.pop() {
+            match depths[pc] {
+                Some(known) if known == depth => continue,
+                Some(known) => {
+                    return Err(JSError::InternalError(format!(
+                        "Invalid bytecode at {}: stack depth {} does not match {} on another path",
+                        pc, depth, known
+                    )));
+                }
+                None => depths[pc] = Some(depth),
+            }
+            let Some(opcode) = self.code.get(pc) else {
+                if depth > 1 {
+                    return Err(JSError::InternalError(format!(
+                        "Invalid bytecode at end: {} values left on the stack",
+                        depth
+                    )));
+                }
+                continue;
+            };
+
+            match opcode {
+                Opcode::LoadConst(idx) | Opcode::CreateFunction(idx)
+                    if *idx >= self.constants.len() =>
+                {
+                    return invalid(
+                        pc,
+                        format!(
+                            "constant index out of bounds (pool size {})",
+                            self.constants.len()
+                        ),
+                    );
+                }
+                Opcode::CreateFunction(idx)
+                    if !matches!(self.constants[*idx], JSValue::Function(_)) =>
+                {
+                    return invalid(pc, "constant is not a function template".to_string());
+                }
+                Opcode::Jump(target)
+                | Opcode::JumpIfFalse(target)
+                | Opcode::JumpIfTrue(target)
+                | Opcode::EnterTry(target)
+                | Opcode::IteratorNext(target)
+                    if *target > self.code.len() =>
+                {
+                    return invalid(
+                        pc,
+                        format!("jump target out of range (code size {})", self.code.len()),
+                    );
+                }
+                _ => {}
+            }
+
+            let (pops, pushes) = opcode.stack_effect();
+            if depth < pops {
+                return invalid(
+                    pc,
+                    format!("stack underflow (needs {}, has {})", pops, depth),
+                );
+            }
+            let next = depth - pops + pushes;
+            match opcode {
+                Opcode::Jump(target) => pending.push((*target, next)),
+                Opcode::JumpIfFalse(target) | Opcode::JumpIfTrue(target) => {
+                    pending.push((*target, next));
+                    pending.push((pc + 1, next));
+                }
+                // catch 節は投げられた値を1つ積んだ状態で始まる
+                Opcode::EnterTry(target) => {
+                    pending.push((*target, depth + 1));
+                    pending.push((pc + 1, next));
+                }
+                Opcode::IteratorNext(target) => {
+                    pending.push((*target, depth));
+                    pending.push((pc + 1, next));
+                }
+                Opcode::Return | Opcode::Throw => {}
+                _ => pending.push((pc + 1, next)),
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Default for BytecodeChunk {
@@ -114,10 +414,67 @@ impl Default for BytecodeChunk {
     }
 }
 
+/// コンパイル中のループ（break / continue の飛び先はループの終わりで書き換える）
+struct LoopContext {
+    /// 完了値をスタックに置いているか（is_last のループ）
+    keeps_completion: bool,
+    /// for-of のようにイテレータをスタックに置いているか
+    holds_iterator: bool,
+    /// ループに入った時点の try_depth
+    try_depth: usize,
+    /// ループに入った時点の scope_depth
+    scope_depth: usize,
+    /// break の Jump 命令の位置
+    breaks: Vec<usize>,
+    /// continue の Jump 命令の位置
+    continues: Vec<usize>,
+}
+
+impl LoopContext {
+    fn new(
+        keeps_completion: bool,
+        holds_iterator: bool,
+        try_depth: usize,
+        scope_depth: usize,
+    ) -> Self {
+        Self {
+            keeps_completion,
+            holds_iterator,
+            try_depth,
+            scope_depth,
+            breaks: Vec::new(),
+            continues: Vec::new(),
+        }
+    }
+}
+
+/// 左結合の連鎖（二項演算・メンバー参照・呼び出し）の1段のうち、左辺より後の部分
+enum ChainLink {
+    /// 二項演算の右辺（&& と || を含む）
+    Binary { op: BinaryOp, right: Expression },
+    /// メンバー参照のプロパティ
+    Member {
+        property: Expression,
+        computed: bool,
+    },
+    /// 呼び出し（method があればメソッド呼び出しで、左辺は this となるオブジェクト）
+    Call {
+        method: Option<Expression>,
+        args: Vec<Expression>,
+        callee_name: Option<String>,
+    },
+}
+
 /// コンパイラ
 pub struct Compiler {
     /// 生成されたバイトコードチャンク
     chunk: BytecodeChunk,
+    /// コンパイル中の位置を囲む try 節の数（末尾呼び出しにできるのは 0 のときだけ）
+    try_depth: usize,
+    /// コンパイル中の位置を囲む、PushScope で作ったスコープの数
+    scope_depth: usize,
+    /// コンパイル中の位置を囲むループ（内側ほど後ろ）
+    loops: Vec<LoopContext>,
 }
 
 impl Compiler {
@@ -125,247 +482,1248 @@ impl Compiler {
     pub fn new() -> Self {
         Self {
             chunk: BytecodeChunk::new(),
+            try_depth: 0,
+            scope_depth: 0,
+            loops: Vec::new(),
         }
     }
 
     /// ASTをバイトコードにコンパイル
     pub fn compile(&mut self, program: Program) -> JSResult<BytecodeChunk> {
-        let len = program.body.len();
-        for (i, statement) in program.body.into_iter().enumerate() {
-            let is_last = i == len - 1;
-            self.compile_statement(statement, is_last)?;
+        self.declare_bindings(&program.body)?;
+        self.compile_statements(program.body, true)?;
+        if cfg!(debug_assertions) {
+            self.chunk.validate()?;
+        }
+
+        Ok(self.chunk.clone())
+    }
+
+    /// 関数本体をコンパイル（末尾に到達した場合は undefined を返す）
+    pub fn compile_function_body(
+        &mut self,
+        params: &[String],
+        body: Vec<Statement>,
+    ) -> JSResult<BytecodeChunk> {
+        Self::check_parameter_redeclarations(params, &body)?;
+        self.declare_bindings(&body)?;
+        self.compile_statements(body, false)?;
+        self.chunk.emit(Opcode::LoadUndefined);
+        self.chunk.emit(Opcode::Return);
+        if cfg!(debug_assertions) {
+            self.chunk.validate()?;
         }
 
         Ok(self.chunk.clone())
     }
 
+    /// スコープ先頭で変数を宣言する
+    ///
+    /// var は入れ子のブロックの中のものも undefined で初期化して巻き上げ、let / const は
+    /// 宣言に到達するまで参照できない状態（TDZ）で作成する。
+    fn declare_bindings(&mut self, body: &[Statement]) -> JSResult<()> {
+        let mut names = Vec::new();
+        Self::hoisted_var_names(body, &mut names);
+        for name in names {
+            self.chunk.emit(Opcode::DeclareVar(name));
+        }
+        self.declare_lexical_bindings(body)
+    }
+
+    /// 文の列の直下の let / const を、現在のスコープに TDZ の状態で宣言する
+    fn declare_lexical_bindings(&mut self, body: &[Statement]) -> JSResult<()> {
+        Self::check_redeclarations(body)?;
+        for name in Self::lexical_names(body) {
+            self.chunk.emit(Opcode::DeclareLexical(name));
+        }
+        Ok(())
+    }
+
+    /// 文の列の直下で let / const により宣言される名前（入れ子のブロックには入らない）
+    fn lexical_names(body: &[Statement]) -> Vec<String> {
+        body.iter()
+            .filter_map(|statement| {
+                let statement = match &statement.kind {
+                    StatementKind::Export(declaration) => declaration,
+                    _ => statement,
+                };
+                match &statement.kind {
+                    StatementKind::VariableDeclaration {
+                        kind: VarKind::Let | VarKind::Const,
+                        name,
+                        ..
+                    } => Some(name.clone()),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+
+    /// 仮引数（catch の仮引数を含む）と同名の let / const を本体の直下で宣言していれば SyntaxError
+    fn check_parameter_redeclarations(params: &[String], body: &[Statement]) -> JSResult<()> {
+        match Self::lexical_names(body)
+            .into_iter()
+            .find(|name| params.contains(name))
+        {
+            Some(name) => Err(Self::redeclaration(&name)),
+            None => Ok(()),
+        }
+    }
+
+    /// 同じ文の列で let / const と同名の宣言があれば SyntaxError（var 同士の再宣言は許す）
+    ///
+    /// 入れ子のブロックから巻き上がる var も、この列の let / const と比べる。
+    fn check_redeclarations(body: &[Statement]) -> JSResult<()> {
+        let mut lexical = FxHashSet::default();
+        let mut others = FxHashSet::default();
+        for statement in body {
+            let statement = match &statement.kind {
+                StatementKind::Export(declaration) => declaration,
+                _ => statement,
+            };
+            let (name, is_lexical) = match &statement.kind {
+                StatementKind::VariableDeclaration { kind, name, .. } => {
+                    (name, matches!(kind, VarKind::Let | VarKind::Const))
+                }
+                StatementKind::FunctionDeclaration { name, .. } => (name, false),
+                _ => continue,
+            };
+            let duplicate = if is_lexical {
+                !lexical.insert(name) || others.contains(name)
+            } else {
+                others.insert(name);
+                lexical.contains(name)
+            };
+            if duplicate {
+                return Err(Self::redeclaration(name));
+            }
+        }
+        // ブロックの中の var も文の列の先頭まで巻き上がるため、この列の let / const と衝突する
+        let mut hoisted = Vec::new();
+        Self::hoisted_var_names(body, &mut hoisted);
+        match hoisted.iter().find(|name| lexical.contains(name)) {
+            Some(name) => Err(Self::redeclaration(name)),
+            None => Ok(()),
+        }
+    }
+
+    fn redeclaration(name: &str) -> JSError {
+        JSError::SyntaxError(format!("Identifier '{}' has already been declared", name))
+    }
+
+    /// 文の列から巻き上がる var の名前を、入れ子のブロックも含めて集める（関数本体には入らない）
+    fn hoisted_var_names(body: &[Statement], names: &mut Vec<String>) {
+        for statement in body {
+            match &statement.kind {
+                StatementKind::VariableDeclaration {
+                    kind: VarKind::Var,
+                    name,
+                    ..
+                } => names.push(name.clone()),
+                StatementKind::Block(body) | StatementKind::While { body, .. } => {
+                    Self::hoisted_var_names(body, names)
+                }
+                StatementKind::Try { block, handler, .. } => {
+                    Self::hoisted_var_names(block, names);
+                    Self::hoisted_var_names(handler, names);
+                }
+                StatementKind::If {
+                    consequent,
+                    alternate,
+                    ..
+                } => {
+                    Self::hoisted_var_names(consequent, names);
+                    if let Some(alternate) = alternate {
+                        Self::hoisted_var_names(alternate, names);
+                    }
+                }
+                StatementKind::For { init, body, .. } => {
+                    if let Some(init) = init {
+                        Self::hoisted_var_names(std::slice::from_ref(init), names);
+                    }
+                    Self::hoisted_var_names(body, names);
+                }
+                StatementKind::ForOf {
+                    kind,
+                    pattern,
+                    body,
+                    ..
+                } => {
+                    if matches!(kind, VarKind::Var) {
+                        pattern.bound_names(names);
+                    }
+                    Self::hoisted_var_names(body, names);
+                }
+                StatementKind::Export(declaration) => {
+                    Self::hoisted_var_names(std::slice::from_ref(declaration), names)
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// 文の列をコンパイル（is_last の場合は最後の文の値をスタックに残す）
+    ///
+    /// 関数宣言は巻き上げ、他の文より先にスコープへ束縛する。
+    fn compile_statements(&mut self, body: Vec<Statement>, is_last: bool) -> JSResult<()> {
+        let (functions, rest): (Vec<_>, Vec<_>) =
+            body.into_iter()
+                .partition(|statement| match &statement.kind {
+                    StatementKind::Export(declaration) => {
+                        matches!(declaration.kind, StatementKind::FunctionDeclaration { .. })
+                    }
+                    kind => matches!(kind, StatementKind::FunctionDeclaration { .. }),
+                });
+
+        // 値を残すのは関数宣言以外の最後の文だけ
+        let hoisted = functions.len();
+        let len = hoisted + rest.len();
+        for (i, statement) in functions.into_iter().chain(rest).enumerate() {
+            self.compile_statement(statement, is_last && i >= hoisted && i + 1 == len)?;
+        }
+        Ok(())
+    }
+
+    /// ブロックをコンパイル（値を残す文がなくても is_last なら undefined を残す）
+    ///
+    /// let / const を宣言するブロックは PushScope で自身のスコープを作り、外側の同名の束縛を隠す。
+    fn compile_block(&mut self, body: Vec<Statement>, is_last: bool) -> JSResult<()> {
+        let has_value = body
+            .iter()
+            .any(|statement| !matches!(statement.kind, StatementKind::FunctionDeclaration { .. }));
+        if is_last && !has_value {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        if Self::lexical_names(&body).is_empty() {
+            Self::check_redeclarations(&body)?;
+            return self.compile_statements(body, is_last);
+        }
+
+        self.chunk.emit(Opcode::PushScope);
+        self.declare_lexical_bindings(&body)?;
+        self.scope_depth += 1;
+        let result = self.compile_statements(body, is_last);
+        self.scope_depth -= 1;
+        result?;
+        self.chunk.emit(Opcode::PopScope);
+        Ok(())
+    }
+
     /// ステートメントをコンパイル
     fn compile_statement(&mut self, statement: Statement, is_last: bool) -> JSResult<()> {
-        match statement {
-            Statement::Expression(expr) => {
+        match statement.kind {
+            StatementKind::Expression(expr) => self.compile_expression_statement(expr, is_last),
+            StatementKind::VariableDeclaration { kind, name, init } => {
+                self.compile_variable_declaration(kind, name, init, is_last)
+            }
+            StatementKind::Return(expr) => self.compile_return(expr),
+            StatementKind::FunctionDeclaration { name, params, body } => {
+                self.compile_function_declaration(name, params, body)
+            }
+            StatementKind::Throw(expr) => {
                 self.compile_expression(expr)?;
+                self.chunk.emit(Opcode::Throw);
+                Ok(())
+            }
+            // 完了値はブロック内の最後の文の値
+            StatementKind::Block(body) => self.compile_block(body, is_last),
+            StatementKind::Try {
+                block,
+                param,
+                handler,
+            } => self.compile_try(block, param, handler, is_last),
+            StatementKind::If {
+                test,
+                consequent,
+                alternate,
+            } => self.compile_if(test, consequent, alternate, is_last),
+            StatementKind::While { test, body } => self.compile_while(test, body, is_last),
+            StatementKind::For {
+                init,
+                test,
+                update,
+                body,
+            } => self.compile_for(init, test, update, body, is_last),
+            StatementKind::Break => self.compile_loop_jump(true),
+            StatementKind::Continue => self.compile_loop_jump(false),
+            StatementKind::ForOf {
+                kind,
+                pattern,
+                iterable,
+                body,
+            } => self.compile_for_of(kind, pattern, iterable, body, is_last),
+            // エクスポートした束縛はまだ外部から参照できないため、宣言としてだけ扱う
+            StatementKind::Export(declaration) => self.compile_statement(*declaration, is_last),
+            StatementKind::Import { source, .. } => Err(Self::unsupported_import(&source)),
+        }
+    }
+
+    /// 式文をコンパイル
+    fn compile_expression_statement(&mut self, expr: Expression, is_last: bool) -> JSResult<()> {
+        let span = expr.span;
+        match expr.into_kind() {
+            // 値を使わない代入式は、代入した値を複製して残さない
+            ExpressionKind::Assignment { left, right } if !is_last => {
+                self.compile_assignment(*left, *right, false)?;
+            }
+            kind => {
+                self.compile_expression(Expression::new(kind, span))?;
                 // 最後の式文の結果はスタックに残す（REPLスタイル）
                 if !is_last {
                     self.chunk.emit(Opcode::Pop);
                 }
             }
-            Statement::VariableDeclaration {
-                kind: _,
-                name,
-                init,
-            } => {
-                if let Some(expr) = init {
-                    self.compile_expression(expr)?;
-                } else {
-                    // 初期化なしの場合はundefined
-                    let idx = self.chunk.add_constant(JSValue::Undefined);
-                    self.chunk.emit(Opcode::LoadConst(idx));
-                }
-                self.chunk.emit(Opcode::StoreVar(name));
+        }
+        Ok(())
+    }
 
-                // 変数宣言の文は常にundefinedを返す
-                if is_last {
-                    let idx = self.chunk.add_constant(JSValue::Undefined);
-                    self.chunk.emit(Opcode::LoadConst(idx));
-                }
+    /// 変数宣言をコンパイル
+    fn compile_variable_declaration(
+        &mut self,
+        kind: VarKind,
+        name: String,
+        init: Option<Expression>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        if let Some(expr) = init {
+            self.compile_expression(expr)?;
+            let opcode = self.initialize_opcode(kind, name);
+            self.chunk.emit(opcode);
+        } else if kind != VarKind::Var {
+            // 初期化なしの let はundefinedで初期化（var は巻き上げ済み）
+            self.chunk.emit(Opcode::LoadUndefined);
+            self.chunk.emit(Opcode::DefineVar(name));
+        }
+
+        // 変数宣言の文は常にundefinedを返す
+        if is_last {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        Ok(())
+    }
+
+    /// 宣言した変数をスタックトップの値で初期化する命令
+    ///
+    /// var は巻き上げ済みの関数スコープの束縛に代入する（ブロックや catch 節のスコープ内でも同じ）。
+    fn initialize_opcode(&self, kind: VarKind, name: String) -> Opcode {
+        match kind {
+            VarKind::Var if self.scope_depth > 0 => Opcode::StoreVar(name),
+            _ => Opcode::DefineVar(name),
+        }
+    }
+
+    /// return 文をコンパイル
+    fn compile_return(&mut self, expr: Option<Expression>) -> JSResult<()> {
+        if let Some(expr) = expr {
+            self.compile_tail_expression(expr)?;
+        } else {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        self.chunk.emit(Opcode::Return);
+        Ok(())
+    }
+
+    /// モジュールの読み込みは未対応
+    fn unsupported_import(source: &str) -> JSError {
+        JSError::TypeError(format!(
+            "Failed to resolve module specifier '{}': module loading is not supported",
+            source
+        ))
+    }
+
+    /// 関数宣言をコンパイル
+    fn compile_function_declaration(
+        &mut self,
+        name: String,
+        params: Vec<String>,
+        body: Vec<Statement>,
+    ) -> JSResult<()> {
+        // 関数本体をコンパイル
+        let function_chunk = Compiler::new().compile_function_body(&params, body)?;
+
+        // 現在のチャンクに関数テンプレートを追加
+        let idx = self
+            .chunk
+            .add_constant(JSValue::Function(Rc::new(JSFunction::new(
+                function_chunk,
+                params,
+            ))));
+        self.chunk.emit(Opcode::CreateFunction(idx));
+
+        // 関数名を現在のスコープに宣言
+        self.chunk.emit(Opcode::DefineVar(name));
+        Ok(())
+    }
+
+    /// try 文をコンパイル
+    fn compile_try(
+        &mut self,
+        block: Vec<Statement>,
+        param: Option<String>,
+        handler: Vec<Statement>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        // EnterTry(catch) / try本体 / LeaveTry / Jump(end) /
+        // catch: PushScope / 束縛 / 本体 / PopScope / end:
+        let enter_try = self.chunk.current_offset();
+        self.chunk.emit(Opcode::EnterTry(0));
+        // try 本体の呼び出しは catch 節へ戻れるよう末尾呼び出しにしない
+        self.try_depth += 1;
+        let result = self.compile_block(block, is_last);
+        self.try_depth -= 1;
+        result?;
+        self.chunk.emit(Opcode::LeaveTry);
+        let jump_to_end = self.chunk.current_offset();
+        self.chunk.emit(Opcode::Jump(0));
+
+        // catch 節の開始時点では、投げられた値がスタックトップにある
+        let catch_start = self.chunk.current_offset();
+        self.chunk.patch_jump(enter_try, catch_start);
+        match param {
+            // catch の仮引数は catch 節だけのスコープに束縛し、外側の同名の変数を隠す
+            Some(name) => {
+                Self::check_parameter_redeclarations(std::slice::from_ref(&name), &handler)?;
+                self.chunk.emit(Opcode::PushScope);
+                self.chunk.emit(Opcode::DefineVar(name));
+                self.scope_depth += 1;
+                let result = self.compile_block(handler, is_last);
+                self.scope_depth -= 1;
+                result?;
+                self.chunk.emit(Opcode::PopScope);
             }
-            Statement::Return(expr) => {
-                if let Some(expr) = expr {
-                    self.compile_expression(expr)?;
-                } else {
-                    let idx = self.chunk.add_constant(JSValue::Undefined);
-                    self.chunk.emit(Opcode::LoadConst(idx));
-                }
-                self.chunk.emit(Opcode::Return);
+            None => {
+                self.chunk.emit(Opcode::Pop);
+                self.compile_block(handler, is_last)?;
             }
-            Statement::FunctionDeclaration { name, params, body } => {
-                // 関数本体をコンパイル
-                let program = Program { body };
-                let function_chunk = Compiler::new().compile(program)?;
+        }
 
-                // 現在のチャンクに関数を追加 (chunk, params)
-                let idx = self
-                    .chunk
-                    .add_constant(JSValue::Function(function_chunk, params.clone()));
-                self.chunk.emit(Opcode::CreateFunction(idx));
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_end, end);
+        Ok(())
+    }
 
-                // 関数名を変数としてストア
-                self.chunk.emit(Opcode::StoreVar(name));
-            }
+    /// if 文をコンパイル
+    fn compile_if(
+        &mut self,
+        test: Expression,
+        consequent: Vec<Statement>,
+        alternate: Option<Vec<Statement>>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        // test / JumpIfFalse(else) / consequent / Jump(end) / else: alternate / end:
+        self.compile_expression(test)?;
+        let jump_to_else = self.chunk.current_offset();
+        self.chunk.emit(Opcode::JumpIfFalse(0));
+        self.compile_block(consequent, is_last)?;
+        let jump_to_end = self.chunk.current_offset();
+        self.chunk.emit(Opcode::Jump(0));
+
+        let else_start = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_else, else_start);
+        self.compile_block(alternate.unwrap_or_default(), is_last)?;
+
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_end, end);
+        Ok(())
+    }
+
+    /// while 文をコンパイル
+    fn compile_while(
+        &mut self,
+        test: Expression,
+        body: Vec<Statement>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        // 完了値（最後に実行した本体の値、一度も実行しなければ undefined）を
+        // スタックに置き、本体を実行するたびに置き換える
+        // [undefined] / start: test / JumpIfFalse(end) / [Pop] / body / Jump(start) / end:
+        if is_last {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        let loop_start = self.chunk.current_offset();
+        self.compile_expression(test)?;
+        let exit_jump = self.chunk.current_offset();
+        self.chunk.emit(Opcode::JumpIfFalse(0));
+        if is_last {
+            self.chunk.emit(Opcode::Pop);
         }
+        self.loops.push(LoopContext::new(
+            is_last,
+            false,
+            self.try_depth,
+            self.scope_depth,
+        ));
+        self.compile_block(body, is_last)?;
+        self.chunk.emit(Opcode::Jump(loop_start));
+
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(exit_jump, end);
+        self.exit_loop(loop_start, end);
         Ok(())
     }
 
-    /// 式をコンパイル
-    fn compile_expression(&mut self, expression: Expression) -> JSResult<()> {
-        match expression {
-            Expression::Literal(lit) => {
-                let value = match lit {
-                    Literal::Undefined => JSValue::Undefined,
-                    Literal::Null => JSValue::Null,
-                    Literal::Boolean(b) => JSValue::Boolean(b),
-                    Literal::Number(n) => JSValue::Number(n),
-                    Literal::String(s) => JSValue::String(s),
-                };
-                let idx = self.chunk.add_constant(value);
-                self.chunk.emit(Opcode::LoadConst(idx));
+    /// for 文をコンパイル
+    ///
+    /// 初期化部の let / const はループ全体を囲むスコープに宣言し、反復ごとにそのスコープを
+    /// 前の反復の値を写した新しいスコープに取り替える（CreatePerIterationEnvironment）。
+    fn compile_for(
+        &mut self,
+        init: Option<Box<Statement>>,
+        test: Option<Expression>,
+        update: Option<Expression>,
+        body: Vec<Statement>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        // while と同様に完了値を置く（test を省略した場合は常に本体へ進む）
+        // [PushScope / 宣言] / init / [copy] / [undefined] / start: [test / JumpIfFalse(end)] /
+        // [Pop] / body / continue: [copy] / [update / Pop] / Jump(start) / end: [PopScope]
+        let names = match &init {
+            Some(init) => Self::lexical_names(std::slice::from_ref(init)),
+            None => Vec::new(),
+        };
+        if !names.is_empty() {
+            self.chunk.emit(Opcode::PushScope);
+            for name in &names {
+                self.chunk.emit(Opcode::DeclareLexical(name.clone()));
             }
-            Expression::Identifier(name) => {
-                self.chunk.emit(Opcode::LoadVar(name));
+            self.scope_depth += 1;
+        }
+        if let Some(init) = init {
+            self.compile_statement(*init, false)?;
+            self.copy_iteration_scope(&names);
+        }
+        if is_last {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        let loop_start = self.chunk.current_offset();
+        let exit_jump = match test {
+            Some(test) => {
+                self.compile_expression(test)?;
+                let exit_jump = self.chunk.current_offset();
+                self.chunk.emit(Opcode::JumpIfFalse(0));
+                Some(exit_jump)
             }
-            Expression::Binary { op, left, right } => {
-                self.compile_expression(*left)?;
-                self.compile_expression(*right)?;
-
-                let opcode = match op {
-                    BinaryOp::Add => Opcode::Add,
-                    BinaryOp::Sub => Opcode::Sub,
-                    BinaryOp::Mul => Opcode::Mul,
-                    BinaryOp::Div => Opcode::Div,
-                    BinaryOp::Mod => Opcode::Mod,
-                    BinaryOp::Power => Opcode::Power,
-                    BinaryOp::Eq => Opcode::Eq,
-                    BinaryOp::NotEq => Opcode::NotEq,
-                    BinaryOp::StrictEq => Opcode::StrictEq,
-                    BinaryOp::StrictNotEq => Opcode::StrictNotEq,
-                    BinaryOp::Lt => Opcode::Lt,
-                    BinaryOp::Gt => Opcode::Gt,
-                    BinaryOp::LtEq => Opcode::LtEq,
-                    BinaryOp::GtEq => Opcode::GtEq,
-                    BinaryOp::And => Opcode::And,
-                    BinaryOp::Or => Opcode::Or,
-                    BinaryOp::BitAnd => Opcode::BitAnd,
-                    BinaryOp::BitOr => Opcode::BitOr,
-                    BinaryOp::BitXor => Opcode::BitXor,
-                    BinaryOp::LeftShift => Opcode::LeftShift,
-                    BinaryOp::RightShift => Opcode::RightShift,
-                    BinaryOp::UnsignedRightShift => Opcode::UnsignedRightShift,
-                };
-                self.chunk.emit(opcode);
+            None => None,
+        };
+        if is_last {
+            self.chunk.emit(Opcode::Pop);
+        }
+        // break はループのスコープを抜ける end へ、continue は同じスコープの update へ飛ぶ
+        self.loops.push(LoopContext::new(
+            is_last,
+            false,
+            self.try_depth,
+            self.scope_depth,
+        ));
+        self.compile_block(body, is_last)?;
+
+        let continue_target = self.chunk.current_offset();
+        self.copy_iteration_scope(&names);
+        if let Some(update) = update {
+            self.compile_expression(update)?;
+            self.chunk.emit(Opcode::Pop);
+        }
+        self.chunk.emit(Opcode::Jump(loop_start));
+
+        let end = self.chunk.current_offset();
+        if let Some(exit_jump) = exit_jump {
+            self.chunk.patch_jump(exit_jump, end);
+        }
+        self.exit_loop(continue_target, end);
+        if !names.is_empty() {
+            self.scope_depth -= 1;
+            self.chunk.emit(Opcode::PopScope);
+        }
+        Ok(())
+    }
+
+    /// for 文のスコープを新しいスコープに取り替え、ループ変数に現在の値を写す
+    ///
+    /// 前の反復で作成したクロージャは、取り替える前のスコープの値を捕捉したまま残る。
+    fn copy_iteration_scope(&mut self, names: &[String]) {
+        if names.is_empty() {
+            return;
+        }
+        for name in names {
+            self.chunk.emit(Opcode::LoadVar(name.clone()));
+        }
+        self.chunk.emit(Opcode::PopScope);
+        self.chunk.emit(Opcode::PushScope);
+        for name in names.iter().rev() {
+            self.chunk.emit(Opcode::DefineVar(name.clone()));
+        }
+    }
+
+    /// for-of 文をコンパイル
+    ///
+    /// let / const の束縛は反復ごとに PushScope で作る新しいスコープに置き、
+    /// 本体で作成したクロージャがそれぞれの反復の値を捕捉するようにする。
+    fn compile_for_of(
+        &mut self,
+        kind: VarKind,
+        pattern: Pattern,
+        iterable: Expression,
+        body: Vec<Statement>,
+        is_last: bool,
+    ) -> JSResult<()> {
+        // ループ中はイテレータをスタックに置き、is_last の場合はその下に完了値を置く
+        // [undefined] / iterable / GetIterator / start: IteratorNext(end) / [PushScope] / bind /
+        // [Swap / Pop] / body / [Swap] / [PopScope] / Jump(start) / end: Pop
+        if is_last {
+            self.chunk.emit(Opcode::LoadUndefined);
+        }
+        self.compile_expression(iterable)?;
+        self.chunk.emit(Opcode::GetIterator);
+        let loop_start = self.chunk.current_offset();
+        self.chunk.emit(Opcode::IteratorNext(0));
+        // break / continue は反復のスコープも抜ける
+        self.loops.push(LoopContext::new(
+            is_last,
+            true,
+            self.try_depth,
+            self.scope_depth,
+        ));
+        let per_iteration = kind != VarKind::Var;
+        if per_iteration {
+            self.chunk.emit(Opcode::PushScope);
+            self.scope_depth += 1;
+        }
+        self.compile_pattern(kind, pattern);
+        let result = if is_last {
+            // 前回の完了値を捨て、本体の値をイテレータの下に置く
+            self.chunk.emit(Opcode::Swap);
+            self.chunk.emit(Opcode::Pop);
+            let result = self.compile_block(body, true);
+            self.chunk.emit(Opcode::Swap);
+            result
+        } else {
+            self.compile_block(body, false)
+        };
+        if per_iteration {
+            self.scope_depth -= 1;
+        }
+        result?;
+        if per_iteration {
+            self.chunk.emit(Opcode::PopScope);
+        }
+        self.chunk.emit(Opcode::Jump(loop_start));
+
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(loop_start, end);
+        self.exit_loop(loop_start, end);
+        self.chunk.emit(Opcode::Pop);
+        Ok(())
+    }
+
+    /// break / continue をコンパイル（最も内側のループの終端・次の反復へジャンプする）
+    ///
+    /// ループ内で入った try 節のハンドラを解除してスコープを抜け、飛び先で期待されるスタックの形に揃える。
+    /// 完了値を置くループでは、break / continue した反復の完了値は undefined とする。
+    fn compile_loop_jump(&mut self, is_break: bool) -> JSResult<()> {
+        let Some(context) = self.loops.last() else {
+            let message = if is_break {
+                "Illegal break statement"
+            } else {
+                "Illegal continue statement: no surrounding iteration statement"
+            };
+            return Err(JSError::SyntaxError(message.to_string()));
+        };
+        let (keeps_completion, holds_iterator) = (context.keeps_completion, context.holds_iterator);
+        for _ in context.try_depth..self.try_depth {
+            self.chunk.emit(Opcode::LeaveTry);
+        }
+        for _ in context.scope_depth..self.scope_depth {
+            self.chunk.emit(Opcode::PopScope);
+        }
+        if keeps_completion {
+            self.chunk.emit(Opcode::LoadUndefined);
+            // 完了値はイテレータの下に置く
+            if holds_iterator {
+                self.chunk.emit(Opcode::Swap);
             }
-            Expression::Unary { op, arg } => {
-                self.compile_expression(*arg)?;
-
-                let opcode = match op {
-                    UnaryOp::Plus => return Ok(()), // +x は x と同じ
-                    UnaryOp::Minus => Opcode::Neg,
-                    UnaryOp::Not => Opcode::Not,
-                    UnaryOp::BitNot => Opcode::BitNot,
-                    UnaryOp::Typeof => Opcode::Typeof,
-                    UnaryOp::Void => Opcode::Void,
-                    UnaryOp::Delete => {
-                        // Delete は現時点では未実装
-                        return Err(JSError::InternalError(
-                            "delete operator not yet implemented".to_string(),
-                        ));
-                    }
-                };
-                self.chunk.emit(opcode);
+        }
+        let at = self.chunk.current_offset();
+        self.chunk.emit(Opcode::Jump(0));
+        let context = self.loops.last_mut().expect("loop context");
+        if is_break {
+            context.breaks.push(at);
+        } else {
+            context.continues.push(at);
+        }
+        Ok(())
+    }
+
+    /// ループを抜け、break / continue のジャンプ先を書き換える
+    fn exit_loop(&mut self, continue_target: usize, end: usize) {
+        let context = self.loops.pop().expect("loop context");
+        for at in context.breaks {
+            self.chunk.patch_jump(at, end);
+        }
+        for at in context.continues {
+            self.chunk.patch_jump(at, continue_target);
+        }
+    }
+
+    /// 代入式をコンパイル（keep_value の場合は代入した値をスタックに残す）
+    fn compile_assignment(
+        &mut self,
+        left: Expression,
+        right: Expression,
+        keep_value: bool,
+    ) -> JSResult<()> {
+        match left.into_kind() {
+            ExpressionKind::Identifier(name) => {
+                self.compile_expression(right)?;
+                if keep_value {
+                    self.chunk.emit(Opcode::Dup);
+                }
+                self.chunk.emit(Opcode::StoreVar(name));
             }
-            Expression::Assignment { left, right } => {
-                match *left {
-                    Expression::Identifier(name) => {
-                        self.compile_expression(*right)?;
-                        self.chunk.emit(Opcode::StoreVar(name.clone()));
-                        self.chunk.emit(Opcode::LoadVar(name));
-                    }
-                    Expression::MemberAccess {
-                        object,
-                        property,
-                        computed,
-                    } => {
-                        // obj[prop] = value の形式
-                        // スタック順序: [obj, key, value]
-                        self.compile_expression(*object)?;
-                        if computed {
-                            self.compile_expression(*property)?;
+            ExpressionKind::MemberAccess {
+                object,
+                property,
+                computed,
+            } => {
+                // obj[prop] = value の形式
+                // スタック順序: [obj, key, value]（名前が定数なら [obj, value]）
+                self.compile_expression(*object)?;
+                // 値を使わない場合は値を積まない Store 系の命令で代入する
+                match Self::property_name(computed, *property) {
+                    Ok(name) => {
+                        self.compile_expression(right)?;
+                        self.chunk.emit(if keep_value {
+                            Opcode::SetPropertyByName(name)
                         } else {
-                            // obj.prop の場合、property は文字列リテラル
-                            self.compile_expression(*property)?;
-                        }
-                        self.compile_expression(*right)?;
-                        self.chunk.emit(Opcode::SetProperty);
+                            Opcode::StorePropertyByName(name)
+                        });
                     }
-                    _ => {
-                        return Err(JSError::SyntaxError(
-                            "Invalid assignment target".to_string(),
-                        ));
+                    Err(property) => {
+                        self.compile_expression(property)?;
+                        self.compile_expression(right)?;
+                        self.chunk.emit(if keep_value {
+                            Opcode::SetProperty
+                        } else {
+                            Opcode::StoreProperty
+                        });
                     }
                 }
             }
-            Expression::ArrayLiteral(elements) => {
-                // 空の配列を作成してスタックにプッシュ
-                self.chunk.emit(Opcode::NewArray(0));
+            _ => {
+                return Err(JSError::SyntaxError(
+                    "Invalid left-hand side in assignment".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
 
-                // 各要素をコンパイルして配列に追加
+    /// スタックトップの値をパターンに従って束縛する（値はポップする）
+    fn compile_pattern(&mut self, kind: VarKind, pattern: Pattern) {
+        match pattern {
+            Pattern::Identifier(name) => {
+                let opcode = self.initialize_opcode(kind, name);
+                self.chunk.emit(opcode);
+            }
+            Pattern::Array(elements) => {
                 for (i, element) in elements.into_iter().enumerate() {
-                    // 値をコンパイル
-                    self.compile_expression(element)?;
-                    // インデックスをプッシュ
-                    let idx = self.chunk.add_constant(JSValue::Number(i as f64));
-                    self.chunk.emit(Opcode::LoadConst(idx));
-                    // スタック: [array, value, index]
-                    // ArraySetElementを使用（新しいオペコード）
-                    self.chunk.emit(Opcode::ArrayPush);
+                    let Some(element) = element else {
+                        continue;
+                    };
+                    self.chunk.emit(Opcode::Dup);
+                    let index = self.chunk.add_constant(JSValue::Number(i as f64));
+                    self.chunk.emit(Opcode::LoadConst(index));
+                    self.chunk.emit(Opcode::GetProperty);
+                    self.compile_pattern(kind, element);
                 }
+                self.chunk.emit(Opcode::Pop);
             }
-            Expression::ObjectLiteral(properties) => {
-                // 空のオブジェクトを作成してスタックにプッシュ
-                self.chunk.emit(Opcode::NewObject);
-
-                // 各プロパティを設定
-                for (key, value) in properties {
-                    // 値をコンパイル
-                    self.compile_expression(value)?;
-                    // キーをプッシュ
-                    let key_idx = self.chunk.add_constant(JSValue::String(key));
-                    self.chunk.emit(Opcode::LoadConst(key_idx));
-                    // スタック: [object, value, key]
-                    self.chunk.emit(Opcode::ObjectSetProperty);
+        }
+    }
+
+    /// 式をコンパイル
+    fn compile_expression(&mut self, expression: Expression) -> JSResult<()> {
+        match expression.kind {
+            ExpressionKind::Binary { .. }
+            | ExpressionKind::MemberAccess { .. }
+            | ExpressionKind::Call { .. } => self.compile_chain(expression),
+            _ => self.compile_operand(expression.into_kind()),
+        }
+    }
+
+    /// 左結合の連鎖（二項演算・メンバー参照・呼び出し）をコンパイル
+    ///
+    /// 連鎖は長さの上限なくパースできるため、左辺を再帰でたどらずに
+    /// 連鎖の左端まで下りてから、1段ずつ左辺より後の部分をコンパイルする。
+    fn compile_chain(&mut self, expression: Expression) -> JSResult<()> {
+        let mut links = Vec::new();
+        let leftmost = Self::unwind_chain(expression, &mut links);
+        self.compile_operand(leftmost)?;
+        while let Some(link) = links.pop() {
+            self.compile_chain_link(link)?;
+        }
+        Ok(())
+    }
+
+    /// 連鎖の左端まで下り、途中の段を右端から順に links に積んで左端の式を返す
+    fn unwind_chain(mut expression: Expression, links: &mut Vec<ChainLink>) -> ExpressionKind {
+        loop {
+            expression = match expression.into_kind() {
+                ExpressionKind::Binary { op, left, right } => {
+                    links.push(ChainLink::Binary { op, right: *right });
+                    *left
                 }
-            }
-            Expression::MemberAccess {
-                object,
-                property,
-                computed,
-            } => {
-                // obj[prop] または obj.prop
-                self.compile_expression(*object)?;
-                if computed {
-                    // obj[prop] - property を評価
-                    self.compile_expression(*property)?;
-                } else {
-                    // obj.prop - property は文字列リテラル
-                    self.compile_expression(*property)?;
+                ExpressionKind::MemberAccess {
+                    object,
+                    property,
+                    computed,
+                } => {
+                    links.push(ChainLink::Member {
+                        property: *property,
+                        computed,
+                    });
+                    *object
                 }
+                ExpressionKind::Call { callee, args } => {
+                    let callee_name = Self::callee_name(&callee);
+                    let span = callee.span;
+                    match callee.into_kind() {
+                        // obj.method(...) の場合は obj を this として呼び出す
+                        ExpressionKind::MemberAccess {
+                            object, property, ..
+                        } => {
+                            links.push(ChainLink::Call {
+                                method: Some(*property),
+                                args,
+                                callee_name,
+                            });
+                            *object
+                        }
+                        kind => {
+                            links.push(ChainLink::Call {
+                                method: None,
+                                args,
+                                callee_name,
+                            });
+                            Expression::new(kind, span)
+                        }
+                    }
+                }
+                kind => return kind,
+            };
+        }
+    }
+
+    /// 左辺をコンパイルした後に、連鎖の1段の残りをコンパイル
+    fn compile_chain_link(&mut self, link: ChainLink) -> JSResult<()> {
+        match link {
+            ChainLink::Binary {
+                op: op @ (BinaryOp::And | BinaryOp::Or),
+                right,
+            } => self.finish_logical(op, right),
+            ChainLink::Binary { op, right } => self.finish_binary(op, right),
+            ChainLink::Member { property, computed } => {
+                self.finish_member_access(property, computed)
+            }
+            ChainLink::Call {
+                method,
+                args,
+                callee_name,
+            } => self.finish_call(method, args, callee_name),
+        }
+    }
+
+    /// 連鎖の段にならない式をコンパイル
+    ///
+    /// 深くネストした式では再帰の段数だけこのフレームが積まれるため、各ノードの
+    /// コンパイルは個別のメソッドに分けてフレームを小さく保つ。
+    fn compile_operand(&mut self, kind: ExpressionKind) -> JSResult<()> {
+        match kind {
+            ExpressionKind::Literal(lit) => self.compile_literal(lit),
+            ExpressionKind::Identifier(name) => {
+                self.chunk.emit(Opcode::LoadVar(name));
+                Ok(())
+            }
+            ExpressionKind::This => {
+                self.chunk.emit(Opcode::LoadThis);
+                Ok(())
+            }
+            ExpressionKind::Unary { op, arg } => self.compile_unary(op, *arg),
+            ExpressionKind::Conditional {
+                test,
+                consequent,
+                alternate,
+            } => self.compile_conditional(*test, *consequent, *alternate, false),
+            ExpressionKind::Assignment { left, right } => {
+                self.compile_assignment(*left, *right, true)
+            }
+            ExpressionKind::ArrayLiteral(elements) => self.compile_array_literal(elements),
+            ExpressionKind::ObjectLiteral(properties) => self.compile_object_literal(properties),
+            ExpressionKind::Function { name, params, body } => {
+                self.compile_function_expression(name, params, body)
+            }
+            ExpressionKind::ArrowFunction { params, body } => {
+                self.compile_arrow_function(params, body)
+            }
+            ExpressionKind::New { callee, args } => self.compile_new(*callee, args),
+            ExpressionKind::Spread(_) => {
+                Err(JSError::SyntaxError("Unexpected token '...'".to_string()))
+            }
+            // 連鎖の段は compile_expression で扱う
+            ExpressionKind::Binary { .. }
+            | ExpressionKind::MemberAccess { .. }
+            | ExpressionKind::Call { .. } => unreachable!(),
+        }
+    }
+
+    /// リテラルをコンパイル
+    fn compile_literal(&mut self, lit: Literal) -> JSResult<()> {
+        // undefined / null / 真偽値は定数プールを使わずに直接積む
+        let opcode = match lit {
+            Literal::Undefined => Opcode::LoadUndefined,
+            Literal::Null => Opcode::LoadNull,
+            Literal::Boolean(b) => Opcode::LoadBool(b),
+            Literal::Number(n) => Opcode::LoadConst(self.chunk.add_constant(JSValue::Number(n))),
+            Literal::String(s) => Opcode::LoadConst(self.chunk.add_constant(JSValue::String(s))),
+        };
+        self.chunk.emit(opcode);
+        Ok(())
+    }
+
+    /// 左辺に続けて二項演算（短絡評価以外）の右辺と演算をコンパイル
+    fn finish_binary(&mut self, op: BinaryOp, right: Expression) -> JSResult<()> {
+        self.compile_expression(right)?;
+
+        let opcode = match op {
+            BinaryOp::Add => Opcode::Add,
+            BinaryOp::Sub => Opcode::Sub,
+            BinaryOp::Mul => Opcode::Mul,
+            BinaryOp::Div => Opcode::Div,
+            BinaryOp::Mod => Opcode::Mod,
+            BinaryOp::Power => Opcode::Power,
+            BinaryOp::Eq => Opcode::Eq,
+            BinaryOp::NotEq => Opcode::NotEq,
+            BinaryOp::StrictEq => Opcode::StrictEq,
+            BinaryOp::StrictNotEq => Opcode::StrictNotEq,
+            BinaryOp::Lt => Opcode::Lt,
+            BinaryOp::Gt => Opcode::Gt,
+            BinaryOp::LtEq => Opcode::LtEq,
+            BinaryOp::GtEq => Opcode::GtEq,
+            BinaryOp::In => Opcode::In,
+            BinaryOp::Instanceof => Opcode::Instanceof,
+            // 短絡評価は finish_logical で扱う
+            BinaryOp::And | BinaryOp::Or => unreachable!(),
+            BinaryOp::BitAnd => Opcode::BitAnd,
+            BinaryOp::BitOr => Opcode::BitOr,
+            BinaryOp::BitXor => Opcode::BitXor,
+            BinaryOp::LeftShift => Opcode::LeftShift,
+            BinaryOp::RightShift => Opcode::RightShift,
+            BinaryOp::UnsignedRightShift => Opcode::UnsignedRightShift,
+        };
+        self.chunk.emit(opcode);
+        Ok(())
+    }
+
+    /// 単項演算をコンパイル
+    fn compile_unary(&mut self, op: UnaryOp, arg: Expression) -> JSResult<()> {
+        self.compile_expression(arg)?;
+
+        let opcode = match op {
+            UnaryOp::Plus => return Ok(()), // +x は x と同じ
+            UnaryOp::Minus => Opcode::Neg,
+            UnaryOp::Not => Opcode::Not,
+            UnaryOp::BitNot => Opcode::BitNot,
+            UnaryOp::Typeof => Opcode::Typeof,
+            UnaryOp::Void => Opcode::Void,
+            UnaryOp::Delete => {
+                // Delete は現時点では未実装
+                return Err(JSError::InternalError(
+                    "delete operator not yet implemented".to_string(),
+                ));
+            }
+        };
+        self.chunk.emit(opcode);
+        Ok(())
+    }
+
+    /// 配列リテラルをコンパイル
+    fn compile_array_literal(&mut self, elements: Vec<Expression>) -> JSResult<()> {
+        // 各要素を順にスタックに積み、まとめて配列を作成
+        let count = elements.len();
+        for element in elements {
+            self.compile_expression(element)?;
+        }
+        self.chunk.emit(Opcode::BuildArray(count));
+        Ok(())
+    }
+
+    /// オブジェクトリテラルをコンパイル
+    fn compile_object_literal(&mut self, properties: Vec<(String, Expression)>) -> JSResult<()> {
+        // キーと値の組を順にスタックに積み、まとめてオブジェクトを作成
+        let count = properties.len();
+        for (key, value) in properties {
+            let key_idx = self.chunk.add_constant(JSValue::String(key));
+            self.chunk.emit(Opcode::LoadConst(key_idx));
+            self.compile_expression(value)?;
+        }
+        self.chunk.emit(Opcode::BuildObject(count));
+        Ok(())
+    }
+
+    /// オブジェクトに続けてメンバー参照のプロパティをコンパイル: obj[prop] または obj.prop
+    fn finish_member_access(&mut self, property: Expression, computed: bool) -> JSResult<()> {
+        match Self::property_name(computed, property) {
+            Ok(name) => self.chunk.emit(Opcode::GetPropertyByName(name)),
+            Err(property) => {
+                self.compile_expression(property)?;
                 self.chunk.emit(Opcode::GetProperty);
             }
-            Expression::Function { params, body } => {
-                // 関数本体をコンパイル
-                let program = Program { body };
-                let function_chunk = Compiler::new().compile(program)?;
+        }
+        Ok(())
+    }
 
-                // 現在のチャンクに関数オブジェクト（チャンク + params）を追加
-                let func_value = JSValue::Function(function_chunk, params.clone());
-                let idx = self.chunk.add_constant(func_value);
-                self.chunk.emit(Opcode::CreateFunction(idx));
+    /// 関数式をコンパイル
+    fn compile_function_expression(
+        &mut self,
+        name: Option<String>,
+        params: Vec<String>,
+        body: Vec<Statement>,
+    ) -> JSResult<()> {
+        // 関数本体をコンパイル
+        let function_chunk = Compiler::new().compile_function_body(&params, body)?;
+
+        // 現在のチャンクに関数テンプレートを追加
+        let mut function = JSFunction::new(function_chunk, params);
+        if let Some(name) = name {
+            function = function.with_self_name(name);
+        }
+        let func_value = JSValue::Function(Rc::new(function));
+        let idx = self.chunk.add_constant(func_value);
+        self.chunk.emit(Opcode::CreateFunction(idx));
+        Ok(())
+    }
+
+    /// アロー関数をコンパイル
+    fn compile_arrow_function(
+        &mut self,
+        params: Vec<String>,
+        body: Vec<Statement>,
+    ) -> JSResult<()> {
+        let function_chunk = Compiler::new().compile_function_body(&params, body)?;
+        let function = JSFunction::arrow(function_chunk, params);
+        let idx = self
+            .chunk
+            .add_constant(JSValue::Function(Rc::new(function)));
+        self.chunk.emit(Opcode::CreateFunction(idx));
+        Ok(())
+    }
+
+    /// 呼び出し対象（メソッド呼び出しでは this となるオブジェクト）に続けて呼び出しをコンパイル
+    fn finish_call(
+        &mut self,
+        method: Option<Expression>,
+        args: Vec<Expression>,
+        callee_name: Option<String>,
+    ) -> JSResult<()> {
+        if Self::has_spread(&args) {
+            match method {
+                // obj.method(...args): メソッドを先に取り出し obj を this とする
+                Some(property) => {
+                    self.chunk.emit(Opcode::Dup);
+                    self.compile_expression(property)?;
+                    self.chunk.emit(Opcode::GetProperty);
+                    self.chunk.emit(Opcode::Swap);
+                }
+                None => self.chunk.emit(Opcode::LoadUndefined),
             }
-            Expression::Call { callee, args } => {
-                // 呼び出し対象をコンパイル
-                self.compile_expression(*callee)?;
+            self.compile_spread_arguments(args)?;
+            self.chunk
+                .emit_call(Opcode::CallFunctionSpread, callee_name);
+            return Ok(());
+        }
 
-                // 引数をコンパイル
-                for arg in &args {
-                    self.compile_expression(arg.clone())?;
+        let arg_count = args.len();
+        let is_method = method.is_some();
+        if let Some(property) = method {
+            self.compile_expression(property)?;
+        }
+        // 引数をコンパイル
+        for arg in args {
+            self.compile_expression(arg)?;
+        }
+        let opcode = if is_method {
+            Opcode::CallMethod(arg_count)
+        } else {
+            Opcode::CallFunction(arg_count)
+        };
+        self.chunk.emit_call(opcode, callee_name);
+        Ok(())
+    }
+
+    /// new 式をコンパイル
+    fn compile_new(&mut self, callee: Expression, args: Vec<Expression>) -> JSResult<()> {
+        self.compile_expression(callee)?;
+        if Self::has_spread(&args) {
+            self.compile_spread_arguments(args)?;
+            self.chunk.emit(Opcode::NewSpread);
+        } else {
+            let arg_count = args.len();
+            for arg in args {
+                self.compile_expression(arg)?;
+            }
+            self.chunk.emit(Opcode::New(arg_count));
+        }
+        Ok(())
+    }
+
+    /// スプレッドを含む引数リストを1つの引数配列としてコンパイル
+    fn compile_spread_arguments(&mut self, args: Vec<Expression>) -> JSResult<()> {
+        let mut spreads = Vec::with_capacity(args.len());
+        for arg in args {
+            let span = arg.span;
+            match arg.into_kind() {
+                ExpressionKind::Spread(iterable) => {
+                    self.compile_expression(*iterable)?;
+                    spreads.push(true);
+                }
+                kind => {
+                    self.compile_expression(Expression::new(kind, span))?;
+                    spreads.push(false);
                 }
+            }
+        }
+        self.chunk.emit(Opcode::BuildSpreadArray(spreads));
+        Ok(())
+    }
 
-                // 引数の数だけスタックからポップ
-                let arg_count = args.len();
-                self.chunk.emit(Opcode::CallFunction(arg_count));
+    /// return の値をコンパイル（末尾位置の呼び出しは TailCall にする）
+    fn compile_tail_expression(&mut self, expr: Expression) -> JSResult<()> {
+        if self.try_depth > 0 {
+            return self.compile_expression(expr);
+        }
+        let span = expr.span;
+        match expr.into_kind() {
+            ExpressionKind::Call { callee, args }
+                if !matches!(callee.kind, ExpressionKind::MemberAccess { .. })
+                    && !Self::has_spread(&args) =>
+            {
+                self.compile_tail_call(*callee, args)
             }
+            // 条件演算子の各分岐も末尾位置
+            ExpressionKind::Conditional {
+                test,
+                consequent,
+                alternate,
+            } => self.compile_conditional(*test, *consequent, *alternate, true),
+            kind => self.compile_expression(Expression::new(kind, span)),
         }
+    }
+
+    /// 末尾位置の呼び出しを TailCall としてコンパイル
+    fn compile_tail_call(&mut self, callee: Expression, args: Vec<Expression>) -> JSResult<()> {
+        let arg_count = args.len();
+        let callee_name = Self::callee_name(&callee);
+        self.compile_expression(callee)?;
+        for arg in args {
+            self.compile_expression(arg)?;
+        }
+        self.chunk
+            .emit_call(Opcode::TailCall(arg_count), callee_name);
+        Ok(())
+    }
+
+    /// 条件演算子をコンパイル（tail なら各分岐を末尾位置としてコンパイル）
+    fn compile_conditional(
+        &mut self,
+        test: Expression,
+        consequent: Expression,
+        alternate: Expression,
+        tail: bool,
+    ) -> JSResult<()> {
+        // test / JumpIfFalse(else) / consequent / Jump(end) / else: alternate / end:
+        self.compile_expression(test)?;
+        let jump_to_else = self.chunk.current_offset();
+        self.chunk.emit(Opcode::JumpIfFalse(0));
+        self.compile_branch(consequent, tail)?;
+        let jump_to_end = self.chunk.current_offset();
+        self.chunk.emit(Opcode::Jump(0));
+
+        let else_start = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_else, else_start);
+        self.compile_branch(alternate, tail)?;
+
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_end, end);
         Ok(())
     }
+
+    /// 左辺に続けて && と || をコンパイル（右辺は左辺の値で結果が決まらないときだけ評価する）
+    fn finish_logical(&mut self, op: BinaryOp, right: Expression) -> JSResult<()> {
+        // left / Dup / JumpIfFalse(end)（|| は JumpIfTrue） / Pop / right / end:
+        self.chunk.emit(Opcode::Dup);
+        let jump_to_end = self.chunk.current_offset();
+        self.chunk.emit(match op {
+            BinaryOp::And => Opcode::JumpIfFalse(0),
+            _ => Opcode::JumpIfTrue(0),
+        });
+        self.chunk.emit(Opcode::Pop);
+        self.compile_expression(right)?;
+
+        let end = self.chunk.current_offset();
+        self.chunk.patch_jump(jump_to_end, end);
+        Ok(())
+    }
+
+    fn compile_branch(&mut self, expr: Expression, tail: bool) -> JSResult<()> {
+        if tail {
+            self.compile_tail_expression(expr)
+        } else {
+            self.compile_expression(expr)
+        }
+    }
+
+    /// 引数にスプレッドを含むか
+    fn has_spread(args: &[Expression]) -> bool {
+        args.iter()
+            .any(|arg| matches!(arg.kind, ExpressionKind::Spread(_)))
+    }
+
+    /// 呼び出し対象の表記（識別子と、識別子から名前で辿るメンバー参照のみ）
+    fn callee_name(callee: &Expression) -> Option<String> {
+        // メンバー参照の連鎖は長くなりうるため、右端の名前から順にループで集める
+        let mut names = Vec::new();
+        let mut expression = callee;
+        loop {
+            match &expression.kind {
+                ExpressionKind::Identifier(name) => {
+                    names.push(name.as_str());
+                    break;
+                }
+                ExpressionKind::MemberAccess {
+                    object,
+                    property,
+                    computed: false,
+                } => match &property.kind {
+                    ExpressionKind::Literal(Literal::String(name)) => {
+                        names.push(name);
+                        expression = object;
+                    }
+                    _ => return None,
+                },
+                _ => return None,
+            }
+        }
+        names.reverse();
+        Some(names.join("."))
+    }
+
+    /// obj.prop のプロパティ名を取り出す（計算されたキーはそのまま返す）
+    fn property_name(computed: bool, property: Expression) -> Result<String, Expression> {
+        let span = property.span;
+        match property.into_kind() {
+            ExpressionKind::Literal(Literal::String(name)) if !computed => Ok(name),
+            kind => Err(Expression::new(kind, span)),
+        }
+    }
 }
 
 impl Default for Compiler {
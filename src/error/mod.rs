@@ -1,3 +1,4 @@
+use crate::value::JSValue;
 use std::fmt;
 
 pub type JSResult<T> = Result<T, JSError>;
@@ -15,6 +16,57 @@ pub enum JSError {
     RangeError(String),
     /// 内部エラー
     InternalError(String),
+    /// スクリプトから throw された値
+    Exception(JSValue),
+}
+
+impl JSError {
+    /// メッセージの先頭に位置を付ける（スクリプトから throw された値はそのまま）
+    ///
+    /// 既に `行:列: ` で始まるメッセージは `位置:行:列: `、それ以外は `位置: ` の形にする。
+    pub fn with_location(self, location: &str) -> Self {
+        let locate = |msg: String| {
+            if starts_with_position(&msg) {
+                format!("{}:{}", location, msg)
+            } else {
+                format!("{}: {}", location, msg)
+            }
+        };
+        match self {
+            JSError::SyntaxError(msg) => JSError::SyntaxError(locate(msg)),
+            JSError::ReferenceError(msg) => JSError::ReferenceError(locate(msg)),
+            JSError::TypeError(msg) => JSError::TypeError(locate(msg)),
+            JSError::RangeError(msg) => JSError::RangeError(locate(msg)),
+            JSError::InternalError(msg) => JSError::InternalError(locate(msg)),
+            JSError::Exception(value) => JSError::Exception(value),
+        }
+    }
+}
+
+/// メッセージが `行:列: ` で始まるか
+fn starts_with_position(msg: &str) -> bool {
+    let is_number = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    msg.split_once(": ")
+        .and_then(|(position, _)| position.split_once(':'))
+        .is_some_and(|(line, column)| is_number(line) && is_number(column))
+}
+
+/// name と message を文字列で持つ（プロトタイプ経由を含む）エラー風オブジェクトを
+/// `name: message` の形にする。message が空なら name だけを返す
+fn error_summary(value: &JSValue) -> Option<String> {
+    let JSValue::Object(obj) = value else {
+        return None;
+    };
+    let obj = obj.borrow();
+    let (JSValue::String(name), JSValue::String(message)) = (obj.get("name"), obj.get("message"))
+    else {
+        return None;
+    };
+    if message.is_empty() {
+        Some(name)
+    } else {
+        Some(format!("{}: {}", name, message))
+    }
 }
 
 impl fmt::Display for JSError {
@@ -26,6 +78,10 @@ impl fmt::Display for JSError {
             JSError::TypeError(msg) => write!(f, "TypeError: {}", msg),
             JSError::RangeError(msg) => write!(f, "RangeError: {}", msg),
             JSError::InternalError(msg) => write!(f, "InternalError: {}", msg),
+            JSError::Exception(value) => match error_summary(value) {
+                Some(summary) => write!(f, "Uncaught {}", summary),
+                None => write!(f, "Uncaught {}", value),
+            },
         }
     }
 }
@@ -11,6 +11,10 @@ pub struct Lexer {
     line: usize,
     /// 現在の列番号
     column: usize,
+    /// タブ1文字の幅（列番号の計算に使用）
+    tab_width: usize,
+    /// コメントを Comment トークンとして出力するか
+    retain_comments: bool,
 }
 
 impl Lexer {
@@ -21,9 +25,25 @@ impl Lexer {
             position: 0,
             line: 1,
             column: 1,
+            tab_width: 1,
+            retain_comments: false,
         }
     }
 
+    /// タブ幅を設定（タブは次のタブストップまで列を進める。デフォルトは 1）
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// コメントを読み飛ばさず Comment トークンとして出力するか設定（デフォルトは false）
+    ///
+    /// ドキュメントコメントの抽出等のツール向け。パーサーは Comment トークンを無視する。
+    pub fn with_comments(mut self, retain_comments: bool) -> Self {
+        self.retain_comments = retain_comments;
+        self
+    }
+
     /// ソースコードを字句解析してトークン列を生成
     pub fn tokenize(&mut self) -> JSResult<Vec<Token>> {
         let mut tokens = Vec::new();
@@ -37,6 +57,13 @@ impl Lexer {
                 break;
             }
 
+            if let Some(comment) = self.scan_comment()? {
+                if self.retain_comments {
+                    tokens.push(comment);
+                }
+                continue;
+            }
+
             let token = self.next_token()?;
             tokens.push(token);
         }
@@ -110,15 +137,7 @@ impl Lexer {
                 }
             }
             '/' => {
-                if self.match_char('/') {
-                    // 行コメント
-                    self.skip_line_comment();
-                    return self.next_token();
-                } else if self.match_char('*') {
-                    // ブロックコメント
-                    self.skip_block_comment()?;
-                    return self.next_token();
-                } else if self.match_char('=') {
+                if self.match_char('=') {
                     TokenKind::SlashEq
                 } else {
                     TokenKind::Slash
@@ -169,7 +188,13 @@ impl Lexer {
             '>' => {
                 if self.match_char('>') {
                     if self.match_char('>') {
-                        TokenKind::UnsignedRightShift
+                        if self.match_char('=') {
+                            TokenKind::UnsignedRightShiftEq
+                        } else {
+                            TokenKind::UnsignedRightShift
+                        }
+                    } else if self.match_char('=') {
+                        TokenKind::RightShiftEq
                     } else {
                         TokenKind::RightShift
                     }
@@ -203,8 +228,11 @@ impl Lexer {
             // 数値リテラル
             '0'..='9' => return self.scan_number(),
 
+            // プライベート識別子
+            '#' => return self.scan_private_identifier(),
+
             // 識別子・キーワード
-            _ if ch.is_alphabetic() || ch == '_' || ch == '$' => {
+            _ if is_identifier_start(ch) || ch == '\\' => {
                 return self.scan_identifier();
             }
 
@@ -231,14 +259,8 @@ impl Lexer {
             }
         }
 
-        // 小数点
-        if self.peek() == Some('.')
-            && !start_from_dot
-            && self
-                .peek_ahead(1)
-                .map(|c| c.is_ascii_digit())
-                .unwrap_or(false)
-        {
+        // 小数点（`5.` のように小数部がなくてもよい。`5..x` の2つ目の '.' はメンバーアクセス）
+        if self.peek() == Some('.') && !start_from_dot {
             self.advance(); // '.'
             while let Some(ch) = self.peek() {
                 if ch.is_ascii_digit() {
@@ -249,7 +271,7 @@ impl Lexer {
             }
         }
 
-        // 指数表記
+        // 指数表記（数字のない `1e`・`1e+` は下の parse で不正なリテラルになる）
         if let Some('e') | Some('E') = self.peek() {
             self.advance();
             if let Some('+') | Some('-') = self.peek() {
@@ -266,10 +288,11 @@ impl Lexer {
 
         let text: String = self.source[start..self.position].iter().collect();
         if text.parse::<f64>().is_err() {
-            return Err(JSError::SyntaxError(format!(
-                "Invalid number literal: {}",
-                text
-            )));
+            return Err(Self::error_at(
+                start_line,
+                start_column,
+                &format!("Invalid number literal: {}", text),
+            ));
         }
 
         let span = Span::new(start, self.position, start_line, start_column);
@@ -282,6 +305,7 @@ impl Lexer {
         let start_line = self.line;
         let start_column = self.column - 1;
         let mut value = String::new();
+        let mut legacy_octal_escape = false;
 
         while let Some(ch) = self.peek() {
             if ch == quote {
@@ -289,8 +313,25 @@ impl Lexer {
                 break;
             } else if ch == '\\' {
                 self.advance();
+                // \0 の後に数字が続かなければ NUL 文字、それ以外の数字は旧来の 8 進エスケープ
+                if let Some(digit @ '0'..='9') = self.peek()
+                    && (digit != '0' || self.peek_ahead(1).is_some_and(|ch| ch.is_ascii_digit()))
+                {
+                    legacy_octal_escape = true;
+                    value.push(self.scan_legacy_octal_escape());
+                    continue;
+                }
+                // 行継続: `\` の直後の改行（\r\n を含む）は文字列に含めない
+                if let Some(terminator @ ('\n' | '\r' | '\u{2028}' | '\u{2029}')) = self.peek() {
+                    self.advance();
+                    if terminator == '\r' && self.peek() == Some('\n') {
+                        self.advance();
+                    }
+                    continue;
+                }
                 if let Some(escaped) = self.peek() {
                     let escaped_char = match escaped {
+                        '0' => '\0',
                         'n' => '\n',
                         'r' => '\r',
                         't' => '\t',
@@ -303,8 +344,10 @@ impl Lexer {
                     self.advance();
                 }
             } else if ch == '\n' {
-                return Err(JSError::SyntaxError(
-                    "Unterminated string literal".to_string(),
+                return Err(Self::error_at(
+                    start_line,
+                    start_column,
+                    "Unterminated string literal",
                 ));
             } else {
                 value.push(ch);
@@ -313,7 +356,32 @@ impl Lexer {
         }
 
         let span = Span::new(start, self.position, start_line, start_column);
-        Ok(Token::new(TokenKind::String(value), span))
+        let mut token = Token::new(TokenKind::String(value), span);
+        token.legacy_octal_escape = legacy_octal_escape;
+        Ok(token)
+    }
+
+    /// `\` に続く旧来の 8 進エスケープをスキャン
+    ///
+    /// 0-3 で始まれば 3 桁まで、4-7 で始まれば 2 桁までの 8 進数を文字コードとする。
+    /// `\8` と `\9` はその数字自体を表す。
+    fn scan_legacy_octal_escape(&mut self) -> char {
+        let Some(first) = self.peek().and_then(|ch| ch.to_digit(8)) else {
+            return self.advance();
+        };
+        self.advance();
+        let max_digits = if first <= 3 { 3 } else { 2 };
+        let mut code = first;
+        for _ in 1..max_digits {
+            match self.peek().and_then(|ch| ch.to_digit(8)) {
+                Some(digit) => {
+                    code = code * 8 + digit;
+                    self.advance();
+                }
+                None => break,
+            }
+        }
+        char::from_u32(code).unwrap_or_default()
     }
 
     /// 識別子・キーワードのスキャン
@@ -322,15 +390,7 @@ impl Lexer {
         let start_line = self.line;
         let start_column = self.column - 1;
 
-        while let Some(ch) = self.peek() {
-            if ch.is_alphanumeric() || ch == '_' || ch == '$' {
-                self.advance();
-            } else {
-                break;
-            }
-        }
-
-        let text: String = self.source[start..self.position].iter().collect();
+        let (text, escaped) = self.scan_identifier_name()?;
         let kind = match text.as_str() {
             "let" => TokenKind::Let,
             "const" => TokenKind::Const,
@@ -341,6 +401,7 @@ impl Lexer {
             "else" => TokenKind::Else,
             "for" => TokenKind::For,
             "while" => TokenKind::While,
+            "with" => TokenKind::With,
             "break" => TokenKind::Break,
             "continue" => TokenKind::Continue,
             "class" => TokenKind::Class,
@@ -369,11 +430,121 @@ impl Lexer {
             "undefined" => TokenKind::Undefined,
             _ => TokenKind::Identifier(text),
         };
+        if escaped && !matches!(kind, TokenKind::Identifier(_)) {
+            return Err(Self::error_at(
+                start_line,
+                start_column,
+                "Keyword must not contain escaped characters",
+            ));
+        }
 
         let span = Span::new(start, self.position, start_line, start_column);
         Ok(Token::new(kind, span))
     }
 
+    /// プライベート識別子のスキャン: #name
+    fn scan_private_identifier(&mut self) -> JSResult<Token> {
+        let start = self.position - 1;
+        let start_line = self.line;
+        let start_column = self.column - 1;
+
+        if !self
+            .peek()
+            .is_some_and(|ch| is_identifier_start(ch) || ch == '\\')
+        {
+            return Err(Self::error_at(
+                start_line,
+                start_column,
+                "Invalid or unexpected token '#'",
+            ));
+        }
+
+        // キーワードも #if のようにプライベート名として使える
+        self.advance();
+        let (name, _) = self.scan_identifier_name()?;
+
+        let span = Span::new(start, self.position, start_line, start_column);
+        Ok(Token::new(TokenKind::PrivateIdentifier(name), span))
+    }
+
+    /// 識別子名を読み取り、`\uXXXX`・`\u{...}` のエスケープを復号する
+    ///
+    /// 先頭の1文字は読み取り済みであること。復号した文字も識別子のその位置に使える
+    /// 文字でなければならない。エスケープを含んだかどうかも返す。
+    fn scan_identifier_name(&mut self) -> JSResult<(String, bool)> {
+        let mut name = String::new();
+        let mut escaped = false;
+        let mut ch = self.source[self.position - 1];
+        loop {
+            if ch == '\\' {
+                let (line, column) = (self.line, self.column - 1);
+                let is_valid = |c: char| {
+                    if name.is_empty() {
+                        is_identifier_start(c)
+                    } else {
+                        is_identifier_part(c)
+                    }
+                };
+                match self.scan_unicode_escape().filter(|&c| is_valid(c)) {
+                    Some(decoded) => name.push(decoded),
+                    None => {
+                        return Err(Self::error_at(
+                            line,
+                            column,
+                            "Invalid Unicode escape sequence in identifier",
+                        ));
+                    }
+                }
+                escaped = true;
+            } else {
+                name.push(ch);
+            }
+
+            match self.peek() {
+                Some(next) if next == '\\' || is_identifier_part(next) => {
+                    self.advance();
+                    ch = next;
+                }
+                _ => return Ok((name, escaped)),
+            }
+        }
+    }
+
+    /// `\` の後の `uXXXX` または `u{...}` を読み取り、表す文字を返す
+    fn scan_unicode_escape(&mut self) -> Option<char> {
+        if !self.match_char('u') {
+            return None;
+        }
+        let mut digits = String::new();
+        if self.match_char('{') {
+            while let Some(ch) = self.peek().filter(char::is_ascii_hexdigit) {
+                digits.push(ch);
+                self.advance();
+            }
+            if digits.is_empty() || !self.match_char('}') {
+                return None;
+            }
+        } else {
+            for _ in 0..4 {
+                digits.push(self.peek().filter(char::is_ascii_hexdigit)?);
+                self.advance();
+            }
+        }
+        // \u{0000061} のような先頭の 0 は桁数に数えない
+        let digits = digits.trim_start_matches('0');
+        let code = if digits.is_empty() {
+            0
+        } else {
+            u32::from_str_radix(digits, 16).ok()?
+        };
+        char::from_u32(code)
+    }
+
+    /// 位置情報付きの構文エラーを作成（"行:列: メッセージ" 形式）
+    fn error_at(line: usize, column: usize, message: &str) -> JSError {
+        JSError::SyntaxError(format!("{}:{}: {}", line, column, message))
+    }
+
     /// 空白文字のスキップ
     fn skip_whitespace(&mut self) {
         while let Some(ch) = self.peek() {
@@ -385,6 +556,31 @@ impl Lexer {
         }
     }
 
+    /// 現在位置にコメントがあれば読み取り、区切りを含むコメント全体のトークンを返す
+    ///
+    /// ソースの先頭（位置 0）の `#!` で始まる行（シバン）も行コメントとして扱う。
+    fn scan_comment(&mut self) -> JSResult<Option<Token>> {
+        let is_shebang = self.position == 0 && self.source.starts_with(&['#', '!']);
+        let is_comment = self.peek() == Some('/') && matches!(self.peek_ahead(1), Some('/' | '*'));
+        if !is_shebang && !is_comment {
+            return Ok(None);
+        }
+        let start = self.position;
+        let start_line = self.line;
+        let start_column = self.column;
+
+        self.advance();
+        if self.advance() == '*' {
+            self.skip_block_comment(start_line, start_column)?;
+        } else {
+            self.skip_line_comment();
+        }
+
+        let text: String = self.source[start..self.position].iter().collect();
+        let span = Span::new(start, self.position, start_line, start_column);
+        Ok(Some(Token::new(TokenKind::Comment(text), span)))
+    }
+
     /// 行コメントのスキップ
     fn skip_line_comment(&mut self) {
         while let Some(ch) = self.peek() {
@@ -395,8 +591,8 @@ impl Lexer {
         }
     }
 
-    /// ブロックコメントのスキップ
-    fn skip_block_comment(&mut self) -> JSResult<()> {
+    /// ブロックコメントのスキップ（閉じていない場合はコメントの開始位置で報告）
+    fn skip_block_comment(&mut self, start_line: usize, start_column: usize) -> JSResult<()> {
         while let Some(ch) = self.peek() {
             if ch == '*' && self.peek_ahead(1) == Some('/') {
                 self.advance();
@@ -405,8 +601,10 @@ impl Lexer {
             }
             self.advance();
         }
-        Err(JSError::SyntaxError(
-            "Unterminated block comment".to_string(),
+        Err(Self::error_at(
+            start_line,
+            start_column,
+            "Unterminated block comment",
         ))
     }
 
@@ -418,6 +616,9 @@ impl Lexer {
         if ch == '\n' {
             self.line += 1;
             self.column = 1;
+        } else if ch == '\t' {
+            // 次のタブストップ（1 + tab_width の倍数）へ進める
+            self.column += self.tab_width - (self.column - 1) % self.tab_width;
         } else {
             self.column += 1;
         }
@@ -464,3 +665,15 @@ impl Lexer {
         Span::new(self.position, self.position, self.line, self.column)
     }
 }
+
+/// 識別子の先頭に使える文字か（`$`・`_` と Unicode の XID_Start）
+///
+/// XID_Start は ID_Start を NFKC 正規化で閉じた集合で、ID_Start とはごく一部の文字だけが異なる。
+fn is_identifier_start(ch: char) -> bool {
+    ch == '$' || ch == '_' || unicode_ident::is_xid_start(ch)
+}
+
+/// 識別子の2文字目以降に使える文字か（`$`・ZWNJ・ZWJ と Unicode の XID_Continue）
+fn is_identifier_part(ch: char) -> bool {
+    ch == '$' || ch == '\u{200C}' || ch == '\u{200D}' || unicode_ident::is_xid_continue(ch)
+}
@@ -13,6 +13,8 @@ pub enum TokenKind {
 
     // 識別子とキーワード
     Identifier(String),
+    /// プライベート識別子 `#name`（`#` を除いた名前を保持）
+    PrivateIdentifier(String),
 
     // キーワード
     Let,
@@ -24,6 +26,7 @@ pub enum TokenKind {
     Else,
     For,
     While,
+    With,
     Break,
     Continue,
     Class,
@@ -85,11 +88,13 @@ pub enum TokenKind {
     Colon,    // :
 
     // 代入演算子
-    PlusEq,    // +=
-    MinusEq,   // -=
-    StarEq,    // *=
-    SlashEq,   // /=
-    PercentEq, // %=
+    PlusEq,               // +=
+    MinusEq,              // -=
+    StarEq,               // *=
+    SlashEq,              // /=
+    PercentEq,            // %=
+    RightShiftEq,         // >>=
+    UnsignedRightShiftEq, // >>>=
 
     // 区切り文字
     LeftParen,    // (
@@ -106,6 +111,7 @@ pub enum TokenKind {
     Arrow,     // =>
 
     // 特殊
+    Comment(String), // コメント（Lexer::with_comments で有効にした場合のみ）
     Eof,
     Unknown(char),
 }
@@ -129,6 +135,11 @@ impl Span {
             column,
         }
     }
+
+    /// self の先頭から other の末尾までを覆う Span を作成
+    pub fn to(self, other: Span) -> Self {
+        Self::new(self.start, other.end, self.line, self.column)
+    }
 }
 
 /// トークン
@@ -136,12 +147,67 @@ impl Span {
 pub struct Token {
     pub kind: TokenKind,
     pub span: Span,
+    /// 文字列リテラルが `\101` や `\8` のような旧来の 8 進エスケープを含むか
+    /// （strict モードでは SyntaxError）
+    pub legacy_octal_escape: bool,
 }
 
 impl Token {
     /// 新しいトークンを作成
     pub fn new(kind: TokenKind, span: Span) -> Self {
-        Self { kind, span }
+        Self {
+            kind,
+            span,
+            legacy_octal_escape: false,
+        }
+    }
+}
+
+impl TokenKind {
+    /// 予約語・リテラルのキーワードであればその綴りを返す
+    ///
+    /// `obj.delete` のようにプロパティ名の位置では識別子として扱うために使う。
+    pub fn keyword_text(&self) -> Option<&'static str> {
+        let text = match self {
+            TokenKind::Let => "let",
+            TokenKind::Const => "const",
+            TokenKind::Var => "var",
+            TokenKind::Function => "function",
+            TokenKind::Return => "return",
+            TokenKind::If => "if",
+            TokenKind::Else => "else",
+            TokenKind::For => "for",
+            TokenKind::While => "while",
+            TokenKind::With => "with",
+            TokenKind::Break => "break",
+            TokenKind::Continue => "continue",
+            TokenKind::Class => "class",
+            TokenKind::New => "new",
+            TokenKind::This => "this",
+            TokenKind::Super => "super",
+            TokenKind::Import => "import",
+            TokenKind::Export => "export",
+            TokenKind::From => "from",
+            TokenKind::As => "as",
+            TokenKind::Async => "async",
+            TokenKind::Await => "await",
+            TokenKind::Try => "try",
+            TokenKind::Catch => "catch",
+            TokenKind::Finally => "finally",
+            TokenKind::Throw => "throw",
+            TokenKind::Typeof => "typeof",
+            TokenKind::Delete => "delete",
+            TokenKind::Void => "void",
+            TokenKind::In => "in",
+            TokenKind::Of => "of",
+            TokenKind::Instanceof => "instanceof",
+            TokenKind::True => "true",
+            TokenKind::False => "false",
+            TokenKind::Null => "null",
+            TokenKind::Undefined => "undefined",
+            _ => return None,
+        };
+        Some(text)
     }
 }
 
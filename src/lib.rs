@@ -16,24 +16,126 @@ pub use compiler::{Compiler, Opcode};
 pub use lexer::{Lexer, TokenKind};
 pub use parser::Parser;
 
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
 /// メインインターフェース
 pub struct JSEngine {
     /// 仮想マシンインスタンス
     vm: vm::VM,
+    /// コンパイル結果のキャッシュ（with_compile_cache で有効化）
+    compile_cache: Option<compiler::CompileCache>,
+    /// ソースをコンパイルした回数
+    compile_count: usize,
 }
 
 impl JSEngine {
     /// 新しいJSエンジンインスタンスを作成
     pub fn new() -> Self {
-        Self { vm: vm::VM::new() }
+        Self {
+            vm: vm::VM::new(),
+            compile_cache: None,
+            compile_count: 0,
+        }
+    }
+
+    /// 同じソースの再評価でコンパイルを省くキャッシュを有効化（最大 capacity 件の LRU）
+    pub fn with_compile_cache(mut self, capacity: usize) -> Self {
+        self.compile_cache = Some(compiler::CompileCache::new(capacity));
+        self
+    }
+
+    /// これまでにソースをコンパイルした回数（キャッシュに当たった評価は数えない）
+    pub fn compile_count(&self) -> usize {
+        self.compile_count
+    }
+
+    /// Date 等が使用する時計を差し替える（Unix エポックからのミリ秒を返す関数）
+    pub fn set_clock(&mut self, clock: impl Fn() -> f64 + 'static) {
+        self.vm.set_clock(Box::new(clock));
+    }
+
+    /// Math.random の乱数生成器のシードを設定
+    pub fn seed_random(&mut self, seed: u64) {
+        self.vm.seed_random(seed);
     }
 
-    /// JavaScriptコードを評価
+    /// 関数呼び出しの深さの上限を設定（超えると RangeError）
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.vm.set_max_call_depth(max_call_depth);
+    }
+
+    /// console.log の出力先を差し替える（デフォルトは標準出力）
+    pub fn set_printer(&mut self, printer: impl FnMut(&str) + 'static) {
+        self.vm.set_printer(Box::new(printer));
+    }
+
+    /// グローバル変数の束縛を保存する（restore で巻き戻す）
+    ///
+    /// 保存するのは束縛だけで、オブジェクトの中身の変更は巻き戻らない。
+    pub fn snapshot(&self) -> runtime::EnvSnapshot {
+        self.vm.global_env().borrow().snapshot()
+    }
+
+    /// グローバル変数の束縛を snapshot の時点に戻す（以降に宣言した変数は削除される）
+    pub fn restore(&mut self, snapshot: runtime::EnvSnapshot) {
+        self.vm.global_env().borrow().restore(snapshot);
+    }
+
+    /// JavaScriptコードをスクリプトとして評価
+    ///
+    /// 最後の式文の値を返す。空白やコメントだけのソース、宣言だけのプログラムは undefined。
     pub fn eval(&mut self, source: &str) -> JSResult<JSValue> {
+        let cached = self
+            .compile_cache
+            .as_mut()
+            .and_then(|cache| cache.get(source));
+        let bytecode = match cached {
+            Some(bytecode) => bytecode,
+            None => {
+                let bytecode = Rc::new(Self::compile(source, parser::ParseGoal::Script)?);
+                self.compile_count += 1;
+                if let Some(cache) = &mut self.compile_cache {
+                    cache.insert(source, bytecode.clone());
+                }
+                bytecode
+            }
+        };
+        self.vm.execute(&bytecode)
+    }
+
+    /// JavaScriptコードをモジュールとして評価（import / export 宣言を受け付ける）
+    ///
+    /// モジュールの読み込みは未対応のため、import 宣言を含むソースは TypeError になる。
+    /// スクリプトとして評価した結果と混ざらないよう、コンパイル結果はキャッシュしない。
+    pub fn eval_module(&mut self, source: &str) -> JSResult<JSValue> {
+        let bytecode = Self::compile(source, parser::ParseGoal::Module)?;
+        self.compile_count += 1;
+        self.vm.execute(&bytecode)
+    }
+
+    /// ファイルを読み込んで評価
+    ///
+    /// エラーは `path:行:列: メッセージ` の形式で報告する（位置が分からない実行時エラーは
+    /// `path: メッセージ`）。
+    pub fn eval_file(&mut self, path: &Path) -> JSResult<JSValue> {
+        let location = path.display().to_string();
+        let source = fs::read_to_string(path)
+            .map_err(|err| JSError::InternalError(format!("{}: {}", location, err)))?;
+        self.eval(&source)
+            .map_err(|err| err.with_location(&location))
+    }
+
+    /// ソースをバイトコードにコンパイル（構文エラーには `行:列: ` を付ける）
+    fn compile(source: &str, goal: parser::ParseGoal) -> JSResult<compiler::BytecodeChunk> {
         let tokens = lexer::Lexer::new(source).tokenize()?;
-        let ast = parser::Parser::new(tokens).parse()?;
-        let bytecode = compiler::Compiler::new().compile(ast)?;
-        self.vm.execute(bytecode)
+        let mut parser = parser::Parser::new(tokens).with_goal(goal);
+        let ast = parser.parse().map_err(|err| {
+            let span = parser.position();
+            err.with_location(&format!("{}:{}", span.line, span.column))
+        })?;
+        compiler::Compiler::new().compile(ast)
     }
 }
 
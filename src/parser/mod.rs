@@ -1,5 +1,9 @@
+pub mod visitor;
+
 use crate::error::{JSError, JSResult};
-use crate::lexer::{Token, TokenKind};
+use crate::lexer::{Span, Token, TokenKind};
+
+pub use visitor::{Visitor, walk_expression, walk_program, walk_statement};
 
 /// AST（抽象構文木）のプログラムノード
 #[derive(Debug, Clone)]
@@ -7,9 +11,23 @@ pub struct Program {
     pub body: Vec<Statement>,
 }
 
-/// 文
+/// 文（種類と、先頭トークンから末尾までのソース位置）
+#[derive(Debug, Clone)]
+pub struct Statement {
+    pub kind: StatementKind,
+    pub span: Span,
+}
+
+impl Statement {
+    /// 新しい文ノードを作成
+    pub fn new(kind: StatementKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+}
+
+/// 文の種類
 #[derive(Debug, Clone)]
-pub enum Statement {
+pub enum StatementKind {
     Expression(Expression),
     VariableDeclaration {
         kind: VarKind,
@@ -22,9 +40,94 @@ pub enum Statement {
         params: Vec<String>,
         body: Vec<Statement>,
     },
+    Throw(Expression),
+    /// ブロック文 `{ ... }`
+    Block(Vec<Statement>),
+    Try {
+        block: Vec<Statement>,
+        /// catch 節の束縛名（`catch { ... }` の場合は None）
+        param: Option<String>,
+        handler: Vec<Statement>,
+    },
+    If {
+        test: Expression,
+        consequent: Vec<Statement>,
+        alternate: Option<Vec<Statement>>,
+    },
+    While {
+        test: Expression,
+        body: Vec<Statement>,
+    },
+    /// for (init; test; update) body（test を省略すると常に真）
+    For {
+        init: Option<Box<Statement>>,
+        test: Option<Expression>,
+        update: Option<Expression>,
+        body: Vec<Statement>,
+    },
+    /// for (kind pattern of iterable) body
+    ForOf {
+        kind: VarKind,
+        pattern: Pattern,
+        iterable: Expression,
+        body: Vec<Statement>,
+    },
+    /// 最も内側のループを抜ける
+    Break,
+    /// 最も内側のループの次の反復へ進む
+    Continue,
+    /// export 宣言（モジュールのトップレベルのみ）
+    Export(Box<Statement>),
+    /// import 宣言（モジュールのトップレベルのみ）
+    Import {
+        specifiers: Vec<ImportSpecifier>,
+        source: String,
+    },
     // TODO: 他の文を追加
 }
 
+/// 束縛パターン（for-of の左辺）
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    Identifier(String),
+    /// `[a, , b]`（None は読み飛ばす要素）
+    Array(Vec<Option<Pattern>>),
+}
+
+impl Pattern {
+    /// パターンが束縛する名前を出現順に集める
+    pub fn bound_names(&self, names: &mut Vec<String>) {
+        match self {
+            Pattern::Identifier(name) => names.push(name.clone()),
+            Pattern::Array(elements) => {
+                for element in elements.iter().flatten() {
+                    element.bound_names(names);
+                }
+            }
+        }
+    }
+}
+
+/// import 宣言で束縛する名前
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImportSpecifier {
+    /// `import x from "m"`
+    Default(String),
+    /// `import * as ns from "m"`
+    Namespace(String),
+    /// `import { imported as local } from "m"`
+    Named { imported: String, local: String },
+}
+
+/// パースの目標（スクリプトかモジュールか）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseGoal {
+    #[default]
+    Script,
+    /// import / export をトップレベルで受け付ける
+    Module,
+}
+
 /// 変数宣言の種類
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum VarKind {
@@ -33,11 +136,45 @@ pub enum VarKind {
     Const,
 }
 
-/// 式
+/// 式（種類と、先頭トークンから末尾までのソース位置）
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub kind: ExpressionKind,
+    pub span: Span,
+}
+
+impl Expression {
+    /// 新しい式ノードを作成
+    pub fn new(kind: ExpressionKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    /// 式の種類を取り出す
+    pub fn into_kind(mut self) -> ExpressionKind {
+        std::mem::replace(&mut self.kind, ExpressionKind::This)
+    }
+}
+
+impl Drop for Expression {
+    /// 左結合の連鎖（二項演算・メンバー参照・呼び出し）は長さの上限なくパースできるため、
+    /// 左辺を再帰で解放せず、連鎖の左端に向かってループで1段ずつ解放する
+    fn drop(&mut self) {
+        let mut kind = std::mem::replace(&mut self.kind, ExpressionKind::This);
+        while let ExpressionKind::Binary { left, .. }
+        | ExpressionKind::MemberAccess { object: left, .. }
+        | ExpressionKind::Call { callee: left, .. } = &mut kind
+        {
+            kind = std::mem::replace(&mut left.kind, ExpressionKind::This);
+        }
+    }
+}
+
+/// 式の種類
 #[derive(Debug, Clone)]
-pub enum Expression {
+pub enum ExpressionKind {
     Literal(Literal),
     Identifier(String),
+    This,
     Binary {
         op: BinaryOp,
         left: Box<Expression>,
@@ -62,7 +199,25 @@ pub enum Expression {
         callee: Box<Expression>,
         args: Vec<Expression>,
     },
+    New {
+        callee: Box<Expression>,
+        args: Vec<Expression>,
+    },
+    /// スプレッド引数（f(...args)。呼び出しの引数にだけ現れる）
+    Spread(Box<Expression>),
+    Conditional {
+        test: Box<Expression>,
+        consequent: Box<Expression>,
+        alternate: Box<Expression>,
+    },
     Function {
+        /// 名前付き関数式の名前（関数本体の中でのみ参照できる）
+        name: Option<String>,
+        params: Vec<String>,
+        body: Vec<Statement>,
+    },
+    /// アロー関数（式の本体は return 文1つの本体として保持する）
+    ArrowFunction {
         params: Vec<String>,
         body: Vec<Statement>,
     },
@@ -96,6 +251,8 @@ pub enum BinaryOp {
     Gt,
     LtEq,
     GtEq,
+    In,
+    Instanceof,
     And,
     Or,
     BitAnd,
@@ -118,41 +275,235 @@ pub enum UnaryOp {
     Delete,
 }
 
+/// 式・文のネストの深さの上限（デフォルト）
+pub const DEFAULT_MAX_DEPTH: usize = 1000;
+
 /// パーサー
 pub struct Parser {
     tokens: Vec<Token>,
     current: usize,
+    /// 現在の式・文のネストの深さ
+    depth: usize,
+    /// ネストの深さの上限（超えると SyntaxError）
+    max_depth: usize,
+    /// スクリプトかモジュールか
+    goal: ParseGoal,
+    /// strict モードのコードをパース中か（"use strict" 指令・モジュール）
+    strict: bool,
 }
 
 impl Parser {
-    /// 新しいパーサーを生成
-    pub fn new(tokens: Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+    /// 新しいパーサーを生成（Comment トークンは取り除く）
+    pub fn new(mut tokens: Vec<Token>) -> Self {
+        tokens.retain(|token| !matches!(token.kind, TokenKind::Comment(_)));
+        Self {
+            tokens,
+            current: 0,
+            depth: 0,
+            max_depth: DEFAULT_MAX_DEPTH,
+            goal: ParseGoal::Script,
+            strict: false,
+        }
+    }
+
+    /// パースの目標を設定（デフォルトはスクリプト）
+    pub fn with_goal(mut self, goal: ParseGoal) -> Self {
+        self.goal = goal;
+        self
+    }
+
+    /// ネストの深さの上限を設定
+    ///
+    /// 再帰下降でのスタックオーバーフローを防ぐため、`((((...))))` のような
+    /// 深すぎる入力は上限を超えた時点で SyntaxError とする。
+    pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// 現在のトークンの位置（パースが失敗した場合はエラーを検出した位置）
+    pub fn position(&self) -> Span {
+        self.peek().span
     }
 
     /// トークン列をパースしてASTを生成
     pub fn parse(&mut self) -> JSResult<Program> {
+        self.validate_tokens()?;
+        self.enter_program();
         let mut body = Vec::new();
 
         while !self.is_at_end() {
-            body.push(self.parse_statement()?);
+            body.push(self.parse_top_level_statement()?);
         }
 
         Ok(Program { body })
     }
 
+    /// エラーから回復しながらパースし、AST と全ての構文エラーを返す
+    ///
+    /// 文の途中でエラーが発生した場合は次の文の区切り（`;` または `}`）まで
+    /// 読み飛ばして続行する。エディタ連携等で全てのエラーをまとめて報告する用途。
+    /// 各エラーのメッセージには、検出した位置を `行:列: ` の形で付ける。
+    pub fn parse_recovering(&mut self) -> (Program, Vec<JSError>) {
+        let mut body = Vec::new();
+        let mut errors = Vec::new();
+        if let Err(err) = self.validate_tokens() {
+            errors.push(err);
+            return (Program { body }, errors);
+        }
+        self.enter_program();
+
+        while !self.is_at_end() {
+            match self.parse_top_level_statement() {
+                Ok(statement) => body.push(statement),
+                Err(err) => {
+                    let span = self.position();
+                    errors.push(err.with_location(&format!("{}:{}", span.line, span.column)));
+                    self.synchronize();
+                }
+            }
+        }
+
+        (Program { body }, errors)
+    }
+
+    /// プログラムの先頭で strict モードかどうかを決める（モジュールは常に strict）
+    fn enter_program(&mut self) {
+        self.strict = self.goal == ParseGoal::Module || self.has_use_strict_directive(self.current);
+    }
+
+    /// from の位置から始まる指令（先頭に並ぶ文字列リテラルの式文）に "use strict" があるか
+    ///
+    /// エスケープや括弧を含むものは指令にならないため、ソース上の長さも確かめる。
+    fn has_use_strict_directive(&self, from: usize) -> bool {
+        let mut i = from;
+        while let Some(token) = self.tokens.get(i) {
+            let TokenKind::String(value) = &token.kind else {
+                return false;
+            };
+            let next = &self.tokens[(i + 1).min(self.tokens.len() - 1)];
+            let ends_statement = match next.kind {
+                TokenKind::Semicolon => {
+                    i += 2;
+                    true
+                }
+                TokenKind::RightBrace | TokenKind::Eof => true,
+                _ => {
+                    i += 1;
+                    next.span.line > token.span.line
+                }
+            };
+            if !ends_statement {
+                return false;
+            }
+            if value == "use strict" && token.span.end - token.span.start == 12 {
+                return true;
+            }
+            if matches!(next.kind, TokenKind::RightBrace | TokenKind::Eof) {
+                return false;
+            }
+        }
+        false
+    }
+
+    /// 関数本体のブロックをパース（"use strict" 指令があれば本体を strict モードにする）
+    fn parse_function_body(&mut self) -> JSResult<Vec<Statement>> {
+        let outer = self.strict;
+        self.strict = outer || self.has_use_strict_directive(self.current + 1);
+        let body = self.parse_block();
+        self.strict = outer;
+        body
+    }
+
+    /// 文字列リテラルのトークンを検証（strict モードでは 8 進エスケープを禁止）
+    fn check_string_literal(&self, token: &Token) -> JSResult<()> {
+        if self.strict && token.legacy_octal_escape {
+            return Err(JSError::SyntaxError(
+                "Octal escape sequences are not allowed in strict mode".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// トークン列が Eof で終わっているか検証（途中で切れたトークン列を弾く）
+    fn validate_tokens(&self) -> JSResult<()> {
+        match self.tokens.last() {
+            Some(token) if token.kind == TokenKind::Eof => Ok(()),
+            _ => Err(JSError::InternalError(
+                "Token stream must end with Eof".to_string(),
+            )),
+        }
+    }
+
+    /// 次の文の区切りまでトークンを読み飛ばす
+    fn synchronize(&mut self) {
+        while !self.is_at_end() {
+            if matches!(
+                self.advance().kind,
+                TokenKind::Semicolon | TokenKind::RightBrace
+            ) {
+                return;
+            }
+        }
+    }
+
+    /// トップレベルの文をパース（モジュールでは import / export 宣言も受け付ける）
+    fn parse_top_level_statement(&mut self) -> JSResult<Statement> {
+        match (self.goal, &self.peek().kind) {
+            (ParseGoal::Module, TokenKind::Import) => self.parse_import_declaration(),
+            (ParseGoal::Module, TokenKind::Export) => self.parse_export_declaration(),
+            _ => self.parse_statement(),
+        }
+    }
+
     /// 文をパース
     fn parse_statement(&mut self) -> JSResult<Statement> {
         match &self.peek().kind {
+            TokenKind::Import | TokenKind::Export if self.goal == ParseGoal::Module => {
+                Err(JSError::SyntaxError(format!(
+                    "'{}' may only appear at the top level of a module",
+                    self.peek().kind.keyword_text().unwrap_or_default()
+                )))
+            }
+            // オブジェクトをスコープにする with 文は実装せず、識別子とも解釈しない
+            TokenKind::With => Err(JSError::SyntaxError(
+                "'with' statements are not supported".to_string(),
+            )),
+            TokenKind::Import => Err(JSError::SyntaxError(
+                "Cannot use import statement outside a module".to_string(),
+            )),
+            TokenKind::Export => Err(JSError::SyntaxError(
+                "Unexpected token 'export' outside a module".to_string(),
+            )),
             TokenKind::Var => self.parse_var_declaration(VarKind::Var),
             TokenKind::Let => self.parse_var_declaration(VarKind::Let),
             TokenKind::Const => self.parse_var_declaration(VarKind::Const),
             TokenKind::Return => self.parse_return_statement(),
             TokenKind::Function => self.parse_function_declaration(),
+            TokenKind::Throw => self.parse_throw_statement(),
+            TokenKind::Try => self.parse_try_statement(),
+            TokenKind::If => self.parse_if_statement(),
+            TokenKind::While => self.parse_while_statement(),
+            TokenKind::For => self.parse_for_statement(),
+            TokenKind::Break => self.parse_jump_statement(StatementKind::Break),
+            TokenKind::Continue => self.parse_jump_statement(StatementKind::Continue),
+            // 文の位置の `{` はオブジェクトリテラルではなくブロック
+            TokenKind::LeftBrace => {
+                let start = self.peek().span;
+                let body = self.parse_block()?;
+                Ok(Statement::new(
+                    StatementKind::Block(body),
+                    self.span_from(start),
+                ))
+            }
             _ => {
+                let start = self.peek().span;
                 let expr = self.parse_expression()?;
                 self.consume_semicolon();
-                Ok(Statement::Expression(expr))
+                Ok(Statement::new(
+                    StatementKind::Expression(expr),
+                    self.span_from(start),
+                ))
             }
         }
     }
@@ -164,7 +515,7 @@ impl Parser {
         }
         let mut body = Vec::new();
         while !self.check(&TokenKind::RightBrace) && !self.is_at_end() {
-            body.push(self.parse_statement()?);
+            body.push(self.nested(Self::parse_statement)?);
         }
         if !self.match_token(&TokenKind::RightBrace) {
             return Err(JSError::SyntaxError("Expected '}'".to_string()));
@@ -174,7 +525,7 @@ impl Parser {
 
     /// 関数宣言をパース: function name(params) { body }
     fn parse_function_declaration(&mut self) -> JSResult<Statement> {
-        self.advance(); // consume 'function'
+        let start = self.advance().span; // consume 'function'
         let name = if let TokenKind::Identifier(s) = &self.peek().kind {
             let s = s.clone();
             self.advance();
@@ -183,7 +534,16 @@ impl Parser {
             return Err(JSError::SyntaxError("Expected function name".to_string()));
         };
 
-        // parameters
+        let params = self.parse_parameter_list()?;
+        let body = self.parse_function_body()?;
+        Ok(Statement::new(
+            StatementKind::FunctionDeclaration { name, params, body },
+            self.span_from(start),
+        ))
+    }
+
+    /// 仮引数リストをパース: (a, b, c)
+    fn parse_parameter_list(&mut self) -> JSResult<Vec<String>> {
         if !self.match_token(&TokenKind::LeftParen) {
             return Err(JSError::SyntaxError("Expected '('".to_string()));
         }
@@ -195,6 +555,7 @@ impl Parser {
             } else {
                 return Err(JSError::SyntaxError("Expected parameter name".to_string()));
             }
+            // 末尾カンマ（function f(a, b,)）は次のループで ')' を検出して終了する
             if !self.check(&TokenKind::RightParen) && !self.match_token(&TokenKind::Comma) {
                 return Err(JSError::SyntaxError(
                     "Expected ',' in parameter list".to_string(),
@@ -204,9 +565,7 @@ impl Parser {
         if !self.match_token(&TokenKind::RightParen) {
             return Err(JSError::SyntaxError("Expected ')'".to_string()));
         }
-
-        let body = self.parse_block()?;
-        Ok(Statement::FunctionDeclaration { name, params, body })
+        Ok(params)
     }
 
     /// 式をパース
@@ -214,23 +573,150 @@ impl Parser {
         self.parse_assignment()
     }
 
+    /// ネストを1段深くして parse を実行（上限を超えたらエラー）
+    fn nested<T>(&mut self, parse: impl FnOnce(&mut Self) -> JSResult<T>) -> JSResult<T> {
+        if self.depth >= self.max_depth {
+            return Err(Self::depth_exceeded());
+        }
+        self.depth += 1;
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    fn depth_exceeded() -> JSError {
+        JSError::SyntaxError("Maximum parse depth exceeded".to_string())
+    }
+
     /// 代入式をパース（右結合）
     fn parse_assignment(&mut self) -> JSResult<Expression> {
-        let left = self.parse_logical_or()?;
-        if self.match_token(&TokenKind::Eq) {
-            let right = self.parse_assignment()?;
-            return Ok(Expression::Assignment {
+        self.nested(Self::parse_assignment_unguarded)
+    }
+
+    fn parse_assignment_unguarded(&mut self) -> JSResult<Expression> {
+        if self.is_arrow_function_start() {
+            return self.parse_arrow_function();
+        }
+        let start = self.current;
+        match self.parse_conditional() {
+            Ok(left) if self.check(&TokenKind::Eq) => self.finish_assignment(start, left),
+            result => result,
+        }
+    }
+
+    /// `=` 以降をパースして代入式を作る（start は代入先の先頭のトークン位置）
+    fn finish_assignment(&mut self, start: usize, left: Expression) -> JSResult<Expression> {
+        // 代入先は識別子かメンバー参照のみ（エラー位置は代入先の先頭にする）
+        if !matches!(
+            left.kind,
+            ExpressionKind::Identifier(_) | ExpressionKind::MemberAccess { .. }
+        ) {
+            self.current = start;
+            return Err(JSError::SyntaxError(
+                "Invalid left-hand side in assignment".to_string(),
+            ));
+        }
+        self.advance();
+        let right = self.parse_assignment()?;
+        let span = left.span.to(right.span);
+        Ok(Expression::new(
+            ExpressionKind::Assignment {
                 left: Box::new(left),
                 right: Box::new(right),
-            });
+            },
+            span,
+        ))
+    }
+
+    /// 現在位置からアロー関数が始まるか（`x =>` または `(...) =>`）
+    fn is_arrow_function_start(&self) -> bool {
+        let token_at = |offset: usize| self.tokens.get(self.current + offset).map(|t| &t.kind);
+        match &self.peek().kind {
+            TokenKind::Identifier(_) => token_at(1) == Some(&TokenKind::Arrow),
+            TokenKind::LeftParen => {
+                // 対応する ')' の直後が '=>' か
+                let mut depth = 0;
+                for (offset, token) in self.tokens[self.current..].iter().enumerate() {
+                    match token.kind {
+                        TokenKind::LeftParen => depth += 1,
+                        TokenKind::RightParen => {
+                            depth -= 1;
+                            if depth == 0 {
+                                return token_at(offset + 1) == Some(&TokenKind::Arrow);
+                            }
+                        }
+                        TokenKind::Eof => return false,
+                        _ => {}
+                    }
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// アロー関数をパース: x => body / (params) => body
+    ///
+    /// 本体がブロックでなければ式として読み、その値を返す本体にする。
+    fn parse_arrow_function(&mut self) -> JSResult<Expression> {
+        let start = self.peek().span;
+        let params = match &self.peek().kind {
+            TokenKind::Identifier(name) => {
+                let name = name.clone();
+                self.advance();
+                vec![name]
+            }
+            _ => self.parse_parameter_list()?,
+        };
+        if !self.match_token(&TokenKind::Arrow) {
+            return Err(JSError::SyntaxError("Expected '=>'".to_string()));
+        }
+        let body = if self.check(&TokenKind::LeftBrace) {
+            self.parse_function_body()?
+        } else {
+            let expr = self.parse_assignment()?;
+            let span = expr.span;
+            vec![Statement::new(StatementKind::Return(Some(expr)), span)]
+        };
+        Ok(Expression::new(
+            ExpressionKind::ArrowFunction { params, body },
+            self.span_from(start),
+        ))
+    }
+
+    /// 条件演算子をパース: test ? consequent : alternate（右結合）
+    fn parse_conditional(&mut self) -> JSResult<Expression> {
+        match self.parse_binary(0) {
+            Ok(test) if self.match_token(&TokenKind::Question) => self.finish_conditional(test),
+            result => result,
         }
-        Ok(left)
+    }
+
+    /// `?` 以降をパースして条件演算子の式を作る
+    fn finish_conditional(&mut self, test: Expression) -> JSResult<Expression> {
+        let consequent = self.parse_assignment()?;
+        if !self.match_token(&TokenKind::Colon) {
+            return Err(JSError::SyntaxError(
+                "Expected ':' in conditional expression".to_string(),
+            ));
+        }
+        let alternate = self.parse_assignment()?;
+
+        let span = test.span.to(alternate.span);
+        Ok(Expression::new(
+            ExpressionKind::Conditional {
+                test: Box::new(test),
+                consequent: Box::new(consequent),
+                alternate: Box::new(alternate),
+            },
+            span,
+        ))
     }
 
     /// 変数宣言をパース
     fn parse_var_declaration(&mut self, kind: VarKind) -> JSResult<Statement> {
         // consume keyword
-        self.advance();
+        let start = self.advance().span;
         // identifier
         let name = if let TokenKind::Identifier(s) = &self.peek().kind {
             let s = s.clone();
@@ -245,147 +731,485 @@ impl Parser {
             init = Some(self.parse_expression()?);
         }
         self.consume_semicolon();
-        Ok(Statement::VariableDeclaration { kind, name, init })
+        Ok(Statement::new(
+            StatementKind::VariableDeclaration { kind, name, init },
+            self.span_from(start),
+        ))
+    }
+
+    /// export 宣言をパース: export var / let / const / function
+    fn parse_export_declaration(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'export'
+        let declaration = match &self.peek().kind {
+            TokenKind::Var | TokenKind::Let | TokenKind::Const | TokenKind::Function => {
+                self.parse_statement()?
+            }
+            _ => {
+                return Err(JSError::SyntaxError(
+                    "Expected declaration after 'export'".to_string(),
+                ));
+            }
+        };
+        Ok(Statement::new(
+            StatementKind::Export(Box::new(declaration)),
+            self.span_from(start),
+        ))
+    }
+
+    /// import 宣言をパース
+    ///
+    /// `import "m"`・`import x from "m"`・`import * as ns from "m"`・
+    /// `import x, { a, b as c } from "m"` の形を受け付ける。
+    fn parse_import_declaration(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'import'
+        let mut specifiers = Vec::new();
+        if !matches!(self.peek().kind, TokenKind::String(_)) {
+            if let Some(name) = self.match_identifier() {
+                specifiers.push(ImportSpecifier::Default(name));
+                if !self.match_token(&TokenKind::Comma) {
+                    return self.finish_import(start, specifiers);
+                }
+            }
+            if self.match_token(&TokenKind::Star) {
+                if !self.match_token(&TokenKind::As) {
+                    return Err(JSError::SyntaxError("Expected 'as' after '*'".to_string()));
+                }
+                let local = self.expect_identifier()?;
+                specifiers.push(ImportSpecifier::Namespace(local));
+            } else if self.match_token(&TokenKind::LeftBrace) {
+                while !self.match_token(&TokenKind::RightBrace) {
+                    let imported = self.expect_identifier()?;
+                    let local = if self.match_token(&TokenKind::As) {
+                        self.expect_identifier()?
+                    } else {
+                        imported.clone()
+                    };
+                    specifiers.push(ImportSpecifier::Named { imported, local });
+                    if !self.check(&TokenKind::RightBrace) && !self.match_token(&TokenKind::Comma) {
+                        return Err(JSError::SyntaxError(
+                            "Expected ',' or '}' in import specifiers".to_string(),
+                        ));
+                    }
+                }
+            } else {
+                return Err(JSError::SyntaxError(
+                    "Expected import specifiers".to_string(),
+                ));
+            }
+        }
+        self.finish_import(start, specifiers)
+    }
+
+    /// import 宣言の `from "m"` 以降をパース（束縛がない場合は `from` を省略する）
+    fn finish_import(
+        &mut self,
+        start: Span,
+        specifiers: Vec<ImportSpecifier>,
+    ) -> JSResult<Statement> {
+        if !specifiers.is_empty() && !self.match_token(&TokenKind::From) {
+            return Err(JSError::SyntaxError(
+                "Expected 'from' after import specifiers".to_string(),
+            ));
+        }
+        let source = match &self.peek().kind {
+            TokenKind::String(s) => s.clone(),
+            _ => {
+                return Err(JSError::SyntaxError(
+                    "Expected module specifier string".to_string(),
+                ));
+            }
+        };
+        self.advance();
+        self.consume_semicolon();
+        Ok(Statement::new(
+            StatementKind::Import { specifiers, source },
+            self.span_from(start),
+        ))
     }
 
     /// return 文をパース
     fn parse_return_statement(&mut self) -> JSResult<Statement> {
-        self.advance(); // consume 'return'
+        let start = self.advance().span; // consume 'return'
         if self.check(&TokenKind::Semicolon)
             || self.check(&TokenKind::Eof)
             || self.check(&TokenKind::RightBrace)
         {
             self.consume_semicolon();
-            return Ok(Statement::Return(None));
+            return Ok(Statement::new(
+                StatementKind::Return(None),
+                self.span_from(start),
+            ));
         }
         let expr = self.parse_expression()?;
         self.consume_semicolon();
-        Ok(Statement::Return(Some(expr)))
+        Ok(Statement::new(
+            StatementKind::Return(Some(expr)),
+            self.span_from(start),
+        ))
+    }
+
+    /// throw 文をパース
+    fn parse_throw_statement(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'throw'
+        let expr = self.parse_expression()?;
+        self.consume_semicolon();
+        Ok(Statement::new(
+            StatementKind::Throw(expr),
+            self.span_from(start),
+        ))
     }
 
-    /// 論理和式をパース
-    fn parse_logical_or(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_logical_and()?;
+    /// try 文をパース: try { ... } catch (e) { ... }
+    fn parse_try_statement(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'try'
+        let block = self.parse_block()?;
 
-        while self.match_token(&TokenKind::Or) {
-            let right = self.parse_logical_and()?;
-            left = Expression::Binary {
-                op: BinaryOp::Or,
-                left: Box::new(left),
-                right: Box::new(right),
-            };
+        if self.check(&TokenKind::Finally) {
+            return Err(JSError::SyntaxError(
+                "'finally' is not supported yet".to_string(),
+            ));
+        }
+        if !self.match_token(&TokenKind::Catch) {
+            return Err(JSError::SyntaxError(
+                "Expected 'catch' after try block".to_string(),
+            ));
         }
 
-        Ok(left)
+        // catch の束縛は省略可能（ES2019 optional catch binding）
+        let param = if self.match_token(&TokenKind::LeftParen) {
+            let name = if let TokenKind::Identifier(s) = &self.peek().kind {
+                let s = s.clone();
+                self.advance();
+                s
+            } else {
+                return Err(JSError::SyntaxError(
+                    "Expected catch parameter name".to_string(),
+                ));
+            };
+            if !self.match_token(&TokenKind::RightParen) {
+                return Err(JSError::SyntaxError("Expected ')'".to_string()));
+            }
+            Some(name)
+        } else {
+            None
+        };
+
+        let handler = self.parse_block()?;
+        Ok(Statement::new(
+            StatementKind::Try {
+                block,
+                param,
+                handler,
+            },
+            self.span_from(start),
+        ))
     }
 
-    /// 論理積式をパース
-    fn parse_logical_and(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_equality()?;
+    /// if 文をパース: if (test) consequent [else alternate]
+    fn parse_if_statement(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'if'
+        let test = self.parse_condition()?;
+        let consequent = self.parse_body()?;
+        let alternate = if self.match_token(&TokenKind::Else) {
+            Some(self.parse_body()?)
+        } else {
+            None
+        };
+        Ok(Statement::new(
+            StatementKind::If {
+                test,
+                consequent,
+                alternate,
+            },
+            self.span_from(start),
+        ))
+    }
 
-        while self.match_token(&TokenKind::And) {
-            let right = self.parse_equality()?;
-            left = Expression::Binary {
-                op: BinaryOp::And,
-                left: Box::new(left),
-                right: Box::new(right),
-            };
+    /// while 文をパース: while (test) body
+    fn parse_while_statement(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'while'
+        let test = self.parse_condition()?;
+        let body = self.parse_body()?;
+        Ok(Statement::new(
+            StatementKind::While { test, body },
+            self.span_from(start),
+        ))
+    }
+
+    /// for 文をパース: for (init; test; update) body / for (var|let|const pattern of iterable) body
+    fn parse_for_statement(&mut self) -> JSResult<Statement> {
+        let start = self.advance().span; // consume 'for'
+        if !self.match_token(&TokenKind::LeftParen) {
+            return Err(JSError::SyntaxError("Expected '(' after 'for'".to_string()));
+        }
+        let init_start = self.peek().span;
+        let kind = match self.peek().kind {
+            TokenKind::Var => Some(VarKind::Var),
+            TokenKind::Let => Some(VarKind::Let),
+            TokenKind::Const => Some(VarKind::Const),
+            _ => None,
+        };
+        let init = match kind {
+            Some(kind) => {
+                self.advance();
+                let pattern = self.parse_binding_pattern()?;
+                if self.check(&TokenKind::Of) {
+                    return self.finish_for_of(start, kind, pattern);
+                }
+                let Pattern::Identifier(name) = pattern else {
+                    return Err(JSError::SyntaxError(
+                        "Destructuring declarations are only supported in for-of loops".to_string(),
+                    ));
+                };
+                let init = if self.match_token(&TokenKind::Eq) {
+                    Some(self.parse_expression()?)
+                } else {
+                    None
+                };
+                Some(Box::new(Statement::new(
+                    StatementKind::VariableDeclaration { kind, name, init },
+                    self.span_from(init_start),
+                )))
+            }
+            None if self.check(&TokenKind::Semicolon) => None,
+            None => {
+                let expr = self.parse_expression()?;
+                Some(Box::new(Statement::new(
+                    StatementKind::Expression(expr),
+                    self.span_from(init_start),
+                )))
+            }
+        };
+        if !self.match_token(&TokenKind::Semicolon) {
+            return Err(JSError::SyntaxError(
+                "Expected ';' after for loop initializer".to_string(),
+            ));
+        }
+        let test = if self.check(&TokenKind::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        if !self.match_token(&TokenKind::Semicolon) {
+            return Err(JSError::SyntaxError(
+                "Expected ';' after for loop condition".to_string(),
+            ));
+        }
+        let update = if self.check(&TokenKind::RightParen) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        if !self.match_token(&TokenKind::RightParen) {
+            return Err(JSError::SyntaxError("Expected ')'".to_string()));
         }
+        let body = self.parse_body()?;
+        Ok(Statement::new(
+            StatementKind::For {
+                init,
+                test,
+                update,
+                body,
+            },
+            self.span_from(start),
+        ))
+    }
 
-        Ok(left)
+    /// for-of 文の `of` 以降をパース
+    fn finish_for_of(
+        &mut self,
+        start: Span,
+        kind: VarKind,
+        pattern: Pattern,
+    ) -> JSResult<Statement> {
+        self.advance(); // consume 'of'
+        let iterable = self.parse_expression()?;
+        if !self.match_token(&TokenKind::RightParen) {
+            return Err(JSError::SyntaxError("Expected ')'".to_string()));
+        }
+        let body = self.parse_body()?;
+        Ok(Statement::new(
+            StatementKind::ForOf {
+                kind,
+                pattern,
+                iterable,
+                body,
+            },
+            self.span_from(start),
+        ))
     }
 
-    /// 等価式をパース
-    fn parse_equality(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_comparison()?;
+    /// break / continue 文をパース（ラベルは未対応）
+    fn parse_jump_statement(&mut self, kind: StatementKind) -> JSResult<Statement> {
+        let token = self.advance().clone();
+        if let TokenKind::Identifier(label) = &self.peek().kind
+            && self.peek().span.line == token.span.line
+        {
+            return Err(JSError::SyntaxError(format!(
+                "Labeled statements are not supported: '{}'",
+                label
+            )));
+        }
+        self.consume_semicolon();
+        Ok(Statement::new(kind, self.span_from(token.span)))
+    }
 
-        loop {
-            let op = match &self.peek().kind {
-                TokenKind::EqEq => BinaryOp::Eq,
-                TokenKind::NotEq => BinaryOp::NotEq,
-                TokenKind::EqEqEq => BinaryOp::StrictEq,
-                TokenKind::NotEqEq => BinaryOp::StrictNotEq,
-                _ => break,
-            };
+    /// 束縛パターンをパース: name または [a, , [b, c]]
+    fn parse_binding_pattern(&mut self) -> JSResult<Pattern> {
+        if let TokenKind::Identifier(name) = &self.peek().kind {
+            let name = name.clone();
             self.advance();
-            let right = self.parse_comparison()?;
-            left = Expression::Binary {
-                op,
-                left: Box::new(left),
-                right: Box::new(right),
-            };
+            return Ok(Pattern::Identifier(name));
         }
-
-        Ok(left)
+        if !self.match_token(&TokenKind::LeftBracket) {
+            return Err(JSError::SyntaxError(
+                "Expected identifier or '[' in binding pattern".to_string(),
+            ));
+        }
+        let mut elements = Vec::new();
+        while !self.check(&TokenKind::RightBracket) {
+            if self.match_token(&TokenKind::Comma) {
+                elements.push(None);
+                continue;
+            }
+            elements.push(Some(self.nested(Self::parse_binding_pattern)?));
+            if !self.check(&TokenKind::RightBracket) && !self.match_token(&TokenKind::Comma) {
+                return Err(JSError::SyntaxError(
+                    "Expected ',' in binding pattern".to_string(),
+                ));
+            }
+        }
+        self.advance(); // consume ']'
+        Ok(Pattern::Array(elements))
     }
 
-    /// 比較をパース
-    fn parse_comparison(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_term()?;
+    /// 括弧で囲んだ条件式をパース: (test)
+    fn parse_condition(&mut self) -> JSResult<Expression> {
+        if !self.match_token(&TokenKind::LeftParen) {
+            return Err(JSError::SyntaxError("Expected '('".to_string()));
+        }
+        let test = self.parse_expression()?;
+        if !self.match_token(&TokenKind::RightParen) {
+            return Err(JSError::SyntaxError("Expected ')'".to_string()));
+        }
+        Ok(test)
+    }
 
-        loop {
-            let op = match &self.peek().kind {
-                TokenKind::Lt => BinaryOp::Lt,
-                TokenKind::Gt => BinaryOp::Gt,
-                TokenKind::LtEq => BinaryOp::LtEq,
-                TokenKind::GtEq => BinaryOp::GtEq,
-                _ => break,
-            };
-            self.advance();
-            let right = self.parse_term()?;
-            left = Expression::Binary {
-                op,
-                left: Box::new(left),
-                right: Box::new(right),
-            };
+    /// if / while / for の本体をパース（ブロックまたは単一の文）
+    fn parse_body(&mut self) -> JSResult<Vec<Statement>> {
+        if self.check(&TokenKind::LeftBrace) {
+            self.parse_block()
+        } else {
+            Ok(vec![self.nested(Self::parse_statement)?])
         }
+    }
 
-        Ok(left)
+    /// 二項演算子とその優先順位（大きいほど強く結合する）を返す
+    fn binary_operator(kind: &TokenKind) -> Option<(BinaryOp, u8)> {
+        let operator = match kind {
+            TokenKind::Or => (BinaryOp::Or, 1),
+            TokenKind::And => (BinaryOp::And, 2),
+            TokenKind::BitOr => (BinaryOp::BitOr, 3),
+            TokenKind::BitXor => (BinaryOp::BitXor, 4),
+            TokenKind::BitAnd => (BinaryOp::BitAnd, 5),
+            TokenKind::EqEq => (BinaryOp::Eq, 6),
+            TokenKind::NotEq => (BinaryOp::NotEq, 6),
+            TokenKind::EqEqEq => (BinaryOp::StrictEq, 6),
+            TokenKind::NotEqEq => (BinaryOp::StrictNotEq, 6),
+            TokenKind::Lt => (BinaryOp::Lt, 7),
+            TokenKind::Gt => (BinaryOp::Gt, 7),
+            TokenKind::LtEq => (BinaryOp::LtEq, 7),
+            TokenKind::GtEq => (BinaryOp::GtEq, 7),
+            TokenKind::In => (BinaryOp::In, 7),
+            TokenKind::Instanceof => (BinaryOp::Instanceof, 7),
+            TokenKind::LeftShift => (BinaryOp::LeftShift, 8),
+            TokenKind::RightShift => (BinaryOp::RightShift, 8),
+            TokenKind::UnsignedRightShift => (BinaryOp::UnsignedRightShift, 8),
+            TokenKind::Plus => (BinaryOp::Add, 9),
+            TokenKind::Minus => (BinaryOp::Sub, 9),
+            TokenKind::Star => (BinaryOp::Mul, 10),
+            TokenKind::Slash => (BinaryOp::Div, 10),
+            TokenKind::Percent => (BinaryOp::Mod, 10),
+            _ => return None,
+        };
+        Some(operator)
     }
 
-    /// 加減乗除のパース
-    fn parse_term(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_factor()?;
+    /// 二項演算式をパース（優先順位が min_precedence 以上の演算子だけを取り込む）
+    ///
+    /// 同じ優先順位の演算子はループで左辺に積み上げ（左結合）、右辺はより強く
+    /// 結合する演算子だけを取り込むよう再帰する。優先順位ごとに関数を分けないのは、
+    /// 括弧のネスト1段あたりのスタック使用量を抑えるため。
+    fn parse_binary(&mut self, min_precedence: u8) -> JSResult<Expression> {
+        match self.parse_exponent() {
+            Ok(left) => self.finish_binary(left, min_precedence),
+            result => result,
+        }
+    }
 
-        loop {
-            let op = match &self.peek().kind {
-                TokenKind::Plus => BinaryOp::Add,
-                TokenKind::Minus => BinaryOp::Sub,
-                _ => break,
-            };
+    /// 左辺に続く二項演算子をパースして二項演算式を作る
+    fn finish_binary(&mut self, mut left: Expression, min_precedence: u8) -> JSResult<Expression> {
+        while let Some((op, precedence)) = Self::binary_operator(&self.peek().kind)
+            && precedence >= min_precedence
+        {
             self.advance();
-            let right = self.parse_factor()?;
-            left = Expression::Binary {
-                op,
-                left: Box::new(left),
-                right: Box::new(right),
-            };
+            let right = self.parse_binary(precedence + 1)?;
+            left = Self::binary(op, left, right);
         }
-
         Ok(left)
     }
 
-    /// 乗算式をパース
-    fn parse_factor(&mut self) -> JSResult<Expression> {
-        let mut left = self.parse_unary()?;
+    /// べき乗式をパース（右結合）
+    ///
+    /// `-2 ** 2` のように括弧のない単項演算子を左辺に取ることはできない。
+    fn parse_exponent(&mut self) -> JSResult<Expression> {
+        let starts_with_unary = matches!(
+            self.peek().kind,
+            TokenKind::Plus
+                | TokenKind::Minus
+                | TokenKind::Not
+                | TokenKind::BitNot
+                | TokenKind::Typeof
+                | TokenKind::Void
+                | TokenKind::Delete
+        );
+        match self.parse_unary() {
+            Ok(left) if self.check(&TokenKind::Power) => {
+                self.finish_exponent(left, starts_with_unary)
+            }
+            result => result,
+        }
+    }
 
-        loop {
-            let op = match &self.peek().kind {
-                TokenKind::Star => BinaryOp::Mul,
-                TokenKind::Slash => BinaryOp::Div,
-                TokenKind::Percent => BinaryOp::Mod,
-                _ => break,
-            };
-            self.advance();
-            let right = self.parse_unary()?;
-            left = Expression::Binary {
+    /// `**` 以降をパースしてべき乗の式を作る
+    fn finish_exponent(
+        &mut self,
+        left: Expression,
+        starts_with_unary: bool,
+    ) -> JSResult<Expression> {
+        if starts_with_unary {
+            return Err(JSError::SyntaxError(
+                "Unary operator used immediately before exponentiation expression; \
+                 parentheses must be used to disambiguate operator precedence"
+                    .to_string(),
+            ));
+        }
+        self.advance();
+        let right = self.nested(Self::parse_exponent)?;
+        Ok(Self::binary(BinaryOp::Power, left, right))
+    }
+
+    /// 二項演算ノードを作成（左辺の先頭から右辺の末尾までを Span とする）
+    fn binary(op: BinaryOp, left: Expression, right: Expression) -> Expression {
+        let span = left.span.to(right.span);
+        Expression::new(
+            ExpressionKind::Binary {
                 op,
                 left: Box::new(left),
                 right: Box::new(right),
-            };
-        }
-
-        Ok(left)
+            },
+            span,
+        )
     }
 
     /// 単項式をパース
@@ -400,115 +1224,217 @@ impl Parser {
             TokenKind::Delete => UnaryOp::Delete,
             _ => return self.parse_postfix(),
         };
-        self.advance();
-        let arg = self.parse_unary()?;
-        Ok(Expression::Unary {
-            op,
-            arg: Box::new(arg),
-        })
+        self.finish_unary(op)
+    }
+
+    /// 単項演算子のトークンから単項式をパース
+    fn finish_unary(&mut self, op: UnaryOp) -> JSResult<Expression> {
+        let start = self.advance().span;
+        let arg = self.nested(Self::parse_unary)?;
+        let span = start.to(arg.span);
+        Ok(Expression::new(
+            ExpressionKind::Unary {
+                op,
+                arg: Box::new(arg),
+            },
+            span,
+        ))
     }
 
     /// 後置式をパース（メンバーアクセス等）
     fn parse_postfix(&mut self) -> JSResult<Expression> {
-        let mut expr = self.parse_primary()?;
+        let expr = if self.check(&TokenKind::New) {
+            self.parse_new_expression()
+        } else {
+            self.parse_primary()
+        };
+        match expr {
+            Ok(expr) => self.parse_postfix_operators(expr),
+            result => result,
+        }
+    }
 
+    /// 式に続くメンバーアクセスと呼び出しをパース
+    fn parse_postfix_operators(&mut self, mut expr: Expression) -> JSResult<Expression> {
         loop {
-            match &self.peek().kind {
-                TokenKind::Dot => {
-                    self.advance();
-                    let property = match &self.peek().kind {
-                        TokenKind::Identifier(s) => {
-                            let s = s.clone();
-                            self.advance();
-                            Expression::Literal(Literal::String(s))
-                        }
-                        _ => {
-                            return Err(JSError::SyntaxError(
-                                "Expected property name after '.'".to_string(),
-                            ));
-                        }
-                    };
-                    expr = Expression::MemberAccess {
-                        object: Box::new(expr),
-                        property: Box::new(property),
-                        computed: false,
-                    };
+            expr = match &self.peek().kind {
+                TokenKind::Dot | TokenKind::LeftBracket => self.parse_member_access(expr),
+                TokenKind::LeftParen => self.parse_call(expr),
+                _ => return Ok(expr),
+            }?;
+        }
+    }
+
+    /// 呼び出しをパース: callee(args)
+    fn parse_call(&mut self, callee: Expression) -> JSResult<Expression> {
+        self.advance(); // consume '('
+        match self.parse_call_arguments() {
+            Ok(args) => Ok(self.call(callee, args)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// 呼び出しノードを作成（呼び出し対象の先頭から直前のトークンまでを Span とする）
+    fn call(&self, callee: Expression, args: Vec<Expression>) -> Expression {
+        let span = self.span_from(callee.span);
+        Expression::new(
+            ExpressionKind::Call {
+                callee: Box::new(callee),
+                args,
+            },
+            span,
+        )
+    }
+
+    /// new 式をパース: new Callee(args)
+    fn parse_new_expression(&mut self) -> JSResult<Expression> {
+        let start = self.advance().span; // consume 'new'
+
+        // 呼び出し対象はメンバーアクセスまで（最初の括弧は new の引数になる）
+        let mut callee = if self.check(&TokenKind::New) {
+            self.nested(Self::parse_new_expression)?
+        } else {
+            self.parse_primary()?
+        };
+        while self.check(&TokenKind::Dot) || self.check(&TokenKind::LeftBracket) {
+            callee = self.parse_member_access(callee)?;
+        }
+
+        // 引数リストは省略可能（new Foo）
+        let args = if self.match_token(&TokenKind::LeftParen) {
+            self.parse_call_arguments()?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Expression::new(
+            ExpressionKind::New {
+                callee: Box::new(callee),
+                args,
+            },
+            self.span_from(start),
+        ))
+    }
+
+    /// メンバーアクセスをパース: obj.prop / obj[expr]
+    fn parse_member_access(&mut self, object: Expression) -> JSResult<Expression> {
+        if self.match_token(&TokenKind::Dot) {
+            let property = match &self.peek().kind {
+                TokenKind::Identifier(s) => {
+                    let s = s.clone();
+                    let span = self.advance().span;
+                    Expression::new(ExpressionKind::Literal(Literal::String(s)), span)
                 }
-                TokenKind::LeftBracket => {
-                    self.advance();
-                    let property = self.parse_expression()?;
-                    if !self.match_token(&TokenKind::RightBracket) {
-                        return Err(JSError::SyntaxError("Expected ']'".to_string()));
+                // 予約語もプロパティ名として使える（obj.delete 等）
+                kind => match kind.keyword_text() {
+                    Some(text) => {
+                        let span = self.advance().span;
+                        Expression::new(
+                            ExpressionKind::Literal(Literal::String(text.to_string())),
+                            span,
+                        )
                     }
-                    expr = Expression::MemberAccess {
-                        object: Box::new(expr),
-                        property: Box::new(property),
-                        computed: true,
-                    };
-                }
-                TokenKind::LeftParen => {
-                    self.advance();
-                    let args = self.parse_call_arguments()?;
-                    if !self.match_token(&TokenKind::RightParen) {
-                        return Err(JSError::SyntaxError("Expected ')'".to_string()));
+                    None => {
+                        return Err(JSError::SyntaxError(
+                            "Expected property name after '.'".to_string(),
+                        ));
                     }
-                    expr = Expression::Call {
-                        callee: Box::new(expr),
-                        args,
-                    };
-                }
-                _ => break,
-            }
+                },
+            };
+            let span = object.span.to(property.span);
+            return Ok(Expression::new(
+                ExpressionKind::MemberAccess {
+                    object: Box::new(object),
+                    property: Box::new(property),
+                    computed: false,
+                },
+                span,
+            ));
         }
 
-        Ok(expr)
+        self.advance(); // consume '['
+        let property = self.parse_expression()?;
+        if !self.match_token(&TokenKind::RightBracket) {
+            return Err(JSError::SyntaxError("Expected ']'".to_string()));
+        }
+        let span = self.span_from(object.span);
+        Ok(Expression::new(
+            ExpressionKind::MemberAccess {
+                object: Box::new(object),
+                property: Box::new(property),
+                computed: true,
+            },
+            span,
+        ))
     }
 
     /// 基本式をパース
     fn parse_primary(&mut self) -> JSResult<Expression> {
+        match self.peek().kind {
+            TokenKind::LeftParen => self.parse_parenthesized(),
+            TokenKind::LeftBracket => self.parse_array_literal(),
+            TokenKind::LeftBrace => self.parse_object_literal(),
+            TokenKind::Function => self.parse_function_expression(),
+            _ => self.parse_primary_token(),
+        }
+    }
+
+    /// 括弧で囲まれた式をパース
+    fn parse_parenthesized(&mut self) -> JSResult<Expression> {
+        self.advance(); // consume '('
+        match self.parse_expression() {
+            Ok(_) if !self.match_token(&TokenKind::RightParen) => {
+                Err(JSError::SyntaxError("Expected ')'".to_string()))
+            }
+            result => result,
+        }
+    }
+
+    /// 1つのトークンからなる基本式をパース（リテラル・識別子・this）
+    fn parse_primary_token(&mut self) -> JSResult<Expression> {
         let token = self.peek().clone();
+        let literal = |lit| Ok(Expression::new(ExpressionKind::Literal(lit), token.span));
 
         match &token.kind {
             TokenKind::NumberLiteral(n) => {
                 self.advance();
-                Ok(Expression::Literal(Literal::Number((*n).parse().unwrap())))
+                let value = n
+                    .parse()
+                    .map_err(|_| JSError::SyntaxError(format!("Invalid number literal: {}", n)))?;
+                literal(Literal::Number(value))
             }
             TokenKind::String(s) => {
-                let s = s.clone();
+                self.check_string_literal(&token)?;
                 self.advance();
-                Ok(Expression::Literal(Literal::String(s)))
+                literal(Literal::String(s.clone()))
             }
             TokenKind::True => {
                 self.advance();
-                Ok(Expression::Literal(Literal::Boolean(true)))
+                literal(Literal::Boolean(true))
             }
             TokenKind::False => {
                 self.advance();
-                Ok(Expression::Literal(Literal::Boolean(false)))
+                literal(Literal::Boolean(false))
             }
             TokenKind::Null => {
                 self.advance();
-                Ok(Expression::Literal(Literal::Null))
+                literal(Literal::Null)
             }
             TokenKind::Undefined => {
                 self.advance();
-                Ok(Expression::Literal(Literal::Undefined))
+                literal(Literal::Undefined)
             }
             TokenKind::Identifier(s) => {
-                let s = s.clone();
                 self.advance();
-                Ok(Expression::Identifier(s))
+                Ok(Expression::new(
+                    ExpressionKind::Identifier(s.clone()),
+                    token.span,
+                ))
             }
-            TokenKind::LeftParen => {
+            TokenKind::This => {
                 self.advance();
-                let expr = self.parse_expression()?;
-                if !self.match_token(&TokenKind::RightParen) {
-                    return Err(JSError::SyntaxError("Expected ')'".to_string()));
-                }
-                Ok(expr)
+                Ok(Expression::new(ExpressionKind::This, token.span))
             }
-            TokenKind::LeftBracket => self.parse_array_literal(),
-            TokenKind::LeftBrace => self.parse_object_literal(),
             _ => Err(JSError::SyntaxError(format!(
                 "Unexpected token: {:?}",
                 token.kind
@@ -516,17 +1442,35 @@ impl Parser {
         }
     }
 
+    /// 関数式をパース: function [name](params) { body }
+    fn parse_function_expression(&mut self) -> JSResult<Expression> {
+        let start = self.advance().span; // consume 'function'
+        let name = if let TokenKind::Identifier(s) = &self.peek().kind {
+            let s = s.clone();
+            self.advance();
+            Some(s)
+        } else {
+            None
+        };
+
+        let params = self.parse_parameter_list()?;
+        let body = self.parse_function_body()?;
+        Ok(Expression::new(
+            ExpressionKind::Function { name, params, body },
+            self.span_from(start),
+        ))
+    }
+
     /// 配列リテラルをパース: [1, 2, 3]
     fn parse_array_literal(&mut self) -> JSResult<Expression> {
-        self.advance(); // consume '['
+        let start = self.advance().span; // consume '['
 
         let mut elements = Vec::new();
 
         while !self.check(&TokenKind::RightBracket) && !self.is_at_end() {
             // 空要素をサポート (例: [1,,3])
             if self.check(&TokenKind::Comma) {
-                elements.push(Expression::Literal(Literal::Undefined));
-                self.advance();
+                elements.push(self.parse_elision());
                 continue;
             }
 
@@ -543,12 +1487,15 @@ impl Parser {
             return Err(JSError::SyntaxError("Expected ']'".to_string()));
         }
 
-        Ok(Expression::ArrayLiteral(elements))
+        Ok(Expression::new(
+            ExpressionKind::ArrayLiteral(elements),
+            self.span_from(start),
+        ))
     }
 
     /// オブジェクトリテラルをパース: { key: value }
     fn parse_object_literal(&mut self) -> JSResult<Expression> {
-        self.advance(); // consume '{'
+        let start = self.advance().span; // consume '{'
 
         let mut properties = Vec::new();
 
@@ -556,20 +1503,40 @@ impl Parser {
             // プロパティキーをパース
             let key = match &self.peek().kind {
                 TokenKind::Identifier(s) => s.clone(),
-                TokenKind::String(s) => s.clone(),
-                _ => return Err(JSError::SyntaxError("Expected property key".to_string())),
+                TokenKind::String(s) => {
+                    self.check_string_literal(self.peek())?;
+                    s.clone()
+                }
+                kind => match kind.keyword_text() {
+                    Some(text) => text.to_string(),
+                    None => return Err(JSError::SyntaxError("Expected property key".to_string())),
+                },
             };
-            self.advance();
-
-            // ':' を期待
-            if !self.match_token(&TokenKind::Colon) {
-                return Err(JSError::SyntaxError(
-                    "Expected ':' after property key".to_string(),
-                ));
-            }
+            let key_span = self.advance().span;
+
+            let value = if self.check(&TokenKind::LeftParen) {
+                // メソッド短縮記法: key(params) { body }
+                let params = self.parse_parameter_list()?;
+                let body = self.parse_function_body()?;
+                Expression::new(
+                    ExpressionKind::Function {
+                        name: None,
+                        params,
+                        body,
+                    },
+                    self.span_from(key_span),
+                )
+            } else {
+                // ':' を期待
+                if !self.match_token(&TokenKind::Colon) {
+                    return Err(JSError::SyntaxError(
+                        "Expected ':' after property key".to_string(),
+                    ));
+                }
 
-            // 値をパース
-            let value = self.parse_assignment()?;
+                // 値をパース
+                self.parse_assignment()?
+            };
 
             properties.push((key, value));
 
@@ -584,23 +1551,29 @@ impl Parser {
             return Err(JSError::SyntaxError("Expected '}'".to_string()));
         }
 
-        Ok(Expression::ObjectLiteral(properties))
+        Ok(Expression::new(
+            ExpressionKind::ObjectLiteral(properties),
+            self.span_from(start),
+        ))
     }
 
-    /// 呼び出し引数リストをパース
+    /// 呼び出し引数リストを閉じ括弧までパース
     fn parse_call_arguments(&mut self) -> JSResult<Vec<Expression>> {
         let mut args = Vec::new();
 
         while !self.check(&TokenKind::RightParen) && !self.is_at_end() {
-            // 空の引数をサポート (例: func(1,,3))
-            if self.check(&TokenKind::Comma) {
-                args.push(Expression::Literal(Literal::Undefined));
-                self.advance();
-                continue;
-            }
-
-            args.push(self.parse_expression()?);
+            let arg = match self.peek().kind {
+                // 空の引数をサポート (例: func(1,,3))
+                TokenKind::Comma => {
+                    args.push(self.parse_elision());
+                    continue;
+                }
+                TokenKind::DotDotDot => self.parse_spread_argument(),
+                _ => self.parse_expression(),
+            };
+            args.push(arg?);
 
+            // 末尾カンマ（f(a, b,)）は余分な引数を追加せずに ')' で終了する
             if !self.check(&TokenKind::RightParen) && !self.match_token(&TokenKind::Comma) {
                 return Err(JSError::SyntaxError(
                     "Expected ',' or ')' in function call".to_string(),
@@ -608,20 +1581,44 @@ impl Parser {
             }
         }
 
+        if !self.match_token(&TokenKind::RightParen) {
+            return Err(JSError::SyntaxError("Expected ')'".to_string()));
+        }
         Ok(args)
     }
 
-    /// 現在のトークンを取得
+    /// 空の要素・引数のカンマを読み、undefined の式を返す
+    fn parse_elision(&mut self) -> Expression {
+        let span = self.advance().span; // consume ','
+        Expression::new(ExpressionKind::Literal(Literal::Undefined), span)
+    }
+
+    /// スプレッド引数をパース: ...expr
+    fn parse_spread_argument(&mut self) -> JSResult<Expression> {
+        let start = self.advance().span; // consume '...'
+        let arg = self.parse_expression()?;
+        let span = start.to(arg.span);
+        Ok(Expression::new(ExpressionKind::Spread(Box::new(arg)), span))
+    }
+
+    /// 現在のトークンを取得（終端では常に末尾の Eof を返す）
     fn peek(&self) -> &Token {
-        &self.tokens[self.current]
+        &self.tokens[self.current.min(self.tokens.len() - 1)]
     }
 
-    /// トークンを1つ進めて取得
+    /// 現在のトークンを取得して1つ進める（Eof より先には進まない）
     fn advance(&mut self) -> &Token {
+        let index = self.current.min(self.tokens.len() - 1);
         if !self.is_at_end() {
             self.current += 1;
         }
-        &self.tokens[self.current - 1]
+        &self.tokens[index]
+    }
+
+    /// start から直前に消費したトークンの末尾までの Span を取得
+    fn span_from(&self, start: Span) -> Span {
+        let previous = self.current.saturating_sub(1).min(self.tokens.len() - 1);
+        start.to(self.tokens[previous].span)
     }
 
     /// 現在のトークンが指定の種類かチェック
@@ -632,6 +1629,22 @@ impl Parser {
         std::mem::discriminant(&self.peek().kind) == std::mem::discriminant(kind)
     }
 
+    /// 現在のトークンが識別子なら進めてその名前を返す
+    fn match_identifier(&mut self) -> Option<String> {
+        let TokenKind::Identifier(name) = &self.peek().kind else {
+            return None;
+        };
+        let name = name.clone();
+        self.advance();
+        Some(name)
+    }
+
+    /// 識別子を読み進めてその名前を返す（識別子でなければ SyntaxError）
+    fn expect_identifier(&mut self) -> JSResult<String> {
+        self.match_identifier()
+            .ok_or_else(|| JSError::SyntaxError("Expected identifier".to_string()))
+    }
+
     /// 現在のトークンが指定の種類なら進めてtrueを返す
     fn match_token(&mut self, kind: &TokenKind) -> bool {
         if self.check(kind) {
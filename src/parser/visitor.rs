@@ -0,0 +1,157 @@
+use super::{Expression, ExpressionKind, Program, Statement, StatementKind};
+
+/// AST を走査するビジター
+///
+/// 各 `visit_*` はデフォルトで対応する `walk_*` を呼び出して子ノードへ再帰する。
+/// 解析パスは関心のあるノードの `visit_*` だけを上書きし、
+/// 子ノードの走査を続けたい場合は自分で `walk_*` を呼び出す。
+pub trait Visitor {
+    /// プログラムを訪問
+    fn visit_program(&mut self, program: &Program) {
+        walk_program(self, program);
+    }
+
+    /// 文を訪問
+    fn visit_statement(&mut self, statement: &Statement) {
+        walk_statement(self, statement);
+    }
+
+    /// 式を訪問
+    fn visit_expression(&mut self, expression: &Expression) {
+        walk_expression(self, expression);
+    }
+}
+
+/// プログラム直下の文を順に訪問
+pub fn walk_program<V: Visitor + ?Sized>(visitor: &mut V, program: &Program) {
+    for statement in &program.body {
+        visitor.visit_statement(statement);
+    }
+}
+
+/// 文の子ノードを訪問
+pub fn walk_statement<V: Visitor + ?Sized>(visitor: &mut V, statement: &Statement) {
+    match &statement.kind {
+        StatementKind::Expression(expr) => visitor.visit_expression(expr),
+        StatementKind::VariableDeclaration { init, .. } => {
+            if let Some(expr) = init {
+                visitor.visit_expression(expr);
+            }
+        }
+        StatementKind::Return(expr) => {
+            if let Some(expr) = expr {
+                visitor.visit_expression(expr);
+            }
+        }
+        StatementKind::FunctionDeclaration { body, .. } => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::Throw(expr) => visitor.visit_expression(expr),
+        StatementKind::Block(body) => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::Try { block, handler, .. } => {
+            for statement in block.iter().chain(handler) {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::If {
+            test,
+            consequent,
+            alternate,
+        } => {
+            visitor.visit_expression(test);
+            for statement in consequent.iter().chain(alternate.iter().flatten()) {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::While { test, body } => {
+            visitor.visit_expression(test);
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::For {
+            init,
+            test,
+            update,
+            body,
+        } => {
+            if let Some(init) = init {
+                visitor.visit_statement(init);
+            }
+            for expr in test.iter().chain(update) {
+                visitor.visit_expression(expr);
+            }
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::ForOf { iterable, body, .. } => {
+            visitor.visit_expression(iterable);
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        StatementKind::Export(declaration) => visitor.visit_statement(declaration),
+        StatementKind::Break | StatementKind::Continue | StatementKind::Import { .. } => {}
+    }
+}
+
+/// 式の子ノードを訪問
+pub fn walk_expression<V: Visitor + ?Sized>(visitor: &mut V, expression: &Expression) {
+    match &expression.kind {
+        ExpressionKind::Literal(_) | ExpressionKind::Identifier(_) | ExpressionKind::This => {}
+        ExpressionKind::Binary { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        ExpressionKind::Unary { arg, .. } | ExpressionKind::Spread(arg) => {
+            visitor.visit_expression(arg)
+        }
+        ExpressionKind::Conditional {
+            test,
+            consequent,
+            alternate,
+        } => {
+            visitor.visit_expression(test);
+            visitor.visit_expression(consequent);
+            visitor.visit_expression(alternate);
+        }
+        ExpressionKind::Assignment { left, right } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        ExpressionKind::ArrayLiteral(elements) => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        ExpressionKind::ObjectLiteral(properties) => {
+            for (_, value) in properties {
+                visitor.visit_expression(value);
+            }
+        }
+        ExpressionKind::MemberAccess {
+            object, property, ..
+        } => {
+            visitor.visit_expression(object);
+            visitor.visit_expression(property);
+        }
+        ExpressionKind::Call { callee, args } | ExpressionKind::New { callee, args } => {
+            visitor.visit_expression(callee);
+            for arg in args {
+                visitor.visit_expression(arg);
+            }
+        }
+        ExpressionKind::Function { body, .. } | ExpressionKind::ArrowFunction { body, .. } => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+    }
+}
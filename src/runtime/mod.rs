@@ -1,5 +1,6 @@
+use crate::error::{JSError, JSResult};
 use crate::value::JSValue;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -8,6 +9,17 @@ use std::rc::Rc;
 pub struct Environment {
     pub bindings: Rc<RefCell<FxHashMap<String, JSValue>>>,
     pub outer: Option<Rc<RefCell<Environment>>>,
+    /// 宣言済みだが未初期化（TDZ）の束縛
+    uninitialized: Rc<RefCell<FxHashSet<String>>>,
+}
+
+/// スコープの束縛を複製した状態（Environment::snapshot で作成）
+///
+/// 束縛の値そのものは共有するため、オブジェクトの中身の変更は巻き戻らない。
+#[derive(Debug, Clone)]
+pub struct EnvSnapshot {
+    bindings: FxHashMap<String, JSValue>,
+    uninitialized: FxHashSet<String>,
 }
 
 impl Environment {
@@ -15,6 +27,7 @@ impl Environment {
         Self {
             bindings: Rc::new(RefCell::new(FxHashMap::default())),
             outer: None,
+            uninitialized: Rc::new(RefCell::new(FxHashSet::default())),
         }
     }
 
@@ -22,32 +35,100 @@ impl Environment {
         Self {
             bindings: Rc::new(RefCell::new(FxHashMap::default())),
             outer: Some(outer),
+            uninitialized: Rc::new(RefCell::new(FxHashSet::default())),
         }
     }
 
+    /// 束縛を作成（TDZ 状態の束縛は初期化される）
     pub fn define(&self, name: String, value: JSValue) {
+        self.initialize(&name);
         self.bindings.borrow_mut().insert(name, value);
     }
 
+    /// このスコープに束縛があるか（外側のスコープは含まない）
+    pub fn has_own_binding(&self, name: &str) -> bool {
+        self.bindings.borrow().contains_key(name)
+    }
+
+    /// 未初期化（TDZ）の束縛を作成
+    pub fn declare_uninitialized(&self, name: String) {
+        self.bindings
+            .borrow_mut()
+            .insert(name.clone(), JSValue::Undefined);
+        self.uninitialized.borrow_mut().insert(name);
+    }
+
+    /// 既存の束縛に代入（見つからない・TDZ 中なら false）
     pub fn set(&self, name: &str, value: JSValue) -> bool {
-        if self.bindings.borrow().contains_key(name) {
-            self.bindings.borrow_mut().insert(name.to_string(), value);
-            return true;
+        matches!(self.assign(name, value), Ok(None))
+    }
+
+    /// 束縛の値を取得（見つからない・TDZ 中なら None）
+    pub fn get(&self, name: &str) -> Option<JSValue> {
+        self.lookup(name).ok().flatten()
+    }
+
+    /// 既存の束縛に代入（TDZ 中なら ReferenceError、見つからなければ値を返す）
+    ///
+    /// 束縛の値は既存のエントリを直接書き換え、キーの再確保を避ける。
+    /// 見つからなかった場合は呼び出し側が値を複製せずに使えるよう、そのまま返す。
+    pub(crate) fn assign(&self, name: &str, value: JSValue) -> JSResult<Option<JSValue>> {
+        if let Some(slot) = self.bindings.borrow_mut().get_mut(name) {
+            self.check_initialized(name)?;
+            *slot = value;
+            return Ok(None);
         }
-        if let Some(ref outer) = self.outer {
-            return outer.borrow().set(name, value);
+        match &self.outer {
+            Some(outer) => outer.borrow().assign(name, value),
+            None => Ok(Some(value)),
         }
-        false
     }
 
-    pub fn get(&self, name: &str) -> Option<JSValue> {
+    /// 束縛の値を取得（TDZ 中なら ReferenceError）
+    pub(crate) fn lookup(&self, name: &str) -> JSResult<Option<JSValue>> {
         if let Some(v) = self.bindings.borrow().get(name) {
-            return Some(v.clone());
+            self.check_initialized(name)?;
+            return Ok(Some(v.clone()));
+        }
+        match &self.outer {
+            Some(outer) => outer.borrow().lookup(name),
+            None => Ok(None),
+        }
+    }
+
+    /// このスコープの束縛を複製して保存する（外側のスコープは含まない）
+    pub fn snapshot(&self) -> EnvSnapshot {
+        EnvSnapshot {
+            bindings: self.bindings.borrow().clone(),
+            uninitialized: self.uninitialized.borrow().clone(),
+        }
+    }
+
+    /// このスコープの束縛を snapshot の時点に戻す（以降に追加した束縛は削除される）
+    pub fn restore(&self, snapshot: EnvSnapshot) {
+        *self.bindings.borrow_mut() = snapshot.bindings;
+        *self.uninitialized.borrow_mut() = snapshot.uninitialized;
+    }
+
+    /// TDZ 状態を解除
+    fn initialize(&self, name: &str) {
+        // 多くのスコープは TDZ の束縛を持たないため、空ならハッシュ計算を省く
+        let mut uninitialized = self.uninitialized.borrow_mut();
+        if !uninitialized.is_empty() {
+            uninitialized.remove(name);
         }
-        if let Some(ref outer) = self.outer {
-            return outer.borrow().get(name);
+    }
+
+    /// このスコープの束縛が初期化済みか確認
+    fn check_initialized(&self, name: &str) -> JSResult<()> {
+        let uninitialized = self.uninitialized.borrow();
+        if !uninitialized.is_empty() && uninitialized.contains(name) {
+            return Err(JSError::ReferenceError(format!(
+                "Cannot access '{}' before initialization",
+                name
+            )));
         }
-        None
+        Ok(())
     }
 }
 
@@ -0,0 +1,186 @@
+use super::{InternalSlot, JSMap, JSObject, JSValue};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+impl JSValue {
+    /// console.log 向けに値の中身を整形する（Node.js の util.inspect 相当）
+    ///
+    /// オブジェクトと配列は中身を展開し、文字列はクォートして表示する。
+    /// 循環参照は `[Circular]` として表示する。
+    pub fn inspect(&self) -> String {
+        let mut out = String::new();
+        inspect_value(self, &mut Vec::new(), &mut out);
+        out
+    }
+}
+
+/// 値を out に書き出す（seen は展開中のオブジェクト）
+fn inspect_value(value: &JSValue, seen: &mut Vec<*const RefCell<JSObject>>, out: &mut String) {
+    match value {
+        JSValue::String(s) => out.push_str(&quote(s)),
+        // -0 は区別して表示する
+        JSValue::Number(n) if *n == 0.0 && n.is_sign_negative() => out.push_str("-0"),
+        JSValue::Function(_) => out.push_str("[Function (anonymous)]"),
+        JSValue::NativeFunction(native) => {
+            out.push_str(&format!("[Function: {}]", native.name));
+        }
+        JSValue::BoundFunction(_) => out.push_str("[Function: bound]"),
+        JSValue::Object(obj) => inspect_object(obj, seen, out),
+        other => out.push_str(&other.to_console_string()),
+    }
+}
+
+/// オブジェクト・配列を `{ a: 1 }` / `[ 1, 2 ]` の形式で書き出す
+fn inspect_object(
+    obj: &Rc<RefCell<JSObject>>,
+    seen: &mut Vec<*const RefCell<JSObject>>,
+    out: &mut String,
+) {
+    let ptr = Rc::as_ptr(obj);
+    if seen.contains(&ptr) {
+        out.push_str("[Circular]");
+        return;
+    }
+    seen.push(ptr);
+
+    let obj = obj.borrow();
+    match obj.internal_slot() {
+        Some(InternalSlot::Map(data)) => {
+            inspect_collection("Map", &data.borrow(), true, seen, out);
+            seen.pop();
+            return;
+        }
+        Some(InternalSlot::Set(data)) => {
+            inspect_collection("Set", &data.borrow(), false, seen, out);
+            seen.pop();
+            return;
+        }
+        // エラーは `[TypeError: message]` の形式で表示する
+        Some(InternalSlot::Error) => {
+            let name = obj.get("name").to_console_string();
+            let message = obj.get("message").to_console_string();
+            if message.is_empty() {
+                out.push_str(&format!("[{}]", name));
+            } else {
+                out.push_str(&format!("[{}: {}]", name, message));
+            }
+            seen.pop();
+            return;
+        }
+        // ラッパーオブジェクトは `[Number: 5]` の形式で表示する
+        Some(InternalSlot::Number(n)) => {
+            out.push_str("[Number: ");
+            inspect_value(&JSValue::Number(*n), seen, out);
+            out.push(']');
+            seen.pop();
+            return;
+        }
+        Some(InternalSlot::String(s)) => {
+            out.push_str(&format!("[String: {}]", quote(s)));
+            seen.pop();
+            return;
+        }
+        Some(InternalSlot::Boolean(b)) => {
+            out.push_str(&format!("[Boolean: {}]", b));
+            seen.pop();
+            return;
+        }
+        _ => {}
+    }
+    let is_array = obj.is_array();
+    let (open, close) = if is_array { ('[', ']') } else { ('{', '}') };
+
+    let keys = obj.keys();
+    if keys.is_empty() {
+        out.push(open);
+        out.push(close);
+    } else {
+        out.push(open);
+        out.push(' ');
+        for (i, key) in keys.iter().enumerate() {
+            if i > 0 {
+                out.push_str(", ");
+            }
+            // 配列の要素はキーを省略する
+            if !(is_array && key.parse::<u32>().is_ok()) {
+                out.push_str(&format_key(key));
+                out.push_str(": ");
+            }
+            // アクセサプロパティは getter を呼ばずに種類だけ示す
+            match obj
+                .get_property_descriptor(key)
+                .and_then(|prop| prop.accessor)
+            {
+                Some(accessor) => out.push_str(match (&accessor.get, &accessor.set) {
+                    (JSValue::Undefined, _) => "[Setter]",
+                    (_, JSValue::Undefined) => "[Getter]",
+                    _ => "[Getter/Setter]",
+                }),
+                None => inspect_value(&obj.get(key), seen, out),
+            }
+        }
+        out.push(' ');
+        out.push(close);
+    }
+
+    seen.pop();
+}
+
+/// Map / Set を `Map(1) { 'a' => 1 }` / `Set(1) { 'a' }` の形式で書き出す
+fn inspect_collection(
+    name: &str,
+    data: &JSMap,
+    with_values: bool,
+    seen: &mut Vec<*const RefCell<JSObject>>,
+    out: &mut String,
+) {
+    out.push_str(&format!("{}({}) ", name, data.len()));
+    if data.is_empty() {
+        out.push_str("{}");
+        return;
+    }
+    out.push_str("{ ");
+    for (i, (key, value)) in data.iter().enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        inspect_value(key, seen, out);
+        if with_values {
+            out.push_str(" => ");
+            inspect_value(value, seen, out);
+        }
+    }
+    out.push_str(" }");
+}
+
+/// プロパティキーを整形（識別子として有効な場合のみクォートしない）
+fn format_key(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+/// 文字列をシングルクォートで囲む
+fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('\'');
+    for c in s.chars() {
+        match c {
+            '\'' => out.push_str("\\'"),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c => out.push(c),
+        }
+    }
+    out.push('\'');
+    out
+}
@@ -1,4 +1,4 @@
-use super::{JSObject, JSValue};
+use super::{InternalSlot, JSObject, JSValue, Property};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -86,18 +86,24 @@ impl JSArray {
             obj.set(i.to_string(), value);
         }
 
-        // lengthプロパティを設定
-        obj.set("length".to_string(), JSValue::Number(len as f64));
+        // lengthプロパティを設定（列挙不可）
+        obj.define_property(
+            "length".to_string(),
+            Property::non_enumerable(JSValue::Number(len as f64)),
+        );
+        obj.set_internal_slot(InternalSlot::Array);
 
         JSValue::Object(Rc::new(RefCell::new(obj)))
     }
 
     /// 配列の参照を取得
+    #[allow(clippy::should_implement_trait)]
     pub fn as_ref(&self) -> &JSObject {
         &self.object
     }
 
     /// 配列の可変参照を取得
+    #[allow(clippy::should_implement_trait)]
     pub fn as_mut(&mut self) -> &mut JSObject {
         &mut self.object
     }
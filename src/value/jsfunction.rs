@@ -0,0 +1,188 @@
+use super::{JSObject, JSValue, Property};
+use crate::compiler::{BytecodeChunk, Opcode};
+use crate::error::JSResult;
+use crate::runtime::Environment;
+use crate::vm::VM;
+use std::cell::{OnceCell, RefCell};
+use std::fmt;
+use std::rc::Rc;
+
+/// JavaScript 関数（クロージャ）の内部表現
+#[derive(Clone)]
+pub struct JSFunction {
+    /// 関数本体のバイトコード
+    pub chunk: Rc<BytecodeChunk>,
+    /// 仮引数名
+    pub params: Vec<String>,
+    /// 生成時にキャプチャした環境（定数プール上のテンプレートでは None）
+    pub env: Option<Rc<RefCell<Environment>>>,
+    /// 名前付き関数式の名前（関数自身のスコープで自身を指す）
+    pub self_name: Option<String>,
+    /// 本体が `arguments` を参照するか（参照しなければ呼び出し時に生成しない）
+    pub uses_arguments: bool,
+    /// 本体が `this` を参照するか（参照しなければ呼び出し時に束縛しない）
+    pub uses_this: bool,
+    /// アロー関数か（`this` と `arguments` を束縛せず外側のものを参照し、new できない）
+    pub is_arrow: bool,
+    /// 関数自身が持つプロパティ（最初に参照したときに作成する）
+    properties: OnceCell<Rc<RefCell<JSObject>>>,
+}
+
+impl JSFunction {
+    /// 環境をキャプチャしていない関数テンプレートを作成
+    pub fn new(chunk: BytecodeChunk, params: Vec<String>) -> Self {
+        // 入れ子の関数は定数プール上の別チャンクなので、自身の命令と、
+        // 外側の this / arguments を参照するアロー関数だけを見ればよい
+        let nested_arrows = || {
+            chunk
+                .constants
+                .iter()
+                .filter_map(|constant| match constant {
+                    JSValue::Function(func) if func.is_arrow => Some(func),
+                    _ => None,
+                })
+        };
+        let uses_arguments = chunk.code.iter().any(|opcode| {
+            matches!(
+                opcode,
+                Opcode::LoadVar(name)
+                    | Opcode::StoreVar(name)
+                    | Opcode::DefineVar(name)
+                    | Opcode::DeclareVar(name)
+                    | Opcode::DeclareLexical(name) if name == "arguments"
+            )
+        }) || nested_arrows().any(|func| func.uses_arguments);
+        let uses_this =
+            chunk.code.contains(&Opcode::LoadThis) || nested_arrows().any(|func| func.uses_this);
+        Self {
+            chunk: Rc::new(chunk),
+            params,
+            env: None,
+            self_name: None,
+            uses_arguments,
+            uses_this,
+            is_arrow: false,
+            properties: OnceCell::new(),
+        }
+    }
+
+    /// 環境をキャプチャしていないアロー関数のテンプレートを作成
+    pub fn arrow(chunk: BytecodeChunk, params: Vec<String>) -> Self {
+        Self {
+            is_arrow: true,
+            ..Self::new(chunk, params)
+        }
+    }
+
+    /// 名前付き関数式として、本体から自身を参照する名前を設定
+    pub fn with_self_name(mut self, name: String) -> Self {
+        self.self_name = Some(name);
+        self
+    }
+
+    /// 指定した環境をキャプチャしたクロージャを作成
+    pub fn with_env(&self, env: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            chunk: self.chunk.clone(),
+            params: self.params.clone(),
+            env: Some(env),
+            self_name: self.self_name.clone(),
+            uses_arguments: self.uses_arguments,
+            uses_this: self.uses_this,
+            is_arrow: self.is_arrow,
+            properties: OnceCell::new(),
+        }
+    }
+
+    /// 関数自身が持つプロパティを取得（初回に作成する）
+    ///
+    /// アロー関数以外は、constructor で自身を指す prototype オブジェクトを持つ。
+    /// new で作成するオブジェクトはこの prototype を継承する。
+    pub fn properties(
+        self: &Rc<Self>,
+        object_prototype: &Rc<RefCell<JSObject>>,
+    ) -> &Rc<RefCell<JSObject>> {
+        self.properties.get_or_init(|| {
+            let mut properties = JSObject::new();
+            if !self.is_arrow {
+                let mut prototype = JSObject::with_prototype(Some(object_prototype.clone()));
+                prototype.define_property(
+                    "constructor".to_string(),
+                    Property::non_enumerable(JSValue::Function(self.clone())),
+                );
+                properties.define_property(
+                    "prototype".to_string(),
+                    Property::non_enumerable(JSValue::Object(Rc::new(RefCell::new(prototype)))),
+                );
+            }
+            Rc::new(RefCell::new(properties))
+        })
+    }
+}
+
+impl fmt::Debug for JSFunction {
+    /// キャプチャした環境は循環しうるため表示しない
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JSFunction")
+            .field("params", &self.params)
+            .field("chunk", &self.chunk)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Function.prototype.bind で作成した関数
+#[derive(Clone)]
+pub struct BoundFunction {
+    /// 呼び出し対象の関数
+    pub target: JSValue,
+    /// 呼び出し時の this（呼び出し側の this は無視する）
+    pub this: JSValue,
+    /// 呼び出し時の引数の前に付ける引数
+    pub args: Vec<JSValue>,
+}
+
+impl fmt::Debug for BoundFunction {
+    /// 束縛した値は循環しうるため対象の関数のみ表示
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoundFunction")
+            .field("target", &self.target)
+            .finish_non_exhaustive()
+    }
+}
+
+/// ネイティブ関数のシグネチャ（VM, this, 引数）
+pub type NativeFn = fn(&mut VM, &JSValue, &[JSValue]) -> JSResult<JSValue>;
+
+/// Rust で実装された組み込み関数
+#[derive(Clone)]
+pub struct NativeFunction {
+    /// 関数名
+    pub name: String,
+    /// 通常の呼び出し時の処理
+    pub call: NativeFn,
+    /// new で呼び出された時の処理（コンストラクタでない場合は None）
+    pub construct: Option<NativeFn>,
+    /// 関数自身が持つプロパティ（Date.now など）
+    pub properties: Rc<RefCell<JSObject>>,
+}
+
+impl NativeFunction {
+    /// 新しいネイティブ関数を作成
+    pub fn new(name: &str, call: NativeFn, construct: Option<NativeFn>) -> Self {
+        Self {
+            name: name.to_string(),
+            call,
+            construct,
+            properties: Rc::new(RefCell::new(JSObject::new())),
+        }
+    }
+}
+
+impl fmt::Debug for NativeFunction {
+    /// プロパティは循環しうるため名前のみ表示
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NativeFunction")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
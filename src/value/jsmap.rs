@@ -0,0 +1,135 @@
+use super::JSValue;
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+
+/// Map / Set のキー（SameValueZero で等しい値が同じキーになる）
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum MapKey {
+    Undefined,
+    Null,
+    Boolean(bool),
+    /// 数値のビット列（-0 は +0、NaN は1つの値に正規化）
+    Number(u64),
+    String(String),
+    /// オブジェクト・関数は参照で比較する
+    Reference(usize),
+}
+
+impl MapKey {
+    fn from_value(value: &JSValue) -> Self {
+        match value {
+            JSValue::Undefined => MapKey::Undefined,
+            JSValue::Null => MapKey::Null,
+            JSValue::Boolean(b) => MapKey::Boolean(*b),
+            JSValue::Number(n) if n.is_nan() => MapKey::Number(f64::NAN.to_bits()),
+            JSValue::Number(n) if *n == 0.0 => MapKey::Number(0),
+            JSValue::Number(n) => MapKey::Number(n.to_bits()),
+            JSValue::String(s) => MapKey::String(s.clone()),
+            JSValue::Object(obj) => MapKey::Reference(Rc::as_ptr(obj) as *const () as usize),
+            JSValue::Function(func) => MapKey::Reference(Rc::as_ptr(func) as *const () as usize),
+            JSValue::NativeFunction(func) => {
+                MapKey::Reference(Rc::as_ptr(func) as *const () as usize)
+            }
+            JSValue::BoundFunction(func) => {
+                MapKey::Reference(Rc::as_ptr(func) as *const () as usize)
+            }
+        }
+    }
+}
+
+/// 挿入順を保持するキーと値の表（Map / Set の内部表現）
+///
+/// 削除したエントリは墓標（None）として残すため、反復中に要素が追加・削除されても
+/// インデックスによる走査を続けられる。
+#[derive(Debug, Clone, Default)]
+pub struct JSMap {
+    /// キーから entries 上の位置への索引
+    index: FxHashMap<MapKey, usize>,
+    /// 挿入順のエントリ（削除済みは None）
+    entries: Vec<Option<(JSValue, JSValue)>>,
+}
+
+impl JSMap {
+    /// 新しい空の表を作成
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 要素数
+    pub fn len(&self) -> usize {
+        self.index.len()
+    }
+
+    /// 空かどうか
+    pub fn is_empty(&self) -> bool {
+        self.index.is_empty()
+    }
+
+    /// キーに対応する値を取得
+    pub fn get(&self, key: &JSValue) -> Option<JSValue> {
+        let i = *self.index.get(&MapKey::from_value(key))?;
+        self.entries[i].as_ref().map(|(_, value)| value.clone())
+    }
+
+    /// キーが存在するか
+    pub fn has(&self, key: &JSValue) -> bool {
+        self.index.contains_key(&MapKey::from_value(key))
+    }
+
+    /// 値を設定（既存のキーは元の位置を保つ）
+    pub fn set(&mut self, key: JSValue, value: JSValue) {
+        let map_key = MapKey::from_value(&key);
+        if let Some(&i) = self.index.get(&map_key) {
+            if let Some(entry) = &mut self.entries[i] {
+                entry.1 = value;
+            }
+            return;
+        }
+        // -0 のキーは +0 として保持する（-0 + 0 は +0）
+        let key = match key {
+            JSValue::Number(n) => JSValue::Number(n + 0.0),
+            key => key,
+        };
+        self.index.insert(map_key, self.entries.len());
+        self.entries.push(Some((key, value)));
+    }
+
+    /// キーを削除（存在した場合 true）
+    pub fn delete(&mut self, key: &JSValue) -> bool {
+        match self.index.remove(&MapKey::from_value(key)) {
+            Some(i) => {
+                self.entries[i] = None;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 全ての要素を削除
+    pub fn clear(&mut self) {
+        self.index.clear();
+        for entry in &mut self.entries {
+            *entry = None;
+        }
+    }
+
+    /// 位置 position 以降で最初の要素と、その次の位置を返す（反復用）
+    pub fn entry_from(&self, position: usize) -> Option<(usize, JSValue, JSValue)> {
+        self.entries[position.min(self.entries.len())..]
+            .iter()
+            .enumerate()
+            .find_map(|(offset, entry)| {
+                entry
+                    .as_ref()
+                    .map(|(key, value)| (position + offset + 1, key.clone(), value.clone()))
+            })
+    }
+
+    /// 挿入順にキーと値を列挙
+    pub fn iter(&self) -> impl Iterator<Item = (&JSValue, &JSValue)> {
+        self.entries
+            .iter()
+            .flatten()
+            .map(|(key, value)| (key, value))
+    }
+}
@@ -1,4 +1,5 @@
-use super::JSValue;
+use super::shape::{Shape, next_shape_id};
+use super::{JSMap, JSValue};
 use rustc_hash::FxHashMap;
 use std::cell::RefCell;
 use std::rc::Rc;
@@ -7,9 +8,184 @@ use std::rc::Rc;
 #[derive(Debug, Clone)]
 pub struct JSObject {
     /// プロパティマップ
-    properties: Rc<RefCell<FxHashMap<String, Property>>>,
+    properties: Rc<RefCell<PropertyMap>>,
     /// プロトタイプチェーン（__proto__）
     prototype: Option<Rc<RefCell<JSObject>>>,
+    /// 組み込みオブジェクトの内部スロット
+    internal: Option<InternalSlot>,
+}
+
+/// 共有シェイプで表すキー数の上限（超えると辞書モードに切り替える）
+const MAX_SHAPE_KEYS: usize = 32;
+
+/// 挿入順を保持するプロパティの表
+///
+/// キーの配置はシェイプで表し、値はその位置順に並べて持つ。キーの多い
+/// オブジェクト（要素の多い配列等）は遷移を作り続けないよう辞書モードにする。
+#[derive(Debug, Clone)]
+struct PropertyMap {
+    /// キーの配置
+    layout: Layout,
+    /// 位置順に並んだプロパティ
+    slots: Vec<Property>,
+}
+
+/// プロパティの表のキーの配置
+#[derive(Debug, Clone)]
+enum Layout {
+    /// 同じ順序で同じキーを持つオブジェクトと共有するシェイプ
+    Shared(Rc<Shape>),
+    /// オブジェクト固有の索引（キーの追加・削除で識別子を更新する）
+    Dictionary {
+        id: u64,
+        index: FxHashMap<String, usize>,
+        keys: Vec<String>,
+    },
+}
+
+impl Default for PropertyMap {
+    fn default() -> Self {
+        Self {
+            layout: Layout::Shared(Shape::root()),
+            slots: Vec::new(),
+        }
+    }
+}
+
+impl PropertyMap {
+    /// キーの配置の識別子
+    fn shape_id(&self) -> u64 {
+        match &self.layout {
+            Layout::Shared(shape) => shape.id(),
+            Layout::Dictionary { id, .. } => *id,
+        }
+    }
+
+    /// キーの位置を取得
+    fn slot(&self, key: &str) -> Option<usize> {
+        match &self.layout {
+            Layout::Shared(shape) => shape.slot(key),
+            Layout::Dictionary { index, .. } => index.get(key).copied(),
+        }
+    }
+
+    /// 位置順のキー
+    fn keys(&self) -> &[String] {
+        match &self.layout {
+            Layout::Shared(shape) => shape.keys(),
+            Layout::Dictionary { keys, .. } => keys,
+        }
+    }
+
+    fn get(&self, key: &str) -> Option<&Property> {
+        self.slot(key).map(|i| &self.slots[i])
+    }
+
+    fn get_mut(&mut self, key: &str) -> Option<&mut Property> {
+        self.slot(key).map(|i| &mut self.slots[i])
+    }
+
+    fn contains_key(&self, key: &str) -> bool {
+        self.slot(key).is_some()
+    }
+
+    /// 追加または上書き（既存のキーは元の位置を保つ）
+    fn insert(&mut self, key: String, property: Property) {
+        if let Some(i) = self.slot(&key) {
+            self.slots[i] = property;
+            return;
+        }
+        match &mut self.layout {
+            Layout::Shared(shape) if shape.len() < MAX_SHAPE_KEYS => {
+                *shape = shape.with_key(&key);
+            }
+            Layout::Shared(shape) => {
+                let mut keys = shape.keys().to_vec();
+                let mut index: FxHashMap<String, usize> = keys.iter().cloned().zip(0..).collect();
+                index.insert(key.clone(), keys.len());
+                keys.push(key);
+                self.layout = Layout::Dictionary {
+                    id: next_shape_id(),
+                    index,
+                    keys,
+                };
+            }
+            Layout::Dictionary { id, index, keys } => {
+                *id = next_shape_id();
+                index.insert(key.clone(), keys.len());
+                keys.push(key);
+            }
+        }
+        self.slots.push(property);
+    }
+
+    fn remove(&mut self, key: &str) -> Option<Property> {
+        let i = self.slot(key)?;
+        let property = self.slots.remove(i);
+        match &mut self.layout {
+            Layout::Shared(shape) => *shape = shape.without_slot(i),
+            Layout::Dictionary { id, index, keys } => {
+                *id = next_shape_id();
+                index.remove(key);
+                keys.remove(i);
+                // 後ろにずれたキーの索引を詰める
+                for key in &keys[i..] {
+                    *index.get_mut(key).unwrap() -= 1;
+                }
+            }
+        }
+        Some(property)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = (&String, &Property)> + Clone {
+        self.keys().iter().zip(&self.slots)
+    }
+}
+
+/// 組み込みオブジェクトが保持する内部値
+#[derive(Debug, Clone)]
+pub enum InternalSlot {
+    /// 配列リテラル・Array から作られたオブジェクトの印
+    Array,
+    /// Error コンストラクタから作られたオブジェクトの印
+    Error,
+    /// Date オブジェクトの時刻値（Unix エポックからのミリ秒）
+    Date(f64),
+    /// Map オブジェクトの要素
+    Map(Rc<RefCell<JSMap>>),
+    /// Set オブジェクトの要素（キーと値に同じ値を持つ）
+    Set(Rc<RefCell<JSMap>>),
+    /// new Number() が保持するプリミティブ値
+    Number(f64),
+    /// new String() が保持するプリミティブ値
+    String(String),
+    /// new Boolean() が保持するプリミティブ値
+    Boolean(bool),
+    /// 配列イテレータの状態（走査対象・返す値の種類・次のインデックス）
+    ArrayIterator {
+        target: JSValue,
+        kind: IterationKind,
+        index: usize,
+    },
+    /// Map / Set イテレータの状態（走査する表・返す値の種類・次の位置）
+    ///
+    /// 走査し終えると data は None になる。
+    MapIterator {
+        data: Option<Rc<RefCell<JSMap>>>,
+        kind: IterationKind,
+        position: usize,
+    },
+}
+
+/// イテレータが返す値の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IterationKind {
+    /// インデックスまたはキー（keys）
+    Keys,
+    /// 要素（values）
+    Values,
+    /// [インデックスまたはキー, 要素] の配列（entries）
+    Entries,
 }
 
 /// プロパティディスクリプタ
@@ -23,6 +199,15 @@ pub struct Property {
     pub writable: bool,
     /// 設定変更可能かどうか
     pub configurable: bool,
+    /// アクセサプロパティの getter / setter（データプロパティは None、value は使わない）
+    pub accessor: Option<Accessor>,
+}
+
+/// アクセサプロパティの getter / setter（省略したものは undefined）
+#[derive(Debug, Clone)]
+pub struct Accessor {
+    pub get: JSValue,
+    pub set: JSValue,
 }
 
 impl Property {
@@ -33,6 +218,7 @@ impl Property {
             enumerable: true,
             writable: true,
             configurable: true,
+            accessor: None,
         }
     }
 
@@ -43,6 +229,29 @@ impl Property {
             enumerable: true,
             writable: false,
             configurable: false,
+            accessor: None,
+        }
+    }
+
+    /// 列挙不可のプロパティを作成（組み込みメソッド用）
+    pub fn non_enumerable(value: JSValue) -> Self {
+        Self {
+            value,
+            enumerable: false,
+            writable: true,
+            configurable: true,
+            accessor: None,
+        }
+    }
+
+    /// アクセサプロパティを作成
+    pub fn accessor(accessor: Accessor, enumerable: bool, configurable: bool) -> Self {
+        Self {
+            value: JSValue::Undefined,
+            enumerable,
+            writable: false,
+            configurable,
+            accessor: Some(accessor),
         }
     }
 }
@@ -51,20 +260,24 @@ impl JSObject {
     /// 新しい空のJSオブジェクトを作成
     pub fn new() -> Self {
         Self {
-            properties: Rc::new(RefCell::new(FxHashMap::default())),
+            properties: Rc::new(RefCell::new(PropertyMap::default())),
             prototype: None,
+            internal: None,
         }
     }
 
     /// プロトタイプを指定してオブジェクトを作成
     pub fn with_prototype(prototype: Option<Rc<RefCell<JSObject>>>) -> Self {
         Self {
-            properties: Rc::new(RefCell::new(FxHashMap::default())),
+            properties: Rc::new(RefCell::new(PropertyMap::default())),
             prototype,
+            internal: None,
         }
     }
 
     /// プロパティを取得
+    ///
+    /// アクセサプロパティは getter を呼び出せないため undefined になる（getter は VM が呼び出す）。
     pub fn get(&self, key: &str) -> JSValue {
         // 自身のプロパティを検索
         if let Some(prop) = self.properties.borrow().get(key) {
@@ -79,6 +292,38 @@ impl JSObject {
         JSValue::Undefined
     }
 
+    /// 自身のプロパティの配置を表すシェイプの識別子
+    ///
+    /// キーの追加・削除で変わり、値の書き換えでは変わらない。同じ順序で同じキーを
+    /// 追加したオブジェクトは同じ値になり、同じ値の間は own_slot で得た位置が
+    /// 同じキーを指す。
+    pub fn shape(&self) -> u64 {
+        self.properties.borrow().shape_id()
+    }
+
+    /// 自身のプロパティの位置を取得（インラインキャッシュ用）
+    pub fn own_slot(&self, key: &str) -> Option<usize> {
+        self.properties.borrow().slot(key)
+    }
+
+    /// own_slot で得た位置のデータプロパティの値を取得（アクセサプロパティは None）
+    pub fn slot_value(&self, slot: usize) -> Option<JSValue> {
+        self.properties
+            .borrow()
+            .slots
+            .get(slot)
+            .filter(|prop| prop.accessor.is_none())
+            .map(|prop| prop.value.clone())
+    }
+
+    /// プロパティディスクリプタをプロトタイプチェーンを辿って取得
+    pub fn find_property(&self, key: &str) -> Option<Property> {
+        if let Some(prop) = self.properties.borrow().get(key) {
+            return Some(prop.clone());
+        }
+        self.prototype.as_ref()?.borrow().find_property(key)
+    }
+
     /// プロパティを設定
     pub fn set(&mut self, key: String, value: JSValue) -> bool {
         // 既存のプロパティを確認
@@ -90,6 +335,13 @@ impl JSObject {
             return true;
         }
 
+        // 配列では length 以上のインデックスへの書き込みで length を伸ばす
+        if self.is_array()
+            && let Some(index) = array_index(&key)
+        {
+            self.grow_length(index);
+        }
+
         // 新しいプロパティを追加
         self.properties
             .borrow_mut()
@@ -97,6 +349,17 @@ impl JSObject {
         true
     }
 
+    /// 配列の length を index + 1 以上に更新
+    fn grow_length(&mut self, index: u32) {
+        let mut properties = self.properties.borrow_mut();
+        if let Some(length) = properties.get_mut("length")
+            && let JSValue::Number(n) = length.value
+            && (index as f64) >= n
+        {
+            length.value = JSValue::Number(index as f64 + 1.0);
+        }
+    }
+
     /// プロパティが存在するか確認（自身のプロパティのみ）
     pub fn has_own_property(&self, key: &str) -> bool {
         self.properties.borrow().contains_key(key)
@@ -136,13 +399,43 @@ impl JSObject {
         self.prototype = prototype;
     }
 
+    /// 内部スロットを取得
+    pub fn internal_slot(&self) -> Option<&InternalSlot> {
+        self.internal.as_ref()
+    }
+
+    /// 内部スロットを設定
+    pub fn set_internal_slot(&mut self, slot: InternalSlot) {
+        self.internal = Some(slot);
+    }
+
+    /// 配列由来のオブジェクトかどうか
+    pub fn is_array(&self) -> bool {
+        matches!(self.internal, Some(InternalSlot::Array))
+    }
+
     /// 全てのプロパティキーを取得（列挙可能なもののみ）
+    ///
+    /// 順序は OrdinaryOwnPropertyKeys に従い、配列インデックスを昇順に並べたあと
+    /// 残りのキーを追加順に並べる。
     pub fn keys(&self) -> Vec<String> {
-        self.properties
-            .borrow()
-            .iter()
-            .filter(|(_, prop)| prop.enumerable)
-            .map(|(key, _)| key.clone())
+        let properties = self.properties.borrow();
+        let enumerable = properties.iter().filter(|(_, prop)| prop.enumerable);
+
+        let mut indices: Vec<(u32, &String)> = enumerable
+            .clone()
+            .filter_map(|(key, _)| array_index(key).map(|i| (i, key)))
+            .collect();
+        indices.sort_unstable_by_key(|(i, _)| *i);
+
+        indices
+            .into_iter()
+            .map(|(_, key)| key.clone())
+            .chain(
+                enumerable
+                    .filter(|(key, _)| array_index(key).is_none())
+                    .map(|(key, _)| key.clone()),
+            )
             .collect()
     }
 
@@ -157,6 +450,12 @@ impl JSObject {
     }
 }
 
+/// 配列インデックス（正規形の 0 〜 2^32 - 2 の整数文字列）なら数値を返す
+pub fn array_index(key: &str) -> Option<u32> {
+    let index: u32 = key.parse().ok()?;
+    (index != u32::MAX && index.to_string() == key).then_some(index)
+}
+
 impl Default for JSObject {
     fn default() -> Self {
         Self::new()
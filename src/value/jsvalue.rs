@@ -1,5 +1,5 @@
+use super::jsfunction::{BoundFunction, JSFunction, NativeFunction};
 use super::jsobject::JSObject;
-use crate::compiler::BytecodeChunk;
 use std::cell::RefCell;
 use std::fmt;
 use std::rc::Rc;
@@ -13,7 +13,9 @@ pub enum JSValue {
     Number(f64),
     String(String),
     Object(Rc<RefCell<JSObject>>),
-    Function(BytecodeChunk, Vec<String>),
+    Function(Rc<JSFunction>),
+    NativeFunction(Rc<NativeFunction>),
+    BoundFunction(Rc<BoundFunction>),
     // TODO: Symbol, BigInt 等は後のフェーズで実装
 }
 
@@ -24,22 +26,32 @@ impl JSValue {
             JSValue::Undefined => "undefined".to_string(),
             JSValue::Null => "null".to_string(),
             JSValue::Boolean(b) => b.to_string(),
-            JSValue::Number(n) => {
-                if n.is_nan() {
-                    "NaN".to_string()
-                } else if n.is_infinite() {
-                    if n.is_sign_positive() {
-                        "Infinity".to_string()
-                    } else {
-                        "-Infinity".to_string()
-                    }
-                } else {
-                    n.to_string()
-                }
-            }
+            JSValue::Number(n) => number_to_string(*n),
             JSValue::String(s) => s.clone(),
             JSValue::Object(_) => "[object Object]".to_string(),
-            JSValue::Function(_, _) => "[function]".to_string(),
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => {
+                "[function]".to_string()
+            }
+        }
+    }
+
+    /// プリミティブ値をプロパティキーに変換
+    ///
+    /// オブジェクトの ToPrimitive は行わないため、オブジェクトのキーは VM::to_property_key で
+    /// 変換する。数値キーは Number::toString で正規化されるため、`obj[1]`・`obj[1.0]`・`obj["1"]`
+    /// は同じプロパティを指す。
+    pub fn to_property_key(&self) -> String {
+        match self {
+            JSValue::String(s) => s.clone(),
+            other => other.to_console_string(),
+        }
+    }
+
+    /// to_property_key と同じだが、文字列はコピーせずにそのまま使う
+    pub fn into_property_key(self) -> String {
+        match self {
+            JSValue::String(s) => s,
+            other => other.to_console_string(),
         }
     }
 
@@ -58,9 +70,35 @@ impl JSValue {
                 }
                 trimmed.parse().unwrap_or(f64::NAN)
             }
-            JSValue::Object(_) => f64::NAN,      // オブジェクトはNaN
-            JSValue::Function(_, _) => f64::NAN, // 関数もNaN
+            JSValue::Object(_) => f64::NAN, // オブジェクトはNaN
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => {
+                f64::NAN
+            } // 関数もNaN
+        }
+    }
+
+    /// 値を整数に変換（ToIntegerOrInfinity 抽象操作）
+    ///
+    /// 小数部を切り捨て、NaN と -0 は +0 とする。±Infinity はそのまま返す。
+    pub fn to_integer_or_infinity(&self) -> f64 {
+        let n = self.to_number();
+        if n.is_nan() { 0.0 } else { n.trunc() + 0.0 }
+    }
+
+    /// 値を 32 ビット符号付き整数に変換（ToInt32 抽象操作）
+    ///
+    /// 小数部を切り捨てて 2^32 を法として折り返す。NaN と ±Infinity は 0。
+    pub fn to_int32(&self) -> i32 {
+        self.to_uint32() as i32
+    }
+
+    /// 値を 32 ビット符号なし整数に変換（ToUint32 抽象操作）
+    pub fn to_uint32(&self) -> u32 {
+        let n = self.to_number();
+        if !n.is_finite() {
+            return 0;
         }
+        n.trunc().rem_euclid(4_294_967_296.0) as u32
     }
 
     /// 値を真偽値に変換（ToBoolean 抽象操作）
@@ -70,11 +108,58 @@ impl JSValue {
             JSValue::Boolean(b) => *b,
             JSValue::Number(n) => !n.is_nan() && *n != 0.0,
             JSValue::String(s) => !s.is_empty(),
-            JSValue::Object(_) => true,      // オブジェクトは常にtrue
-            JSValue::Function(_, _) => true, // 関数も常にtrue
+            JSValue::Object(_) => true, // オブジェクトは常にtrue
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => true, // 関数も常にtrue
+        }
+    }
+
+    /// Number であればその値を返す（型変換はしない）
+    pub fn as_number(&self) -> Option<f64> {
+        match self {
+            JSValue::Number(n) => Some(*n),
+            _ => None,
         }
     }
 
+    /// String であればその値を返す（型変換はしない）
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JSValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Boolean であればその値を返す（型変換はしない）
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JSValue::Boolean(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Object（配列を含む）であればその参照を返す
+    pub fn as_object(&self) -> Option<Rc<RefCell<JSObject>>> {
+        match self {
+            JSValue::Object(obj) => Some(obj.clone()),
+            _ => None,
+        }
+    }
+
+    /// 配列であれば length を要素数として返す（配列以外は None）
+    pub fn array_length(&self) -> Option<usize> {
+        let JSValue::Object(obj) = self else {
+            return None;
+        };
+        let obj = obj.borrow();
+        if !obj.is_array() {
+            return None;
+        }
+        obj.get_property_descriptor("length")?
+            .value
+            .as_number()
+            .map(|n| n as usize)
+    }
+
     /// 型名を取得
     pub fn type_of(&self) -> &'static str {
         match self {
@@ -84,10 +169,22 @@ impl JSValue {
             JSValue::Number(_) => "number",
             JSValue::String(_) => "string",
             JSValue::Object(_) => "object",
-            JSValue::Function(_, _) => "function",
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => {
+                "function"
+            }
         }
     }
 
+    /// 関数として呼び出せるか（IsCallable 抽象操作）
+    ///
+    /// typeof・関数呼び出し・instanceof はこの判定を共通して使う。
+    pub fn is_callable(&self) -> bool {
+        matches!(
+            self,
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_)
+        )
+    }
+
     /// 厳密等価比較（===）
     pub fn strict_equals(&self, other: &JSValue) -> bool {
         match (self, other) {
@@ -106,11 +203,83 @@ impl JSValue {
                 // オブジェクトは参照が同じ場合のみtrue
                 Rc::ptr_eq(a, b)
             }
+            (JSValue::Function(a), JSValue::Function(b)) => Rc::ptr_eq(a, b),
+            (JSValue::NativeFunction(a), JSValue::NativeFunction(b)) => Rc::ptr_eq(a, b),
+            (JSValue::BoundFunction(a), JSValue::BoundFunction(b)) => Rc::ptr_eq(a, b),
             _ => false,
         }
     }
 
+    /// SameValue 比較（Object.is）
+    ///
+    /// strict_equals と異なり、NaN 同士は等しく、+0 と -0 は区別する。
+    pub fn same_value(&self, other: &JSValue) -> bool {
+        match (self, other) {
+            (JSValue::Number(a), JSValue::Number(b)) => {
+                (a.is_nan() && b.is_nan())
+                    || (a == b && a.is_sign_negative() == b.is_sign_negative())
+            }
+            _ => self.strict_equals(other),
+        }
+    }
+
+    /// SameValueZero 比較（Map / Set のキー）
+    ///
+    /// NaN 同士は等しく、+0 と -0 も等しいとみなす。
+    pub fn same_value_zero(&self, other: &JSValue) -> bool {
+        match (self, other) {
+            (JSValue::Number(a), JSValue::Number(b)) if a.is_nan() && b.is_nan() => true,
+            _ => self.strict_equals(other),
+        }
+    }
+
+    /// 構造的な等価比較（テスト等で値の中身を比較する用途）
+    ///
+    /// オブジェクト・配列は参照ではなく、列挙可能なキーとその値を再帰的に比較する。
+    /// プリミティブは `strict_equals` と同じだが、NaN 同士は等しいとみなす。
+    pub fn deep_equals(&self, other: &JSValue) -> bool {
+        self.deep_equals_inner(other, &mut Vec::new())
+    }
+
+    /// deep_equals の本体（visited は比較中のオブジェクトの組で、循環参照を検出する）
+    fn deep_equals_inner(
+        &self,
+        other: &JSValue,
+        visited: &mut Vec<(*const RefCell<JSObject>, *const RefCell<JSObject>)>,
+    ) -> bool {
+        match (self, other) {
+            (JSValue::Number(a), JSValue::Number(b)) if a.is_nan() && b.is_nan() => true,
+            (JSValue::Object(a), JSValue::Object(b)) => {
+                if Rc::ptr_eq(a, b) {
+                    return true;
+                }
+                let pair = (Rc::as_ptr(a), Rc::as_ptr(b));
+                if visited.contains(&pair) {
+                    // 比較中の組に戻ってきた場合は、残りの比較結果に委ねる
+                    return true;
+                }
+                visited.push(pair);
+
+                let (a, b) = (a.borrow(), b.borrow());
+                let keys = a.keys();
+                let equal = keys.len() == b.keys().len()
+                    && keys.iter().all(|key| {
+                        b.get_property_descriptor(key)
+                            .is_some_and(|prop| prop.enumerable)
+                            && a.get(key).deep_equals_inner(&b.get(key), visited)
+                    });
+
+                visited.pop();
+                equal
+            }
+            _ => self.strict_equals(other),
+        }
+    }
+
     /// 抽象等価比較（==）
+    ///
+    /// オブジェクトとプリミティブの比較には ToPrimitive（valueOf / toString の呼び出し）が
+    /// 必要なため、VM で変換してから呼ぶ。ここではオブジェクトは参照でのみ比較する。
     pub fn abstract_equals(&self, other: &JSValue) -> bool {
         // 同じ型の場合は厳密等価
         if std::mem::discriminant(self) == std::mem::discriminant(other) {
@@ -121,19 +290,68 @@ impl JSValue {
             // null == undefined
             (JSValue::Null, JSValue::Undefined) | (JSValue::Undefined, JSValue::Null) => true,
 
-            // 数値と文字列の比較
-            (JSValue::Number(n), JSValue::String(_)) => *n == other.to_number(),
-            (JSValue::String(_), JSValue::Number(n)) => self.to_number() == *n,
-
-            // 真偽値は数値に変換して比較
-            (JSValue::Boolean(_), _) => JSValue::Number(self.to_number()).abstract_equals(other),
-            (_, JSValue::Boolean(_)) => self.abstract_equals(&JSValue::Number(other.to_number())),
+            // 数値・文字列・真偽値の異なる型同士は数値に変換して比較（NaN はどれとも等しくない）
+            (
+                JSValue::Number(_) | JSValue::String(_) | JSValue::Boolean(_),
+                JSValue::Number(_) | JSValue::String(_) | JSValue::Boolean(_),
+            ) => self.to_number() == other.to_number(),
 
             _ => false,
         }
     }
 }
 
+/// 数値を文字列に変換（Number::toString 抽象操作）
+pub fn number_to_string(n: f64) -> String {
+    if n.is_nan() {
+        return "NaN".to_string();
+    }
+    if n == 0.0 {
+        // -0 も "0"
+        return "0".to_string();
+    }
+    if n.is_infinite() {
+        return if n > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if n < 0.0 {
+        return format!("-{}", number_to_string(-n));
+    }
+
+    // 最短の往復可能な表現から仮数部の桁 (digits) と指数 (n) を取り出す
+    // n は「小数点の位置」: 値 = 0.d1d2...dk × 10^n
+    let sci = format!("{:e}", n);
+    let (mantissa, exponent) = sci.split_once('e').unwrap();
+    let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+    let k = digits.len() as i32;
+    let point = exponent.parse::<i32>().unwrap() + 1;
+
+    if k <= point && point <= 21 {
+        // 整数: 桁の後ろに 0 を補う
+        format!("{}{}", digits, "0".repeat((point - k) as usize))
+    } else if 0 < point && point <= 21 {
+        // 小数点が桁の途中にある
+        let (int_part, frac_part) = digits.split_at(point as usize);
+        format!("{}.{}", int_part, frac_part)
+    } else if -6 < point && point <= 0 {
+        // 0.000ddd 形式
+        format!("0.{}{}", "0".repeat((-point) as usize), digits)
+    } else {
+        // 指数表記
+        let exp = point - 1;
+        let sign = if exp >= 0 { '+' } else { '-' };
+        if k == 1 {
+            format!("{}e{}{}", digits, sign, exp.abs())
+        } else {
+            format!("{}.{}e{}{}", &digits[..1], &digits[1..], sign, exp.abs())
+        }
+    }
+}
+
+/// `==` は strict_equals（`===`）と同じ
+///
+/// NaN は自身とも等しくなく、+0 と -0 は等しい。反射律を満たさないため Eq と Hash は
+/// 実装しない。値の同一性で重複排除・検索する場合は same_value / same_value_zero
+/// （定数プールではビット列の比較）を使う。
 impl PartialEq for JSValue {
     fn eq(&self, other: &Self) -> bool {
         self.strict_equals(other)
@@ -1,7 +1,15 @@
+mod inspect;
 pub mod jsarray;
+pub mod jsfunction;
+pub mod jsmap;
 pub mod jsobject;
 pub mod jsvalue;
+pub mod shape;
+mod structured_clone;
 
 pub use jsarray::JSArray;
-pub use jsobject::{JSObject, Property};
+pub use jsfunction::{BoundFunction, JSFunction, NativeFn, NativeFunction};
+pub use jsmap::JSMap;
+pub use jsobject::{Accessor, InternalSlot, IterationKind, JSObject, Property, array_index};
 pub use jsvalue::JSValue;
+pub use shape::Shape;
@@ -0,0 +1,118 @@
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// オブジェクトのキーの配置（隠しクラス）
+///
+/// 同じ順序で同じキーを追加したオブジェクトは同じシェイプを共有し、値は
+/// シェイプが決める位置に並べて持つ。シェイプは不変で、キーの追加は遷移先の
+/// シェイプへの切り替えとして表す。
+pub struct Shape {
+    /// シェイプの識別子（一度使った値は再利用しない）
+    id: u64,
+    /// キーから値の位置への索引
+    index: FxHashMap<String, usize>,
+    /// 位置順のキー
+    keys: Vec<String>,
+    /// 最後のキーを追加する前のシェイプ（遷移の経路を保つ）
+    parent: Option<Rc<Shape>>,
+    /// キーを追加したときの遷移先
+    transitions: RefCell<FxHashMap<String, Weak<Shape>>>,
+}
+
+thread_local! {
+    /// 空のオブジェクトのシェイプ（全ての遷移の起点）
+    static ROOT: Rc<Shape> = Rc::new(Shape {
+        id: next_shape_id(),
+        index: FxHashMap::default(),
+        keys: Vec::new(),
+        parent: None,
+        transitions: RefCell::default(),
+    });
+}
+
+/// 新しいシェイプ識別子を払い出す
+pub(super) fn next_shape_id() -> u64 {
+    static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+    NEXT_ID.fetch_add(1, Ordering::Relaxed)
+}
+
+impl Shape {
+    /// 空のオブジェクトのシェイプを取得
+    pub fn root() -> Rc<Shape> {
+        ROOT.with(Rc::clone)
+    }
+
+    /// シェイプの識別子
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    /// キーの位置を取得
+    pub fn slot(&self, key: &str) -> Option<usize> {
+        self.index.get(key).copied()
+    }
+
+    /// 位置順のキー
+    pub fn keys(&self) -> &[String] {
+        &self.keys
+    }
+
+    /// キーの数
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    /// キーを持たないか
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    /// キーを末尾に追加したシェイプに遷移（同じ遷移は同じシェイプを返す）
+    pub fn with_key(self: &Rc<Self>, key: &str) -> Rc<Shape> {
+        if let Some(next) = self.transitions.borrow().get(key).and_then(Weak::upgrade) {
+            return next;
+        }
+        let mut index = self.index.clone();
+        index.insert(key.to_string(), self.keys.len());
+        let mut keys = self.keys.clone();
+        keys.push(key.to_string());
+        let next = Rc::new(Shape {
+            id: next_shape_id(),
+            index,
+            keys,
+            parent: Some(self.clone()),
+            transitions: RefCell::default(),
+        });
+        self.transitions
+            .borrow_mut()
+            .insert(key.to_string(), Rc::downgrade(&next));
+        next
+    }
+
+    /// 指定位置のキーを取り除いたシェイプに遷移
+    ///
+    /// そのキーを追加する前のシェイプまで戻り、後ろのキーを同じ順で追加し直すため、
+    /// 最初から残りのキーだけを追加したオブジェクトと同じシェイプになる。
+    pub fn without_slot(self: &Rc<Self>, slot: usize) -> Rc<Shape> {
+        let mut base = self.clone();
+        while base.len() > slot {
+            base = base.parent.clone().unwrap_or_else(Shape::root);
+        }
+        self.keys[slot + 1..]
+            .iter()
+            .fold(base, |shape, key| shape.with_key(key))
+    }
+}
+
+impl fmt::Debug for Shape {
+    /// 遷移先は表示しない
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Shape")
+            .field("id", &self.id)
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
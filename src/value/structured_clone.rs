@@ -0,0 +1,85 @@
+use super::{InternalSlot, JSMap, JSObject, JSValue, Property};
+use crate::error::{JSError, JSResult};
+use rustc_hash::FxHashMap;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// 複製元オブジェクトから複製先オブジェクトへの対応表
+type CloneMemo = FxHashMap<*const RefCell<JSObject>, Rc<RefCell<JSObject>>>;
+
+impl JSValue {
+    /// 値を構造化複製する（HTML の structured clone 相当）
+    ///
+    /// 配列・オブジェクトは新しいオブジェクトとして再帰的に複製する。
+    /// グラフ内で共有されている参照や循環参照はそのままの形で複製される。
+    /// 関数は複製できないため TypeError を返す。
+    pub fn structured_clone(&self) -> JSResult<JSValue> {
+        clone_value(self, &mut CloneMemo::default())
+    }
+}
+
+fn clone_value(value: &JSValue, memo: &mut CloneMemo) -> JSResult<JSValue> {
+    match value {
+        JSValue::Object(obj) => Ok(JSValue::Object(clone_object(obj, memo)?)),
+        JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => Err(
+            JSError::TypeError("function could not be cloned".to_string()),
+        ),
+        primitive => Ok(primitive.clone()),
+    }
+}
+
+fn clone_object(
+    source: &Rc<RefCell<JSObject>>,
+    memo: &mut CloneMemo,
+) -> JSResult<Rc<RefCell<JSObject>>> {
+    let ptr = Rc::as_ptr(source);
+    if let Some(cloned) = memo.get(&ptr) {
+        return Ok(cloned.clone());
+    }
+
+    // 子の複製より先に登録しておくことで、循環参照が自身を指せるようにする
+    let source = source.borrow();
+    let target = Rc::new(RefCell::new(JSObject::with_prototype(
+        source.get_prototype(),
+    )));
+    memo.insert(ptr, target.clone());
+    if let Some(slot) = source.internal_slot() {
+        // Map / Set の要素は共有せず、中身を複製した新しい表を持たせる
+        let slot = match slot {
+            InternalSlot::Map(data) => InternalSlot::Map(clone_entries(&data.borrow(), memo)?),
+            InternalSlot::Set(data) => InternalSlot::Set(clone_entries(&data.borrow(), memo)?),
+            slot => slot.clone(),
+        };
+        target.borrow_mut().set_internal_slot(slot);
+    }
+    // 配列の length と Map / Set の size は列挙不可のため個別に複製する
+    let hidden = match source.internal_slot() {
+        Some(InternalSlot::Array) => Some("length"),
+        Some(InternalSlot::Map(_) | InternalSlot::Set(_)) => Some("size"),
+        _ => None,
+    };
+    if let Some(key) = hidden
+        && let Some(property) = source.get_property_descriptor(key)
+    {
+        target
+            .borrow_mut()
+            .define_property(key.to_string(), property);
+    }
+
+    for key in source.keys() {
+        let value = clone_value(&source.get(&key), memo)?;
+        target
+            .borrow_mut()
+            .define_property(key, Property::data(value));
+    }
+
+    Ok(target)
+}
+
+fn clone_entries(source: &JSMap, memo: &mut CloneMemo) -> JSResult<Rc<RefCell<JSMap>>> {
+    let mut target = JSMap::new();
+    for (key, value) in source.iter() {
+        target.set(clone_value(key, memo)?, clone_value(value, memo)?);
+    }
+    Ok(Rc::new(RefCell::new(target)))
+}
@@ -1,289 +1,1307 @@
-use crate::compiler::{BytecodeChunk, Opcode};
+use crate::builtins::{Builtins, ErrorKind, XorShift64, array_iterator_step, map_iterator_step};
+use crate::compiler::{BytecodeChunk, Opcode, PropertyCache};
 use crate::error::{JSError, JSResult};
-use crate::value::JSValue;
-use rustc_hash::FxHashMap;
+use crate::runtime::Environment;
+use crate::value::{
+    Accessor, InternalSlot, IterationKind, JSArray, JSFunction, JSObject, JSValue, Property,
+    array_index,
+};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::rc::Rc;
+#[cfg(feature = "clock")]
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// 現在時刻（Unix エポックからのミリ秒）を返す時計
+pub type Clock = Box<dyn Fn() -> f64>;
+
+/// console.log の出力先（整形済みの1行を受け取る）
+pub type Printer = Box<dyn FnMut(&str)>;
+
+/// 命令の実行前に呼ばれるトレースフック（pc, 命令, スタックの深さ）
+pub type TraceHook = Box<dyn FnMut(usize, &Opcode, usize)>;
+
+/// 関数呼び出しの深さのデフォルトの上限
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 10_000;
+
+/// 算術演算の整数の高速経路で扱う値（i32 に収まる整数値）
+///
+/// スタック上の値は常に f64 の Number で、i32 で計算した方が速い演算（% と **）の間だけ
+/// この表現に変換する。
+#[derive(Clone, Copy)]
+struct SmallInt(i32);
+
+impl SmallInt {
+    /// 整数値なら変換（-0 は i32 で表せないため除く）
+    fn from_f64(n: f64) -> Option<Self> {
+        let i = n as i32;
+        (f64::from(i) == n && !(i == 0 && n.is_sign_negative())).then_some(SmallInt(i))
+    }
+
+    /// 両辺が i32 に収まる整数なら int_op で計算し、桁あふれなどで None が返ったときや
+    /// 整数でない値を含むときは float_op で計算する
+    fn apply(
+        a: f64,
+        b: f64,
+        int_op: impl FnOnce(i32, i32) -> Option<i32>,
+        float_op: impl FnOnce(f64, f64) -> f64,
+    ) -> f64 {
+        match (Self::from_f64(a), Self::from_f64(b)) {
+            (Some(x), Some(y)) => int_op(x.0, y.0).map_or_else(|| float_op(a, b), f64::from),
+            _ => float_op(a, b),
+        }
+    }
+}
+
+/// 例外ハンドラ（try 節）の情報
+struct TryHandler {
+    /// catch 節の開始位置
+    catch_pc: usize,
+    /// try 節開始時のスタックの深さ
+    stack_depth: usize,
+    /// try 節開始時の環境（catch 節へ移るときに戻す）
+    env: Rc<RefCell<Environment>>,
+}
+
+/// 呼び出しフレーム（実行中のチャンクごとの状態）
+struct Frame<'a> {
+    /// 実行中のバイトコード
+    code: Code<'a>,
+    /// 次に実行する命令の位置
+    pc: usize,
+    /// フレーム開始時のオペランドスタックの深さ
+    base: usize,
+    /// 登録中の例外ハンドラ（末尾が最も内側）
+    handlers: Vec<TryHandler>,
+    /// 呼び出し元の環境（関数呼び出しのフレームを抜けるときに戻す）
+    caller_env: Option<Rc<RefCell<Environment>>>,
+    /// new で呼び出された場合に、戻り値がオブジェクトでなければ結果とするオブジェクト
+    construct: Option<JSValue>,
+}
+
+/// フレームが実行するバイトコード
+enum Code<'a> {
+    /// execute に渡されたチャンク
+    Entry(&'a BytecodeChunk),
+    /// 関数本体のチャンク
+    Function(Rc<BytecodeChunk>),
+}
+
+impl Code<'_> {
+    fn chunk(&self) -> &BytecodeChunk {
+        match self {
+            Code::Entry(chunk) => chunk,
+            Code::Function(chunk) => chunk,
+        }
+    }
+}
+
+/// 命令を実行した後の制御
+enum Flow {
+    /// 次の命令へ進む
+    Next,
+    /// 現在のフレームから値を返す
+    Return(JSValue),
+    /// バイトコード関数を新しいフレームで呼び出す
+    Call {
+        func: Rc<JSFunction>,
+        this: JSValue,
+        args: Vec<JSValue>,
+        construct: Option<JSValue>,
+    },
+    /// 現在のフレームをバイトコード関数のフレームで置き換える
+    TailCall {
+        func: Rc<JSFunction>,
+        args: Vec<JSValue>,
+    },
+}
+
+/// ToPrimitive で優先する型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PreferredType {
+    /// ヒントなし（`+` 演算子・`==` 比較）
+    Default,
+    Number,
+    String,
+}
 
 /// 仮想マシン
 pub struct VM {
     /// オペランドスタック
     stack: Vec<JSValue>,
-    /// グローバル変数テーブル
-    globals: FxHashMap<String, JSValue>,
+    /// 現在実行中のスコープの環境
+    env: Rc<RefCell<Environment>>,
+    /// グローバル環境
+    global_env: Rc<RefCell<Environment>>,
+    /// 組み込みオブジェクト
+    builtins: Builtins,
+    /// Date 等が使用する時計
+    clock: Clock,
+    /// Math.random が使用する乱数生成器
+    rng: XorShift64,
+    /// console.log の出力先
+    printer: Printer,
+    /// デバッグ・プロファイル用のトレースフック
+    trace: Option<TraceHook>,
+    /// 実行中の関数呼び出しの深さ
+    call_depth: usize,
+    /// 関数呼び出しの深さの上限
+    max_call_depth: usize,
 }
 
 impl VM {
     /// 新しいVMインスタンスを作成
     pub fn new() -> Self {
+        let global_env = Rc::new(RefCell::new(Environment::new()));
+        let builtins = Builtins::new();
+        builtins.install(&global_env.borrow());
         Self {
             stack: Vec::new(),
-            globals: FxHashMap::default(),
+            env: global_env.clone(),
+            global_env,
+            builtins,
+            clock: Box::new(system_clock),
+            rng: XorShift64::new(system_clock().to_bits()),
+            printer: default_printer(),
+            trace: None,
+            call_depth: 0,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+        }
+    }
+
+    /// グローバル環境を取得
+    pub fn global_env(&self) -> &Rc<RefCell<Environment>> {
+        &self.global_env
+    }
+
+    /// 組み込みオブジェクトを取得
+    pub fn builtins(&self) -> &Builtins {
+        &self.builtins
+    }
+
+    /// Object.prototype を継承する空のオブジェクトを作成
+    pub fn create_object(&self) -> JSObject {
+        JSObject::with_prototype(Some(self.builtins.object_prototype.clone()))
+    }
+
+    /// 要素から Array.prototype を継承する配列オブジェクトを作成
+    pub fn create_array(&self, elements: Vec<JSValue>) -> JSValue {
+        let array = JSArray::from_vec(elements).to_object();
+        if let JSValue::Object(obj) = &array {
+            obj.borrow_mut()
+                .set_prototype(Some(self.builtins.array_prototype.clone()));
         }
+        array
+    }
+
+    /// 時計を差し替える（テスト用に固定時刻を注入する等）
+    pub fn set_clock(&mut self, clock: Clock) {
+        self.clock = clock;
+    }
+
+    /// 現在時刻（Unix エポックからのミリ秒）を取得
+    pub fn now(&self) -> f64 {
+        (self.clock)()
+    }
+
+    /// 乱数生成器のシードを設定（再現可能な Math.random 用）
+    pub fn seed_random(&mut self, seed: u64) {
+        self.rng = XorShift64::new(seed);
+    }
+
+    /// [0, 1) の乱数を生成
+    pub fn next_random(&mut self) -> f64 {
+        self.rng.next_f64()
+    }
+
+    /// console.log の出力先を差し替える（デフォルトは標準出力）
+    pub fn set_printer(&mut self, printer: Printer) {
+        self.printer = printer;
+    }
+
+    /// console.log の出力先に1行書き出す
+    pub fn print(&mut self, line: &str) {
+        (self.printer)(line);
+    }
+
+    /// トレースフックを設定（None で無効化）
+    pub fn set_trace(&mut self, trace: Option<TraceHook>) {
+        self.trace = trace;
+    }
+
+    /// 関数呼び出しの深さの上限を設定（超えると RangeError）
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
     }
 
     /// バイトコードを実行
-    pub fn execute(&mut self, chunk: BytecodeChunk) -> JSResult<JSValue> {
-        let mut pc = 0; // プログラムカウンタ
+    pub fn execute(&mut self, chunk: &BytecodeChunk) -> JSResult<JSValue> {
+        let entry = Frame {
+            code: Code::Entry(chunk),
+            pc: 0,
+            base: self.stack.len(),
+            handlers: Vec::new(),
+            caller_env: None,
+            construct: None,
+        };
+        let result = self.run(entry);
+        if result.is_err() {
+            // 捕捉されなかったエラーで中断した場合、途中の値を残さず、
+            // catch 節等で入ったスコープからグローバル環境に戻す
+            self.stack.clear();
+            self.env = self.global_env.clone();
+        }
+        result.map_err(Self::uncaught_error)
+    }
 
-        while pc < chunk.code.len() {
-            let opcode = &chunk.code[pc];
-            pc += 1;
+    /// バイトコードを実行するメインループ
+    ///
+    /// バイトコード関数の呼び出しは再帰せずにフレームを積んで同じループで実行し、
+    /// 最初のフレームから戻った時点でその値を返す。
+    fn run(&mut self, entry: Frame) -> JSResult<JSValue> {
+        let mut frames = vec![entry];
 
-            match opcode {
-                Opcode::LoadConst(idx) => {
-                    let value = chunk.constants[*idx].clone();
-                    self.stack.push(value);
-                }
-                Opcode::LoadVar(name) => {
-                    let value = self
-                        .globals
-                        .get(name)
-                        .cloned()
-                        .unwrap_or(JSValue::Undefined);
-                    self.stack.push(value);
-                }
-                Opcode::StoreVar(name) => {
-                    if let Some(value) = self.stack.pop() {
-                        self.globals.insert(name.clone(), value);
-                    } else {
-                        return Err(JSError::InternalError("Stack underflow".to_string()));
+        while let Some(frame) = frames.last_mut() {
+            let chunk = frame.code.chunk();
+            let flow = match chunk.code.get(frame.pc) {
+                Some(opcode) => {
+                    if let Some(trace) = &mut self.trace {
+                        trace(frame.pc, opcode, self.stack.len());
                     }
+                    frame.pc += 1;
+                    self.execute_opcode(chunk, opcode, &mut frame.pc, &mut frame.handlers)
                 }
-                Opcode::Pop => {
-                    self.stack.pop();
-                }
+                // チャンクの終端に達した場合、フレーム上に値があればそれを返す
+                None => Ok(Flow::Return(if self.stack.len() > frame.base {
+                    self.pop()?
+                } else {
+                    JSValue::Undefined
+                })),
+            };
 
-                // 算術演算
-                Opcode::Add => self.binary_op(|a, b| {
-                    // JavaScriptの加算は文字列連結も含む
-                    match (&a, &b) {
-                        (JSValue::String(s1), JSValue::String(s2)) => {
-                            JSValue::String(format!("{}{}", s1, s2))
+            let flow = match flow {
+                Ok(Flow::Call {
+                    func,
+                    this,
+                    args,
+                    construct,
+                }) => self
+                    .enter_function(&func, this, args, construct)
+                    .map(|frame| frames.push(frame))
+                    .map(|_| Flow::Next),
+                Ok(Flow::TailCall { func, args }) => {
+                    // 呼び出し元へ戻る代わりに、現在のフレームを呼び出し先で置き換える
+                    let construct = match frames.pop() {
+                        Some(frame) if frame.caller_env.is_some() => {
+                            let construct = frame.construct.clone();
+                            self.leave_frame(frame, JSValue::Undefined);
+                            construct
                         }
-                        (JSValue::String(s), _) => JSValue::String(format!("{}{}", s, b)),
-                        (_, JSValue::String(s)) => JSValue::String(format!("{}{}", a, s)),
-                        _ => JSValue::Number(a.to_number() + b.to_number()),
-                    }
-                })?,
-                Opcode::Sub => self.binary_numeric_op(|a, b| a - b)?,
-                Opcode::Mul => self.binary_numeric_op(|a, b| a * b)?,
-                Opcode::Div => self.binary_numeric_op(|a, b| a / b)?,
-                Opcode::Mod => self.binary_numeric_op(|a, b| a % b)?,
-                Opcode::Power => self.binary_numeric_op(|a, b| a.powf(b))?,
-
-                // 単項演算
-                Opcode::Neg => {
-                    let value = self.pop()?;
-                    self.stack.push(JSValue::Number(-value.to_number()));
-                }
-                Opcode::Not => {
-                    let value = self.pop()?;
-                    self.stack.push(JSValue::Boolean(!value.to_boolean()));
-                }
-                Opcode::BitNot => {
-                    let value = self.pop()?;
-                    let n = value.to_number() as i32;
-                    self.stack.push(JSValue::Number((!n) as f64));
+                        // 関数呼び出しでないフレームは残して通常の呼び出しにする
+                        Some(frame) => {
+                            frames.push(frame);
+                            None
+                        }
+                        None => None,
+                    };
+                    self.enter_function(&func, JSValue::Undefined, args, construct)
+                        .map(|frame| frames.push(frame))
+                        .map(|_| Flow::Next)
                 }
+                other => other,
+            };
 
-                // 比較演算
-                Opcode::Eq => self.comparison_op(|a, b| a.abstract_equals(b))?,
-                Opcode::NotEq => self.comparison_op(|a, b| !a.abstract_equals(b))?,
-                Opcode::StrictEq => self.comparison_op(|a, b| a.strict_equals(b))?,
-                Opcode::StrictNotEq => self.comparison_op(|a, b| !a.strict_equals(b))?,
-                Opcode::Lt => self.numeric_comparison_op(|a, b| a < b)?,
-                Opcode::Gt => self.numeric_comparison_op(|a, b| a > b)?,
-                Opcode::LtEq => self.numeric_comparison_op(|a, b| a <= b)?,
-                Opcode::GtEq => self.numeric_comparison_op(|a, b| a >= b)?,
-
-                // 論理演算
-                Opcode::And => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    // JavaScriptの && は短絡評価で、最初の falsy な値か最後の値を返す
-                    if !a.to_boolean() {
-                        self.stack.push(a);
-                    } else {
-                        self.stack.push(b);
+            match flow {
+                Ok(Flow::Next) | Ok(Flow::Call { .. }) | Ok(Flow::TailCall { .. }) => {}
+                Ok(Flow::Return(value)) => {
+                    let Some(frame) = frames.pop() else { break };
+                    let value = self.leave_frame(frame, value);
+                    if frames.is_empty() {
+                        return Ok(value);
                     }
+                    self.stack.push(value);
                 }
-                Opcode::Or => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    // JavaScriptの || は短絡評価で、最初の truthy な値か最後の値を返す
-                    if a.to_boolean() {
-                        self.stack.push(a);
-                    } else {
-                        self.stack.push(b);
+                Err(err) => {
+                    // ハンドラのあるフレームまで巻き戻して catch 節へ、なければ呼び出し元へ伝播する
+                    loop {
+                        let Some(frame) = frames.last_mut() else {
+                            return Err(err);
+                        };
+                        if let Some(handler) = frame.handlers.pop() {
+                            self.stack.truncate(handler.stack_depth);
+                            self.env = handler.env;
+                            let value = self.error_to_value(err);
+                            self.stack.push(value);
+                            frame.pc = handler.catch_pc;
+                            break;
+                        }
+                        if let Some(frame) = frames.pop() {
+                            self.leave_frame(frame, JSValue::Undefined);
+                        }
                     }
                 }
+            }
+        }
+        Ok(JSValue::Undefined)
+    }
 
-                // ビット演算
-                Opcode::BitAnd => self.bitwise_op(|a, b| a & b)?,
-                Opcode::BitOr => self.bitwise_op(|a, b| a | b)?,
-                Opcode::BitXor => self.bitwise_op(|a, b| a ^ b)?,
-                Opcode::LeftShift => self.bitwise_op(|a, b| a << (b & 0x1f))?,
-                Opcode::RightShift => self.bitwise_op(|a, b| a >> (b & 0x1f))?,
-                Opcode::UnsignedRightShift => {
-                    let b = self.pop()?;
-                    let a = self.pop()?;
-                    let a_u32 = a.to_number() as u32;
-                    let b_u32 = b.to_number() as u32;
-                    self.stack
-                        .push(JSValue::Number((a_u32 >> (b_u32 & 0x1f)) as f64));
-                }
+    /// 関数本体を実行するフレームを作成し、関数のスコープに入る
+    ///
+    /// 関数本体は捕捉した環境の子スコープで実行する。呼び出し元の環境はフレームに
+    /// 保存し、leave_frame で必ず復元する。
+    fn enter_function(
+        &mut self,
+        func: &JSFunction,
+        this: JSValue,
+        args: Vec<JSValue>,
+        construct: Option<JSValue>,
+    ) -> JSResult<Frame<'static>> {
+        if self.call_depth >= self.max_call_depth {
+            return Err(JSError::RangeError(
+                "Maximum call stack size exceeded".to_string(),
+            ));
+        }
+
+        let outer = func.env.clone().unwrap_or_else(|| self.global_env.clone());
+        let env = Environment::with_outer(outer);
+
+        // アロー関数は束縛せず、外側の関数の this / arguments をスコープチェーンで参照する
+        if !func.is_arrow {
+            // 渡された全ての引数を arguments オブジェクトとして公開（同名の仮引数が優先）
+            if func.uses_arguments {
+                env.define("arguments".to_string(), self.create_arguments(&args));
+            }
+            // `this` は識別子にならないため、同じ名前の束縛と衝突しない
+            if func.uses_this {
+                env.define("this".to_string(), this);
+            }
+        }
+
+        // パラメータ名に対応して引数をセット（足りない引数は undefined）
+        let mut args = args.into_iter();
+        for param in &func.params {
+            env.define(param.clone(), args.next().unwrap_or(JSValue::Undefined));
+        }
 
-                // 配列・オブジェクト操作
-                Opcode::NewArray(_size) => {
-                    use crate::value::JSArray;
-                    let arr = JSArray::new();
-                    self.stack.push(arr.to_object());
+        self.call_depth += 1;
+        let caller_env = std::mem::replace(&mut self.env, Rc::new(RefCell::new(env)));
+        Ok(Frame {
+            code: Code::Function(func.chunk.clone()),
+            pc: 0,
+            base: self.stack.len(),
+            handlers: Vec::new(),
+            caller_env: Some(caller_env),
+            construct,
+        })
+    }
+
+    /// フレームを抜けて呼び出し元の状態に戻し、呼び出しの結果を返す
+    fn leave_frame(&mut self, frame: Frame, value: JSValue) -> JSValue {
+        self.stack.truncate(frame.base);
+        if let Some(env) = frame.caller_env {
+            self.env = env;
+            self.call_depth -= 1;
+        }
+        // new で呼ばれた関数がオブジェクトを返さなければ新しく作成したオブジェクトを結果とする
+        match frame.construct {
+            Some(object) if !matches!(value, JSValue::Object(_)) => object,
+            _ => value,
+        }
+    }
+
+    /// 命令を1つ実行
+    fn execute_opcode(
+        &mut self,
+        chunk: &BytecodeChunk,
+        opcode: &Opcode,
+        pc: &mut usize,
+        handlers: &mut Vec<TryHandler>,
+    ) -> JSResult<Flow> {
+        match opcode {
+            Opcode::LoadConst(idx) => {
+                let value = Self::constant(chunk, *idx)?.clone();
+                self.stack.push(value);
+            }
+            Opcode::LoadUndefined => self.stack.push(JSValue::Undefined),
+            Opcode::LoadNull => self.stack.push(JSValue::Null),
+            Opcode::LoadBool(b) => self.stack.push(JSValue::Boolean(*b)),
+            Opcode::LoadVar(name) => {
+                let value = self
+                    .env
+                    .borrow()
+                    .lookup(name)?
+                    .unwrap_or(JSValue::Undefined);
+                self.stack.push(value);
+            }
+            Opcode::LoadThis => {
+                // 関数の外では undefined
+                let value = self
+                    .env
+                    .borrow()
+                    .lookup("this")?
+                    .unwrap_or(JSValue::Undefined);
+                self.stack.push(value);
+            }
+            Opcode::StoreVar(name) => {
+                let value = self.pop()?;
+                // 未宣言の変数への代入はグローバルに作成する
+                if let Some(value) = self.env.borrow().assign(name, value)? {
+                    self.global_env.borrow().define(name.clone(), value);
                 }
-                Opcode::NewObject => {
-                    use crate::value::JSObject;
-                    use std::cell::RefCell;
-                    use std::rc::Rc;
-                    let obj = JSObject::new();
-                    self.stack.push(JSValue::Object(Rc::new(RefCell::new(obj))));
+            }
+            Opcode::DefineVar(name) => {
+                let value = self.pop()?;
+                self.env.borrow().define(name.clone(), value);
+            }
+            Opcode::DeclareVar(name) => {
+                // 既に同名の束縛がある場合（再宣言・前回の評価で宣言済み）は値を保つ
+                let env = self.env.borrow();
+                if !env.has_own_binding(name) {
+                    env.define(name.clone(), JSValue::Undefined);
                 }
-                Opcode::GetProperty => {
-                    let key = self.pop()?;
-                    let obj = self.pop()?;
-
-                    match obj {
-                        JSValue::Object(ref obj_ref) => {
-                            let key_str = key.to_string();
-                            let value = obj_ref.borrow().get(&key_str);
-                            self.stack.push(value);
-                        }
-                        _ => {
-                            // プリミティブ値のプロパティアクセスは後で実装
-                            self.stack.push(JSValue::Undefined);
-                        }
-                    }
+            }
+            Opcode::DeclareLexical(name) => {
+                // REPL で前回の評価が宣言した束縛は TDZ に戻さない
+                let env = self.env.borrow();
+                if !env.has_own_binding(name) {
+                    env.declare_uninitialized(name.clone());
                 }
-                Opcode::SetProperty => {
-                    let value = self.pop()?;
-                    let key = self.pop()?;
-                    let obj = self.pop()?;
-
-                    match obj {
-                        JSValue::Object(ref obj_ref) => {
-                            let key_str = key.to_string();
-                            obj_ref.borrow_mut().set(key_str, value.clone());
-                            self.stack.push(obj.clone()); // オブジェクトを返す
+            }
+            Opcode::Pop => {
+                self.stack.pop();
+            }
+            Opcode::Dup => {
+                let top = self
+                    .stack
+                    .last()
+                    .cloned()
+                    .ok_or_else(|| JSError::InternalError("Stack underflow".to_string()))?;
+                self.stack.push(top);
+            }
+            Opcode::Swap => {
+                let len = self.stack.len();
+                if len < 2 {
+                    return Err(JSError::InternalError("Stack underflow".to_string()));
+                }
+                self.stack.swap(len - 1, len - 2);
+            }
+
+            // 算術演算
+            Opcode::Add => {
+                if !self.number_op(|a, b| a + b) {
+                    self.binary_op(|a, b| {
+                        // JavaScriptの加算は文字列連結も含む
+                        match (&a, &b) {
+                            (JSValue::String(s1), JSValue::String(s2)) => {
+                                JSValue::String(format!("{}{}", s1, s2))
+                            }
+                            (JSValue::String(s), _) => JSValue::String(format!("{}{}", s, b)),
+                            (_, JSValue::String(s)) => JSValue::String(format!("{}{}", a, s)),
+                            _ => JSValue::Number(a.to_number() + b.to_number()),
                         }
-                        _ => {
-                            return Err(JSError::TypeError(
-                                "Cannot set property on non-object".to_string(),
-                            ));
+                    })?
+                }
+            }
+            Opcode::Sub => self.arithmetic_op(|a, b| a - b)?,
+            Opcode::Mul => self.arithmetic_op(|a, b| a * b)?,
+            Opcode::Div => self.arithmetic_op(|a, b| a / b)?,
+            Opcode::Mod => self.arithmetic_op(|a, b| {
+                SmallInt::apply(
+                    a,
+                    b,
+                    // 負の数を割り切ると -0、0 で割ると NaN になるため浮動小数点数で計算する
+                    |x, y| x.checked_rem(y).filter(|&r| r != 0 || x >= 0),
+                    |a, b| a % b,
+                )
+            })?,
+            Opcode::Power => self.arithmetic_op(|a, b| {
+                SmallInt::apply(
+                    a,
+                    b,
+                    |x, y| u32::try_from(y).ok().and_then(|y| x.checked_pow(y)),
+                    |a, b| {
+                        // powf と異なり、1 ** NaN と (±1) ** ±Infinity は NaN
+                        if b.is_nan() || (a.abs() == 1.0 && b.is_infinite()) {
+                            f64::NAN
+                        } else {
+                            a.powf(b)
                         }
+                    },
+                )
+            })?,
+
+            // 単項演算
+            Opcode::Neg => {
+                let value = self.pop()?;
+                let n = self.to_number(value)?;
+                self.stack.push(JSValue::Number(-n));
+            }
+            Opcode::Not => {
+                let value = self.pop()?;
+                self.stack.push(JSValue::Boolean(!value.to_boolean()));
+            }
+            Opcode::BitNot => {
+                let value = self.pop()?;
+                let n = self.to_int32(value)?;
+                self.stack.push(JSValue::Number((!n) as f64));
+            }
+
+            // 比較演算
+            Opcode::Eq => {
+                let result = self.loose_equals()?;
+                self.stack.push(JSValue::Boolean(result));
+            }
+            Opcode::NotEq => {
+                let result = self.loose_equals()?;
+                self.stack.push(JSValue::Boolean(!result));
+            }
+            Opcode::StrictEq => self.comparison_op(|a, b| a.strict_equals(b))?,
+            Opcode::StrictNotEq => self.comparison_op(|a, b| !a.strict_equals(b))?,
+            Opcode::Lt => self.relational_op(|ord| ord.is_lt())?,
+            Opcode::Gt => self.relational_op(|ord| ord.is_gt())?,
+            Opcode::LtEq => self.relational_op(|ord| ord.is_le())?,
+            Opcode::GtEq => self.relational_op(|ord| ord.is_ge())?,
+            Opcode::Instanceof => {
+                let constructor = self.pop()?;
+                let value = self.pop()?;
+                let result = self.instance_of(&value, &constructor)?;
+                self.stack.push(JSValue::Boolean(result));
+            }
+            Opcode::In => {
+                let obj = self.pop()?;
+                let key = self.pop()?;
+                let key_str = self.to_property_key(key)?;
+                // 配列はオブジェクトとして保持しているため、インデックスも has_property で判定できる
+                let found = match &obj {
+                    JSValue::Object(obj_ref) => obj_ref.borrow().has_property(&key_str),
+                    JSValue::NativeFunction(native) => {
+                        native.properties.borrow().has_property(&key_str)
                     }
-                }
-                Opcode::ArrayPush => {
-                    // スタック: [array, value, index]
-                    let index = self.pop()?;
-                    let value = self.pop()?;
-
-                    // 配列はスタックの一番下にあるが、ポップしない
-                    if let Some(JSValue::Object(obj_ref)) = self.stack.last() {
-                        let idx_num = index.to_number() as usize;
-                        let key_str = idx_num.to_string();
-                        obj_ref.borrow_mut().set(key_str, value);
-                    } else {
-                        return Err(JSError::TypeError("ArrayPush: not an object".to_string()));
+                    JSValue::Function(func) => func
+                        .properties(&self.builtins.object_prototype)
+                        .borrow()
+                        .has_property(&key_str),
+                    JSValue::BoundFunction(_) => false,
+                    _ => {
+                        return Err(JSError::TypeError(format!(
+                            "Cannot use 'in' operator to search for '{}' in {}",
+                            key_str, obj
+                        )));
                     }
-                }
-                Opcode::ObjectSetProperty => {
-                    // スタック: [object, value, key]
-                    let key = self.pop()?;
-                    let value = self.pop()?;
-
-                    // オブジェクトはスタックの一番下にあるが、ポップしない
-                    if let Some(JSValue::Object(obj_ref)) = self.stack.last() {
-                        let key_str = key.to_string();
-                        obj_ref.borrow_mut().set(key_str, value);
-                    } else {
-                        return Err(JSError::TypeError(
-                            "ObjectSetProperty: not an object".to_string(),
-                        ));
+                };
+                self.stack.push(JSValue::Boolean(found));
+            }
+
+            // ビット演算
+            Opcode::BitAnd => self.bitwise_op(|a, b| a & b)?,
+            Opcode::BitOr => self.bitwise_op(|a, b| a | b)?,
+            Opcode::BitXor => self.bitwise_op(|a, b| a ^ b)?,
+            Opcode::LeftShift => self.bitwise_op(|a, b| a << (b & 0x1f))?,
+            Opcode::RightShift => self.bitwise_op(|a, b| a >> (b & 0x1f))?,
+            Opcode::UnsignedRightShift => {
+                let b = self.pop()?;
+                let a = self.pop()?;
+                let a = self.to_uint32(a)?;
+                let b = self.to_uint32(b)?;
+                self.stack.push(JSValue::Number(f64::from(a >> (b & 0x1f))));
+            }
+
+            // 配列・オブジェクト操作
+            Opcode::NewArray(_size) => {
+                let array = self.create_array(Vec::new());
+                self.stack.push(array);
+            }
+            Opcode::BuildArray(count) => {
+                let elements = self.pop_args(*count)?;
+                let array = self.create_array(elements);
+                self.stack.push(array);
+            }
+            Opcode::BuildSpreadArray(spreads) => {
+                let values = self.pop_args(spreads.len())?;
+                let mut elements = Vec::with_capacity(values.len());
+                for (value, spread) in values.into_iter().zip(spreads) {
+                    if !spread {
+                        elements.push(value);
+                        continue;
+                    }
+                    let iterator = self.get_iterator(value)?;
+                    while let Some(element) = self.iterator_next(&iterator)? {
+                        elements.push(element);
                     }
                 }
-                Opcode::CreateFunction(idx) => {
-                    // 定数プールの関数オブジェクト（BytecodeChunk）をそのままプッシュ
-                    let func = chunk.constants[*idx].clone();
-                    self.stack.push(func);
+                let array = self.create_array(elements);
+                self.stack.push(array);
+            }
+            Opcode::BuildObject(count) => {
+                let mut entries = self.pop_args(count * 2)?.into_iter();
+                let mut obj = self.create_object();
+                while let (Some(key), Some(value)) = (entries.next(), entries.next()) {
+                    obj.set(key.into_property_key(), value);
                 }
-                Opcode::CallFunction(arg_count) => {
-                    // スタック: [..., arg1, arg2, ..., func]
-                    let mut args = Vec::new();
-                    for _ in 0..*arg_count {
-                        args.push(self.pop()?);
-                    }
-                    // argsは逆順なので反転
-                    args.reverse();
-
-                    let func = self.pop()?;
-
-                    match func {
-                        JSValue::Function(func_chunk, params) => {
-                            // 新しいVMを作って関数を実行（簡易実装）
-                            let mut new_vm = VM::new();
-
-                            // パラメータ名があれば、それに対応して引数をセット
-                            for (i, arg) in args.into_iter().enumerate() {
-                                if i < params.len() {
-                                    new_vm.globals.insert(params[i].clone(), arg);
-                                } else {
-                                    // 余分な引数は argN としても格納
-                                    new_vm.globals.insert(format!("arg{}", i), arg);
-                                }
-                            }
+                self.stack.push(JSValue::Object(Rc::new(RefCell::new(obj))));
+            }
+            Opcode::NewObject => {
+                let obj = self.create_object();
+                self.stack.push(JSValue::Object(Rc::new(RefCell::new(obj))));
+            }
+            Opcode::GetProperty => {
+                let key = self.pop()?;
+                let obj = self.pop()?;
+                let key = self.to_property_key(key)?;
+                let value = self.get_property(&obj, &key)?;
+                self.stack.push(value);
+            }
+            Opcode::SetProperty | Opcode::StoreProperty => {
+                let value = self.pop()?;
+                let key = self.pop()?;
+                let obj = self.pop()?;
+                let key = self.to_property_key(key)?;
+                // 代入式の値は代入された値（StoreProperty は値を積まない）
+                if matches!(opcode, Opcode::SetProperty) {
+                    self.stack.push(value.clone());
+                }
+                self.set_property(&obj, key, value)?;
+            }
+            Opcode::GetPropertyByName(name) => {
+                let obj = self.pop()?;
+                let value = match chunk.property_cache(*pc - 1) {
+                    Some(cache) => self.get_property_cached(&obj, name, cache)?,
+                    None => self.get_property(&obj, name)?,
+                };
+                self.stack.push(value);
+            }
+            Opcode::SetPropertyByName(name) | Opcode::StorePropertyByName(name) => {
+                let value = self.pop()?;
+                let obj = self.pop()?;
+                if matches!(opcode, Opcode::SetPropertyByName(_)) {
+                    self.stack.push(value.clone());
+                }
+                self.set_property(&obj, name.clone(), value)?;
+            }
+            Opcode::ArrayPush => {
+                // スタック: [array, value, index]
+                let index = self.pop()?;
+                let value = self.pop()?;
 
-                            let res = new_vm.execute(func_chunk)?;
-                            self.stack.push(res);
-                        }
-                        _ => {
-                            return Err(JSError::TypeError(
-                                "CallFunction: not a function".to_string(),
-                            ));
-                        }
+                // 配列はスタックの一番下にあるが、ポップしない
+                if let Some(JSValue::Object(obj_ref)) = self.stack.last() {
+                    let idx_num = index.to_number() as usize;
+                    let key_str = idx_num.to_string();
+                    obj_ref.borrow_mut().set(key_str, value);
+                } else {
+                    return Err(JSError::TypeError("ArrayPush: not an object".to_string()));
+                }
+            }
+            Opcode::ObjectSetProperty => {
+                // スタック: [object, value, key]
+                let key = self.pop()?;
+                let value = self.pop()?;
+
+                // オブジェクトはスタックの一番下にあるが、ポップしない
+                let key_str = self.to_property_key(key)?;
+                if let Some(JSValue::Object(obj_ref)) = self.stack.last() {
+                    obj_ref.borrow_mut().set(key_str, value);
+                } else {
+                    return Err(JSError::TypeError(
+                        "ObjectSetProperty: not an object".to_string(),
+                    ));
+                }
+            }
+            Opcode::CreateFunction(idx) => {
+                // 定数プールの関数テンプレートから、現在の環境を捕捉したクロージャを生成
+                let JSValue::Function(template) = Self::constant(chunk, *idx)? else {
+                    return Err(JSError::InternalError(
+                        "CreateFunction: constant is not a function".to_string(),
+                    ));
+                };
+                let closure = match &template.self_name {
+                    Some(name) => {
+                        // 名前付き関数式: 自身の名前だけを束縛したスコープを間に挟む
+                        let env = Rc::new(RefCell::new(Environment::with_outer(self.env.clone())));
+                        let closure = Rc::new(template.with_env(env.clone()));
+                        env.borrow()
+                            .define(name.clone(), JSValue::Function(closure.clone()));
+                        closure
                     }
+                    None => Rc::new(template.with_env(self.env.clone())),
+                };
+                self.stack.push(JSValue::Function(closure));
+            }
+            Opcode::CallFunction(arg_count) => {
+                // スタック: [..., func, arg1, arg2, ..., argN]
+                let args = self.pop_args(*arg_count)?;
+                let func = self.pop()?;
+                if let JSValue::Function(func) = func {
+                    return Ok(Flow::Call {
+                        func,
+                        this: JSValue::Undefined,
+                        args,
+                        construct: None,
+                    });
+                }
+                Self::ensure_callable(chunk, *pc - 1, &func)?;
+                let result = self.call_value(&func, JSValue::Undefined, args)?;
+                self.stack.push(result);
+            }
+            Opcode::TailCall(arg_count) => {
+                let args = self.pop_args(*arg_count)?;
+                let func = self.pop()?;
+                if let JSValue::Function(func) = func {
+                    return Ok(Flow::TailCall { func, args });
+                }
+                // ネイティブ関数は通常どおり呼び出し、続く Return で値を返す
+                Self::ensure_callable(chunk, *pc - 1, &func)?;
+                let result = self.call_value(&func, JSValue::Undefined, args)?;
+                self.stack.push(result);
+            }
+            Opcode::CallMethod(arg_count) => {
+                // スタック: [..., obj, key, arg1, arg2, ..., argN]
+                let args = self.pop_args(*arg_count)?;
+                let key = self.pop()?;
+                let this = self.pop()?;
+                let key = self.to_property_key(key)?;
+                let func = self.get_property(&this, &key)?;
+                if let JSValue::Function(func) = func {
+                    return Ok(Flow::Call {
+                        func,
+                        this,
+                        args,
+                        construct: None,
+                    });
+                }
+                Self::ensure_callable(chunk, *pc - 1, &func)?;
+                let result = self.call_value(&func, this, args)?;
+                self.stack.push(result);
+            }
+            Opcode::CallFunctionSpread => {
+                // スタック: [..., func, this, args]
+                let args = self.pop_argument_array()?;
+                let this = self.pop()?;
+                let func = self.pop()?;
+                if let JSValue::Function(func) = func {
+                    return Ok(Flow::Call {
+                        func,
+                        this,
+                        args,
+                        construct: None,
+                    });
                 }
+                Self::ensure_callable(chunk, *pc - 1, &func)?;
+                let result = self.call_value(&func, this, args)?;
+                self.stack.push(result);
+            }
+            Opcode::New(_) | Opcode::NewSpread => {
+                // スタック: [..., constructor, arg1, arg2, ..., argN] または [..., constructor, args]
+                let args = match opcode {
+                    Opcode::New(arg_count) => self.pop_args(*arg_count)?,
+                    _ => self.pop_argument_array()?,
+                };
+                let constructor = self.pop()?;
+                if let JSValue::Function(func) = &constructor
+                    && !func.is_arrow
+                {
+                    let object = self.create_instance(func);
+                    return Ok(Flow::Call {
+                        func: func.clone(),
+                        this: object.clone(),
+                        args,
+                        construct: Some(object),
+                    });
+                }
+                let result = self.construct(&constructor, args)?;
+                self.stack.push(result);
+            }
+
+            // その他
+            Opcode::Typeof => {
+                let value = self.pop()?;
+                self.stack
+                    .push(JSValue::String(value.type_of().to_string()));
+            }
+            Opcode::Void => {
+                self.pop()?;
+                self.stack.push(JSValue::Undefined);
+            }
 
-                // その他
-                Opcode::Typeof => {
-                    let value = self.pop()?;
-                    self.stack
-                        .push(JSValue::String(value.type_of().to_string()));
+            // 制御フロー
+            Opcode::Jump(offset) => {
+                *pc = *offset;
+            }
+            Opcode::JumpIfFalse(offset) => {
+                let condition = self.pop()?;
+                if !condition.to_boolean() {
+                    *pc = *offset;
                 }
-                Opcode::Void => {
-                    self.pop()?;
-                    self.stack.push(JSValue::Undefined);
+            }
+            Opcode::JumpIfTrue(offset) => {
+                let condition = self.pop()?;
+                if condition.to_boolean() {
+                    *pc = *offset;
                 }
+            }
+            Opcode::Return => {
+                let value = self.pop()?;
+                return Ok(Flow::Return(value));
+            }
 
-                // 制御フロー
-                Opcode::Jump(offset) => {
-                    pc = *offset;
+            // イテレーション
+            Opcode::GetIterator => {
+                let iterable = self.pop()?;
+                let iterator = self.get_iterator(iterable)?;
+                self.stack.push(iterator);
+            }
+            Opcode::IteratorNext(offset) => {
+                let iterator = self
+                    .stack
+                    .last()
+                    .cloned()
+                    .ok_or_else(|| JSError::InternalError("Stack underflow".to_string()))?;
+                match self.iterator_next(&iterator)? {
+                    Some(value) => self.stack.push(value),
+                    None => *pc = *offset,
                 }
-                Opcode::JumpIfFalse(offset) => {
-                    let condition = self.pop()?;
-                    if !condition.to_boolean() {
-                        pc = *offset;
-                    }
+            }
+
+            // 例外処理
+            Opcode::Throw => {
+                let value = self.pop()?;
+                return Err(JSError::Exception(value));
+            }
+            Opcode::EnterTry(catch_pc) => {
+                handlers.push(TryHandler {
+                    catch_pc: *catch_pc,
+                    stack_depth: self.stack.len(),
+                    env: self.env.clone(),
+                });
+            }
+            Opcode::LeaveTry => {
+                handlers.pop();
+            }
+            Opcode::PushScope => {
+                let env = Environment::with_outer(self.env.clone());
+                self.env = Rc::new(RefCell::new(env));
+            }
+            Opcode::PopScope => {
+                let outer = self.env.borrow().outer.clone().ok_or_else(|| {
+                    JSError::InternalError("PopScope: no enclosing scope".to_string())
+                })?;
+                self.env = outer;
+            }
+        }
+        Ok(Flow::Next)
+    }
+
+    /// for-of で走査するイテレータを取得（GetIterator 抽象操作）
+    ///
+    /// 配列と文字列は要素を順に返す配列イテレータ、Map は [キー, 値]・Set は値を返す
+    /// Map / Set イテレータ、呼び出せる next を持つオブジェクトはそれ自身をイテレータとして使う。
+    pub fn get_iterator(&mut self, iterable: JSValue) -> JSResult<JSValue> {
+        if let JSValue::Object(obj) = &iterable {
+            let collection = match obj.borrow().internal_slot() {
+                Some(InternalSlot::Map(data)) => Some((data.clone(), IterationKind::Entries)),
+                Some(InternalSlot::Set(data)) => Some((data.clone(), IterationKind::Values)),
+                _ => None,
+            };
+            if let Some((data, kind)) = collection {
+                return Ok(self.builtins.create_map_iterator(data, kind));
+            }
+        }
+        match &iterable {
+            JSValue::String(_) => {}
+            JSValue::Object(obj) if obj.borrow().is_array() => {}
+            JSValue::Object(_) if self.get_property(&iterable, "next")?.is_callable() => {
+                return Ok(iterable);
+            }
+            _ => {
+                return Err(JSError::TypeError(format!(
+                    "{} is not iterable",
+                    iterable.type_of()
+                )));
+            }
+        }
+        Ok(self
+            .builtins
+            .create_array_iterator(iterable, IterationKind::Values))
+    }
+
+    /// イテレータを1つ進める（終了していれば None）
+    ///
+    /// 配列イテレータと Map / Set イテレータは next を呼ばずに直接進め、
+    /// それ以外は next() の結果の done と value を読む。
+    pub fn iterator_next(&mut self, iterator: &JSValue) -> JSResult<Option<JSValue>> {
+        if let JSValue::Object(obj) = iterator
+            && matches!(
+                obj.borrow().internal_slot(),
+                Some(InternalSlot::ArrayIterator { .. })
+            )
+        {
+            return array_iterator_step(self, obj);
+        }
+        if let JSValue::Object(obj) = iterator
+            && matches!(
+                obj.borrow().internal_slot(),
+                Some(InternalSlot::MapIterator { .. })
+            )
+        {
+            return map_iterator_step(self, obj);
+        }
+        let next = self.get_property(iterator, "next")?;
+        let result = self.call_value(&next, iterator.clone(), Vec::new())?;
+        if !matches!(result, JSValue::Object(_)) {
+            return Err(JSError::TypeError(format!(
+                "Iterator result {} is not an object",
+                result
+            )));
+        }
+        if self.get_property(&result, "done")?.to_boolean() {
+            return Ok(None);
+        }
+        Ok(Some(self.get_property(&result, "value")?))
+    }
+
+    /// 呼び出し命令の対象が関数でなければ TypeError（呼び出し対象の表記と値の型を示す）
+    fn ensure_callable(chunk: &BytecodeChunk, at: usize, func: &JSValue) -> JSResult<()> {
+        if func.is_callable() {
+            return Ok(());
+        }
+        let message = match chunk.callee_name(at) {
+            Some(name) => format!("{} is not a function ({})", name, func.type_of()),
+            None => format!("{} is not a function", func),
+        };
+        Err(JSError::TypeError(message))
+    }
+
+    /// 値を関数として呼び出す
+    pub fn call_value(
+        &mut self,
+        func: &JSValue,
+        this: JSValue,
+        args: Vec<JSValue>,
+    ) -> JSResult<JSValue> {
+        if !func.is_callable() {
+            return Err(JSError::TypeError(format!("{} is not a function", func)));
+        }
+        match func {
+            JSValue::Function(func) => self.call_function(func, this, args, None),
+            JSValue::NativeFunction(native) => (native.call)(self, &this, &args),
+            // 束縛した this を使い、束縛した引数を前に付けて対象の関数を呼ぶ
+            JSValue::BoundFunction(bound) => {
+                let args = bound.args.iter().cloned().chain(args).collect();
+                self.call_value(&bound.target, bound.this.clone(), args)
+            }
+            _ => Err(JSError::InternalError(
+                "callable value has no call behavior".to_string(),
+            )),
+        }
+    }
+
+    /// 値をコンストラクタとして呼び出す（new 演算子）
+    fn construct(&mut self, constructor: &JSValue, args: Vec<JSValue>) -> JSResult<JSValue> {
+        match constructor {
+            JSValue::NativeFunction(native) => match native.construct {
+                Some(construct) => construct(self, &JSValue::Undefined, &args),
+                None => Err(JSError::TypeError(format!(
+                    "{} is not a constructor",
+                    native.name
+                ))),
+            },
+            JSValue::Function(func) if !func.is_arrow => {
+                let object = self.create_instance(func);
+                self.call_function(func, object.clone(), args, Some(object))
+            }
+            // new では束縛した this を使わない
+            JSValue::BoundFunction(bound) => {
+                let args = bound.args.iter().cloned().chain(args).collect();
+                self.construct(&bound.target, args)
+            }
+            JSValue::Function(_) => Err(JSError::TypeError(
+                "arrow function is not a constructor".to_string(),
+            )),
+            _ => Err(JSError::TypeError("not a constructor".to_string())),
+        }
+    }
+
+    /// new で呼び出す関数の this にするオブジェクトを作成
+    ///
+    /// 関数の prototype プロパティを継承し、オブジェクトでなければ Object.prototype を継承する。
+    fn create_instance(&self, func: &Rc<JSFunction>) -> JSValue {
+        let prototype = match func
+            .properties(&self.builtins.object_prototype)
+            .borrow()
+            .get("prototype")
+        {
+            JSValue::Object(prototype) => prototype,
+            _ => self.builtins.object_prototype.clone(),
+        };
+        JSValue::Object(Rc::new(RefCell::new(JSObject::with_prototype(Some(
+            prototype,
+        )))))
+    }
+
+    /// 値をプリミティブに変換（ToPrimitive 抽象操作）
+    ///
+    /// オブジェクトは valueOf / toString を順に呼び出し（String 優先なら逆順）、
+    /// 最初に得られたプリミティブ値を返す。
+    pub fn to_primitive(&mut self, value: JSValue, preferred: PreferredType) -> JSResult<JSValue> {
+        if !matches!(value, JSValue::Object(_)) {
+            return Ok(value);
+        }
+        let methods = match preferred {
+            PreferredType::String => ["toString", "valueOf"],
+            PreferredType::Default | PreferredType::Number => ["valueOf", "toString"],
+        };
+        for name in methods {
+            let method = self.get_property(&value, name)?;
+            if !method.is_callable() {
+                continue;
+            }
+            let result = self.call_value(&method, value.clone(), Vec::new())?;
+            if !matches!(result, JSValue::Object(_)) {
+                return Ok(result);
+            }
+        }
+        // メソッドがない（Object.create(null) 等）か、どちらもプリミティブを返さなかった
+        Err(JSError::TypeError(
+            "Cannot convert object to primitive value".to_string(),
+        ))
+    }
+
+    /// 値を数値に変換（オブジェクトは ToPrimitive を経由する）
+    pub fn to_number(&mut self, value: JSValue) -> JSResult<f64> {
+        Ok(self.to_primitive(value, PreferredType::Number)?.to_number())
+    }
+
+    /// 値を 32 ビット符号付き整数に変換（オブジェクトは ToPrimitive を経由する）
+    pub fn to_int32(&mut self, value: JSValue) -> JSResult<i32> {
+        Ok(self.to_primitive(value, PreferredType::Number)?.to_int32())
+    }
+
+    /// 値を 32 ビット符号なし整数に変換（オブジェクトは ToPrimitive を経由する）
+    pub fn to_uint32(&mut self, value: JSValue) -> JSResult<u32> {
+        Ok(self.to_primitive(value, PreferredType::Number)?.to_uint32())
+    }
+
+    /// 値を文字列に変換（オブジェクトは ToPrimitive を経由する）
+    pub fn to_string(&mut self, value: JSValue) -> JSResult<String> {
+        Ok(self
+            .to_primitive(value, PreferredType::String)?
+            .to_console_string())
+    }
+
+    /// 値をプロパティキーに変換（ToPropertyKey 抽象操作）
+    ///
+    /// オブジェクトは文字列を優先した ToPrimitive を経由する。
+    pub fn to_property_key(&mut self, value: JSValue) -> JSResult<String> {
+        Ok(self
+            .to_primitive(value, PreferredType::String)?
+            .into_property_key())
+    }
+
+    /// 値のプロパティを取得
+    pub fn get_property(&mut self, obj: &JSValue, key: &str) -> JSResult<JSValue> {
+        match obj {
+            JSValue::Object(obj_ref) => self.get_from(obj_ref, key, obj),
+            JSValue::NativeFunction(native) if native.properties.borrow().has_property(key) => {
+                self.get_from(&native.properties, key, obj)
+            }
+            JSValue::Function(func)
+                if func
+                    .properties(&self.builtins.object_prototype)
+                    .borrow()
+                    .has_property(key) =>
+            {
+                let properties = func.properties(&self.builtins.object_prototype).clone();
+                self.get_from(&properties, key, obj)
+            }
+            // 関数自身にないプロパティは Function.prototype から探す
+            JSValue::Function(_) | JSValue::NativeFunction(_) | JSValue::BoundFunction(_) => {
+                let prototype = self.builtins.function_prototype.clone();
+                self.get_from(&prototype, key, obj)
+            }
+            // 文字列は length・インデックスを直接返し、それ以外は String.prototype から探す
+            JSValue::String(s) => {
+                if key == "length" {
+                    return Ok(JSValue::Number(s.chars().count() as f64));
                 }
-                Opcode::Return => {
-                    let value = self.pop()?;
-                    return Ok(value);
+                if let Some(index) = array_index(key) {
+                    return Ok(s
+                        .chars()
+                        .nth(index as usize)
+                        .map(|ch| JSValue::String(ch.to_string()))
+                        .unwrap_or(JSValue::Undefined));
                 }
+                let prototype = self.builtins.string_prototype.clone();
+                self.get_from(&prototype, key, obj)
+            }
+            JSValue::Number(_) => {
+                let prototype = self.builtins.number_prototype.clone();
+                self.get_from(&prototype, key, obj)
+            }
+            JSValue::Boolean(_) => {
+                let prototype = self.builtins.boolean_prototype.clone();
+                self.get_from(&prototype, key, obj)
+            }
+            // その他のプリミティブ値のプロパティアクセスは後で実装
+            _ => Ok(JSValue::Undefined),
+        }
+    }
+
+    /// オブジェクトのプロパティを取得（アクセサプロパティは receiver を this として getter を呼ぶ）
+    fn get_from(
+        &mut self,
+        object: &RefCell<JSObject>,
+        key: &str,
+        receiver: &JSValue,
+    ) -> JSResult<JSValue> {
+        let property = object.borrow().find_property(key);
+        match property {
+            Some(Property {
+                accessor: Some(accessor),
+                ..
+            }) => match accessor.get {
+                JSValue::Undefined => Ok(JSValue::Undefined),
+                getter => self.call_value(&getter, receiver.clone(), Vec::new()),
+            },
+            Some(property) => Ok(property.value),
+            None => Ok(JSValue::Undefined),
+        }
+    }
+
+    /// インラインキャッシュを使って名前付きプロパティを取得
+    ///
+    /// 自身のデータプロパティだけをキャッシュし、それ以外は get_property に任せる。
+    fn get_property_cached(
+        &mut self,
+        obj: &JSValue,
+        name: &str,
+        cache: &PropertyCache,
+    ) -> JSResult<JSValue> {
+        let JSValue::Object(obj_ref) = obj else {
+            return self.get_property(obj, name);
+        };
+        {
+            let obj = obj_ref.borrow();
+            let shape = obj.shape();
+            if let Some(value) = cache.lookup(shape).and_then(|slot| obj.slot_value(slot)) {
+                return Ok(value);
+            }
+            if let Some(slot) = obj.own_slot(name)
+                && let Some(value) = obj.slot_value(slot)
+            {
+                cache.update(shape, slot);
+                return Ok(value);
             }
         }
+        self.get_property(obj, name)
+    }
 
-        // プログラム終了時、スタックに値があればそれを返す
-        if let Some(value) = self.stack.pop() {
-            Ok(value)
-        } else {
-            Ok(JSValue::Undefined)
+    /// 値のプロパティに代入（オブジェクト以外は TypeError）
+    ///
+    /// プロトタイプチェーン上のアクセサプロパティは obj を this として setter を呼ぶ。
+    /// setter のないアクセサプロパティへの代入は何もしない。
+    fn set_property(&mut self, obj: &JSValue, key: String, value: JSValue) -> JSResult<()> {
+        let object = match obj {
+            JSValue::Object(obj_ref) => obj_ref,
+            JSValue::NativeFunction(native) => &native.properties,
+            JSValue::Function(func) => func.properties(&self.builtins.object_prototype),
+            _ => {
+                return Err(JSError::TypeError(
+                    "Cannot set property on non-object".to_string(),
+                ));
+            }
+        };
+        let accessor = object
+            .borrow()
+            .find_property(&key)
+            .and_then(|prop| prop.accessor);
+        match accessor {
+            Some(Accessor {
+                set: JSValue::Undefined,
+                ..
+            }) => {}
+            Some(accessor) => {
+                self.call_value(&accessor.set, obj.clone(), vec![value])?;
+            }
+            None => {
+                object.borrow_mut().set(key, value);
+            }
         }
+        Ok(())
+    }
+
+    /// 関数を呼び出す（ネイティブ関数からの呼び出し用）
+    ///
+    /// 関数本体は新しい実行ループで実行する。呼び出しの深さは実行中のフレームと
+    /// 合わせて数える。
+    fn call_function(
+        &mut self,
+        func: &JSFunction,
+        this: JSValue,
+        args: Vec<JSValue>,
+        construct: Option<JSValue>,
+    ) -> JSResult<JSValue> {
+        let frame = self.enter_function(func, this, args, construct)?;
+        self.run(frame)
+    }
+
+    /// arguments オブジェクト（インデックスと length を持つ配列風オブジェクト）を作成
+    fn create_arguments(&self, args: &[JSValue]) -> JSValue {
+        let mut arguments = self.create_object();
+        for (i, arg) in args.iter().enumerate() {
+            arguments.set(i.to_string(), arg.clone());
+        }
+        arguments.define_property(
+            "length".to_string(),
+            Property::non_enumerable(JSValue::Number(args.len() as f64)),
+        );
+        JSValue::Object(Rc::new(RefCell::new(arguments)))
+    }
+
+    /// 捕捉したエラーを catch 節に渡す値に変換
+    ///
+    /// エンジン内部で発生したエラーは対応する種類のエラーオブジェクトにする。
+    fn error_to_value(&self, err: JSError) -> JSValue {
+        let (kind, message) = match err {
+            JSError::Exception(value) => return value,
+            JSError::TypeError(msg) => (ErrorKind::TypeError, msg),
+            JSError::RangeError(msg) => (ErrorKind::RangeError, msg),
+            JSError::SyntaxError(msg) => (ErrorKind::SyntaxError, msg),
+            JSError::ReferenceError(msg) => (ErrorKind::ReferenceError, msg),
+            JSError::InternalError(msg) => (ErrorKind::Error, msg),
+        };
+        self.builtins.create_error(kind, &message)
+    }
+
+    /// 捕捉されなかった例外をホストに返すエラーに変換
+    ///
+    /// 組み込みのエラーオブジェクトは name に対応する JSError の種類にする。
+    fn uncaught_error(err: JSError) -> JSError {
+        let JSError::Exception(JSValue::Object(obj)) = &err else {
+            return err;
+        };
+        let converted = {
+            let obj = obj.borrow();
+            let message = obj.get("message").to_console_string();
+            match obj.internal_slot() {
+                Some(InternalSlot::Error) => match obj.get("name").to_console_string().as_str() {
+                    "TypeError" => Some(JSError::TypeError(message)),
+                    "RangeError" => Some(JSError::RangeError(message)),
+                    "SyntaxError" => Some(JSError::SyntaxError(message)),
+                    "ReferenceError" => Some(JSError::ReferenceError(message)),
+                    _ => None,
+                },
+                _ => None,
+            }
+        };
+        converted.unwrap_or(err)
+    }
+
+    /// 定数プールから値を取得（壊れたチャンクでもパニックしない）
+    fn constant(chunk: &BytecodeChunk, idx: usize) -> JSResult<&JSValue> {
+        chunk
+            .constants
+            .get(idx)
+            .ok_or_else(|| JSError::InternalError(format!("Constant index {} out of range", idx)))
     }
 
     /// スタックから値をポップ
@@ -293,6 +1311,27 @@ impl VM {
             .ok_or_else(|| JSError::InternalError("Stack underflow".to_string()))
     }
 
+    /// スタックから呼び出し引数を取り出す（元の順序で返す）
+    fn pop_args(&mut self, arg_count: usize) -> JSResult<Vec<JSValue>> {
+        if self.stack.len() < arg_count {
+            return Err(JSError::InternalError("Stack underflow".to_string()));
+        }
+        let start = self.stack.len() - arg_count;
+        Ok(self.stack.split_off(start))
+    }
+
+    /// BuildSpreadArray で作った引数配列をポップして引数リストにする
+    fn pop_argument_array(&mut self) -> JSResult<Vec<JSValue>> {
+        let JSValue::Object(array) = self.pop()? else {
+            return Err(JSError::InternalError(
+                "argument list is not an array".to_string(),
+            ));
+        };
+        let array = array.borrow();
+        let length = array.get("length").to_number() as usize;
+        Ok((0..length).map(|i| array.get(&i.to_string())).collect())
+    }
+
     /// 二項演算ヘルパー
     fn binary_op<F>(&mut self, op: F) -> JSResult<()>
     where
@@ -300,11 +1339,33 @@ impl VM {
     {
         let b = self.pop()?;
         let a = self.pop()?;
+        let a = self.to_primitive(a, PreferredType::Default)?;
+        let b = self.to_primitive(b, PreferredType::Default)?;
         let result = op(a, b);
         self.stack.push(result);
         Ok(())
     }
 
+    /// 両辺が数値のときの算術演算の高速経路（ToPrimitive を経ずに op で計算する）
+    ///
+    /// 両辺が数値でなければ何もせず false を返す。
+    fn number_op(&mut self, op: impl FnOnce(f64, f64) -> f64) -> bool {
+        let [.., JSValue::Number(a), JSValue::Number(b)] = self.stack[..] else {
+            return false;
+        };
+        self.stack.pop();
+        *self.stack.last_mut().unwrap() = JSValue::Number(op(a, b));
+        true
+    }
+
+    /// 数値の算術演算（数値でない値は ToNumber で変換する）
+    fn arithmetic_op(&mut self, op: impl Fn(f64, f64) -> f64) -> JSResult<()> {
+        if self.number_op(&op) {
+            return Ok(());
+        }
+        self.binary_numeric_op(op)
+    }
+
     /// 数値二項演算ヘルパー
     fn binary_numeric_op<F>(&mut self, op: F) -> JSResult<()>
     where
@@ -312,11 +1373,59 @@ impl VM {
     {
         let b = self.pop()?;
         let a = self.pop()?;
-        let result = op(a.to_number(), b.to_number());
+        let result = op(self.to_number(a)?, self.to_number(b)?);
         self.stack.push(JSValue::Number(result));
         Ok(())
     }
 
+    /// value instanceof constructor（OrdinaryHasInstance 抽象操作）
+    ///
+    /// constructor.prototype が value のプロトタイプチェーン上にあるかを判定する。
+    fn instance_of(&mut self, value: &JSValue, constructor: &JSValue) -> JSResult<bool> {
+        if !constructor.is_callable() {
+            return Err(JSError::TypeError(
+                "Right-hand side of 'instanceof' is not callable".to_string(),
+            ));
+        }
+        let JSValue::Object(object) = value else {
+            return Ok(false);
+        };
+        let JSValue::Object(prototype) = self.get_property(constructor, "prototype")? else {
+            return Err(JSError::TypeError(
+                "Function has non-object prototype in instanceof check".to_string(),
+            ));
+        };
+        let mut current = object.borrow().get_prototype();
+        while let Some(obj) = current {
+            if Rc::ptr_eq(&obj, &prototype) {
+                return Ok(true);
+            }
+            current = obj.borrow().get_prototype();
+        }
+        Ok(false)
+    }
+
+    /// スタックの2値を == で比較（IsLooselyEqual 抽象操作）
+    ///
+    /// オブジェクトとプリミティブの比較ではオブジェクトを ToPrimitive で変換する。
+    fn loose_equals(&mut self) -> JSResult<bool> {
+        let b = self.pop()?;
+        let a = self.pop()?;
+        let is_primitive =
+            |v: &JSValue| !matches!(v, JSValue::Object(_) | JSValue::Undefined | JSValue::Null);
+        let (a, b) = match (&a, &b) {
+            (JSValue::Object(_), other) if is_primitive(other) => {
+                (self.to_primitive(a, PreferredType::Default)?, b)
+            }
+            (other, JSValue::Object(_)) if is_primitive(other) => {
+                let b = self.to_primitive(b, PreferredType::Default)?;
+                (a, b)
+            }
+            _ => (a, b),
+        };
+        Ok(a.abstract_equals(&b))
+    }
+
     /// 比較演算ヘルパー
     fn comparison_op<F>(&mut self, op: F) -> JSResult<()>
     where
@@ -329,14 +1438,30 @@ impl VM {
         Ok(())
     }
 
-    /// 数値比較演算ヘルパー
-    fn numeric_comparison_op<F>(&mut self, op: F) -> JSResult<()>
+    /// 関係演算ヘルパー（IsLessThan 抽象操作）
+    ///
+    /// 両辺が文字列なら UTF-16 コード単位で辞書順に比較し、それ以外は数値で比較する。
+    /// NaN を含む比較は順序が定まらないため、どの演算子でも false になる。
+    fn relational_op<F>(&mut self, op: F) -> JSResult<()>
     where
-        F: FnOnce(f64, f64) -> bool,
+        F: FnOnce(Ordering) -> bool,
     {
+        if let [.., JSValue::Number(a), JSValue::Number(b)] = self.stack[..] {
+            self.stack.pop();
+            *self.stack.last_mut().unwrap() = JSValue::Boolean(a.partial_cmp(&b).is_some_and(op));
+            return Ok(());
+        }
         let b = self.pop()?;
         let a = self.pop()?;
-        let result = op(a.to_number(), b.to_number());
+        let a = self.to_primitive(a, PreferredType::Number)?;
+        let b = self.to_primitive(b, PreferredType::Number)?;
+        let ordering = match (&a, &b) {
+            (JSValue::String(x), JSValue::String(y)) => {
+                Some(x.encode_utf16().cmp(y.encode_utf16()))
+            }
+            _ => a.to_number().partial_cmp(&b.to_number()),
+        };
+        let result = ordering.is_some_and(op);
         self.stack.push(JSValue::Boolean(result));
         Ok(())
     }
@@ -348,12 +1473,36 @@ impl VM {
     {
         let b = self.pop()?;
         let a = self.pop()?;
-        let result = op(a.to_number() as i32, b.to_number() as i32);
+        let result = op(self.to_int32(a)?, self.to_int32(b)?);
         self.stack.push(JSValue::Number(result as f64));
         Ok(())
     }
 }
 
+/// システム時計による現在時刻（Unix エポックからのミリ秒）
+#[cfg(feature = "clock")]
+fn system_clock() -> f64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as f64)
+        .unwrap_or(0.0)
+}
+
+/// システム時計を使わない構成では、埋め込み側が set_clock で差し替えるまでエポックを返す
+#[cfg(not(feature = "clock"))]
+fn system_clock() -> f64 {
+    0.0
+}
+
+/// console.log の既定の出力先（stdout 機能が無効なら出力を捨てる）
+fn default_printer() -> Printer {
+    if cfg!(feature = "stdout") {
+        Box::new(|line| println!("{}", line))
+    } else {
+        Box::new(|_| {})
+    }
+}
+
 impl Default for VM {
     /// デフォルト実装
     fn default() -> Self {
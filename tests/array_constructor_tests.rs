@@ -0,0 +1,55 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_new_array_with_length() {
+    let mut engine = JSEngine::new();
+
+    assert_eq!(
+        engine.eval("new Array(3).length").unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 穴は undefined として読める
+    assert_eq!(engine.eval("new Array(3)[1]").unwrap(), JSValue::Undefined);
+    assert_eq!(
+        engine.eval("1 in new Array(3)").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("new Array(0).length").unwrap(),
+        JSValue::Number(0.0)
+    );
+}
+
+#[test]
+fn test_array_with_elements() {
+    let mut engine = JSEngine::new();
+
+    assert_eq!(
+        engine.eval("Array(1, 2).length").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine.eval("new Array(1, 2, 3)[2]").unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 数値以外の単一引数は要素になる
+    assert_eq!(
+        engine.eval(r#"Array("3").length"#).unwrap(),
+        JSValue::Number(1.0)
+    );
+    assert_eq!(engine.eval("Array().length").unwrap(), JSValue::Number(0.0));
+}
+
+#[test]
+fn test_array_invalid_length_is_range_error() {
+    let mut engine = JSEngine::new();
+
+    assert!(matches!(
+        engine.eval("new Array(-1)"),
+        Err(JSError::RangeError(_))
+    ));
+    assert!(matches!(
+        engine.eval("Array(1.5)"),
+        Err(JSError::RangeError(_))
+    ));
+}
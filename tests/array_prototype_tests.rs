@@ -0,0 +1,383 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+/// 式を評価して inspect 表示を返す
+fn eval_inspect(engine: &mut JSEngine, source: &str) -> String {
+    engine.eval(source).unwrap().inspect()
+}
+
+#[test]
+fn test_sort_default_is_string_order() {
+    let mut engine = JSEngine::new();
+    assert_eq!(eval_inspect(&mut engine, "[3, 1, 2].sort()"), "[ 1, 2, 3 ]");
+    assert_eq!(
+        eval_inspect(&mut engine, "[10, 9, 2].sort()"),
+        "[ 10, 2, 9 ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, r#"["b", undefined, "a", "B"].sort()"#),
+        "[ 'B', 'a', 'b', undefined ]"
+    );
+    // オブジェクトは ToString の結果で比較する
+    assert_eq!(
+        eval_inspect(&mut engine, "[[10], [9], [1]].sort()"),
+        "[ [ 1 ], [ 10 ], [ 9 ] ]"
+    );
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            r#"[{ toString() { return "b"; } }, { toString() { return "a"; } }]
+                .sort()
+                .map(function (o) { return String(o); })"#
+        ),
+        "[ 'a', 'b' ]"
+    );
+    // 変換中のエラーは伝播する
+    assert!(matches!(
+        engine.eval(r#"[1, { toString() { throw "boom"; } }].sort()"#),
+        Err(JSError::Exception(JSValue::String(_)))
+    ));
+}
+
+#[test]
+fn test_sort_with_comparator() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "[10, 9, 2].sort(function (a, b) { return a - b; })"
+        ),
+        "[ 2, 9, 10 ]"
+    );
+
+    // 安定ソート: 比較結果が等しい要素は元の順序を保つ
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            r#"[{ k: 1, v: "a" }, { k: 0, v: "b" }, { k: 1, v: "c" }, { k: 0, v: "d" }]
+                .sort(function (x, y) { return x.k - y.k; })"#
+        ),
+        "[ { k: 0, v: 'b' }, { k: 0, v: 'd' }, { k: 1, v: 'a' }, { k: 1, v: 'c' } ]"
+    );
+}
+
+#[test]
+fn test_sort_in_place_returns_same_array() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("let a = [2, 1]; let b = a.sort(); a === b")
+            .unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(eval_inspect(&mut engine, "a"), "[ 1, 2 ]");
+}
+
+#[test]
+fn test_sort_comparator_errors() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(
+        engine.eval("[1, 2].sort(1)"),
+        Err(JSError::TypeError(_))
+    ));
+    assert!(matches!(
+        engine.eval(r#"[1, 2].sort(function () { throw "boom"; })"#),
+        Err(JSError::Exception(JSValue::String(_)))
+    ));
+}
+
+#[test]
+fn test_reverse_in_place() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, 2, 3].reverse()"),
+        "[ 3, 2, 1 ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "let a = [1, 2, 3, 4]; a.reverse(); a"),
+        "[ 4, 3, 2, 1 ]"
+    );
+    assert_eq!(eval_inspect(&mut engine, "[].reverse()"), "[]");
+}
+
+#[test]
+fn test_fill_with_range() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, 2, 3].fill(0, 1)"),
+        "[ 1, 0, 0 ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, 2, 3].fill(9)"),
+        "[ 9, 9, 9 ]"
+    );
+    // 負のインデックスは末尾から数える
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, 2, 3, 4].fill(0, -3, -1)"),
+        "[ 1, 0, 0, 4 ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "new Array(3).fill(7)"),
+        "[ 7, 7, 7 ]"
+    );
+}
+
+#[test]
+fn test_flat_with_depth() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, [2, [3]]].flat()"),
+        "[ 1, 2, [ 3 ] ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, [2, [3, [4]]]].flat(2)"),
+        "[ 1, 2, 3, [ 4 ] ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, [2]].flat(0)"),
+        "[ 1, [ 2 ] ]"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "[1, [2, [3, [4]]]].flat(Infinity)"),
+        "[ 1, 2, 3, 4 ]"
+    );
+    // 元の配列は変更されない
+    assert_eq!(
+        eval_inspect(&mut engine, "let a = [[1], 2]; a.flat(); a"),
+        "[ [ 1 ], 2 ]"
+    );
+}
+
+#[test]
+fn test_flat_cyclic_array_is_range_error() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(
+        engine.eval("let a = [1]; a.push(a); a.flat(Infinity)"),
+        Err(JSError::RangeError(_))
+    ));
+    // 展開しない段にある循環参照はそのまま要素になる
+    assert_eq!(
+        engine
+            .eval("let b = [1]; b.push(b); b.flat(0)[1] === b")
+            .unwrap(),
+        JSValue::Boolean(true)
+    );
+}
+
+#[test]
+fn test_push_and_pop() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("let a = [1, 2]; let n = a.push(3); n").unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+    assert_eq!(engine.eval("a.length").unwrap(), JSValue::Number(3.0));
+    assert_eq!(engine.eval("a.push(4, 5)").unwrap(), JSValue::Number(5.0));
+    assert_eq!(eval_inspect(&mut engine, "a"), "[ 1, 2, 3, 4, 5 ]");
+
+    assert_eq!(engine.eval("a.pop()").unwrap(), JSValue::Number(5.0));
+    assert_eq!(engine.eval("a.length").unwrap(), JSValue::Number(4.0));
+    assert_eq!(engine.eval("[].pop()").unwrap(), JSValue::Undefined);
+}
+
+#[test]
+fn test_shift_and_unshift() {
+    let mut engine = JSEngine::new();
+    engine.eval("let a = [1, 2, 3];").unwrap();
+    assert_eq!(engine.eval("a.shift()").unwrap(), JSValue::Number(1.0));
+    assert_eq!(eval_inspect(&mut engine, "a"), "[ 2, 3 ]");
+
+    assert_eq!(
+        engine.eval("a.unshift(0, 1)").unwrap(),
+        JSValue::Number(4.0)
+    );
+    assert_eq!(eval_inspect(&mut engine, "a"), "[ 0, 1, 2, 3 ]");
+    assert_eq!(engine.eval("[].shift()").unwrap(), JSValue::Undefined);
+
+    // 穴は穴のまま移動する
+    engine
+        .eval("let h = []; h[0] = 1; h[2] = 3; h.unshift(0);")
+        .unwrap();
+    assert_eq!(engine.eval("2 in h").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("h[3]").unwrap(), JSValue::Number(3.0));
+    engine.eval("h.shift();").unwrap();
+    assert_eq!(engine.eval("1 in h").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("h.length").unwrap(), JSValue::Number(3.0));
+}
+
+#[test]
+fn test_splice() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(r#"let a = [1, 2, 3, 4]; let removed = a.splice(1, 2, "x");"#)
+        .unwrap();
+    assert_eq!(eval_inspect(&mut engine, "a"), "[ 1, 'x', 4 ]");
+    assert_eq!(eval_inspect(&mut engine, "removed"), "[ 2, 3 ]");
+
+    // 挿入のみ・負の start・deleteCount の省略と丸め
+    engine.eval("let b = [1, 2, 3];").unwrap();
+    assert_eq!(eval_inspect(&mut engine, "b.splice(1, 0, 'p', 'q')"), "[]");
+    assert_eq!(eval_inspect(&mut engine, "b"), "[ 1, 'p', 'q', 2, 3 ]");
+    assert_eq!(eval_inspect(&mut engine, "b.splice(-2)"), "[ 2, 3 ]");
+    assert_eq!(eval_inspect(&mut engine, "b"), "[ 1, 'p', 'q' ]");
+    assert_eq!(eval_inspect(&mut engine, "b.splice(1, 99)"), "[ 'p', 'q' ]");
+    assert_eq!(eval_inspect(&mut engine, "b.splice(0, -1, 0)"), "[]");
+    assert_eq!(eval_inspect(&mut engine, "b"), "[ 0, 1 ]");
+    assert_eq!(eval_inspect(&mut engine, "b.splice()"), "[]");
+    assert_eq!(engine.eval("b.length").unwrap(), JSValue::Number(2.0));
+
+    // 穴は穴のまま移動し、取り除いた穴は結果でも穴になる
+    engine
+        .eval("let h = []; h[1] = 1; h[3] = 3; let r = h.splice(0, 2, 'a');")
+        .unwrap();
+    assert_eq!(engine.eval("0 in r").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("r[1]").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("1 in h").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("h[2]").unwrap(), JSValue::Number(3.0));
+    assert_eq!(engine.eval("h.length").unwrap(), JSValue::Number(3.0));
+}
+
+#[test]
+fn test_filter_and_map() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "[1, 2, 3, 4].filter(function (x, i) { return x > 2 || i === 0; })"
+        ),
+        "[ 1, 3, 4 ]"
+    );
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "[1, 2, 3].map(function (x, i, a) { return x * 10 + i + a.length; })"
+        ),
+        "[ 13, 24, 35 ]"
+    );
+
+    // filter は穴を飛ばし、map は穴を穴のまま残す
+    engine
+        .eval("let calls = 0; let h = []; h[1] = 1; h[3] = 3;")
+        .unwrap();
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "h.filter(function () { calls = calls + 1; return true; })"
+        ),
+        "[ 1, 3 ]"
+    );
+    engine
+        .eval("let m = h.map(function (x) { calls = calls + 1; return x + 1; });")
+        .unwrap();
+    assert_eq!(engine.eval("calls").unwrap(), JSValue::Number(4.0));
+    assert_eq!(engine.eval("0 in m").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("m[3]").unwrap(), JSValue::Number(4.0));
+    assert_eq!(engine.eval("m.length").unwrap(), JSValue::Number(4.0));
+
+    assert!(matches!(
+        engine.eval("[1].map(1)"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_flat_map() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "[1, 2].flatMap(function (x) { return [x, x * 10]; })"
+        ),
+        "[ 1, 10, 2, 20 ]"
+    );
+    // 配列でない結果はそのまま、配列は1段だけ展開する
+    assert_eq!(
+        eval_inspect(
+            &mut engine,
+            "[1, 2, 3].flatMap(function (x, i) { return x === 2 ? [[x]] : i; })"
+        ),
+        "[ 0, [ 2 ], 2 ]"
+    );
+    assert!(matches!(
+        engine.eval("[1].flatMap()"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_keys_values_and_entries() {
+    let mut engine = JSEngine::new();
+    engine.eval(r#"let it = ["a", "b"].entries();"#).unwrap();
+    assert_eq!(
+        eval_inspect(&mut engine, "it.next()"),
+        "{ value: [ 0, 'a' ], done: false }"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "it.next()"),
+        "{ value: [ 1, 'b' ], done: false }"
+    );
+    assert_eq!(
+        eval_inspect(&mut engine, "it.next()"),
+        "{ value: undefined, done: true }"
+    );
+    // 走査し終えたイテレータは要素を追加しても再開しない
+    assert_eq!(
+        eval_inspect(&mut engine, "it.next()"),
+        "{ value: undefined, done: true }"
+    );
+
+    assert_eq!(
+        engine
+            .eval("let s = ''; for (const k of [7, 8, 9].keys()) { s = s + k; } s")
+            .unwrap(),
+        JSValue::String("012".to_string())
+    );
+    assert_eq!(
+        engine
+            .eval("let t = 0; for (const v of [7, 8, 9].values()) { t = t + v; } t")
+            .unwrap(),
+        JSValue::Number(24.0)
+    );
+}
+
+#[test]
+fn test_join_and_to_string() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("[1, 2, 3].join()", "1,2,3"),
+        ("[1, 2, 3].join(' - ')", "1 - 2 - 3"),
+        (
+            "[1, [2, [3]], null, undefined, , 'x'].join(';')",
+            "1;2,3;;;;x",
+        ),
+        ("[].join()", ""),
+        ("[1, 2].toString()", "1,2"),
+        ("'' + [1, 2]", "1,2"),
+        ("let a = [1]; a.push(a); a.join()", "1,"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::String(expected.to_string()),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_at() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("[1, 2, 3].at(-1)").unwrap(),
+        JSValue::Number(3.0)
+    );
+    assert_eq!(
+        engine.eval("[1, 2, 3].at(0)").unwrap(),
+        JSValue::Number(1.0)
+    );
+    assert_eq!(
+        engine.eval("[1, 2, 3].at(1.7)").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(engine.eval("[1, 2, 3].at()").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("[1, 2, 3].at(5)").unwrap(), JSValue::Undefined);
+    assert_eq!(engine.eval("[1, 2, 3].at(-4)").unwrap(), JSValue::Undefined);
+}
@@ -0,0 +1,89 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_every_callable_kind_is_function() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(
+            r#"
+        function declared() {}
+        let expression = function () {};
+        let object = { method() { return 1; } };
+        let array = [];
+    "#,
+        )
+        .unwrap();
+
+    // バイトコード関数・ネイティブ関数・オブジェクトから取り出したメソッド
+    for source in [
+        "declared",
+        "expression",
+        "object.method",
+        "Math.random",
+        "Array",
+        "array.sort",
+        r#""s".trim"#,
+    ] {
+        let value = engine.eval(source).unwrap();
+        assert!(value.is_callable(), "{}", source);
+        assert!(value.to_boolean(), "{}", source);
+        assert_eq!(
+            engine.eval(&format!("typeof {}", source)).unwrap(),
+            JSValue::String("function".to_string()),
+            "{}",
+            source
+        );
+    }
+
+    for source in ["({})", "[]", "null", "1", r#""f""#] {
+        assert!(!engine.eval(source).unwrap().is_callable(), "{}", source);
+    }
+}
+
+#[test]
+fn test_calling_non_callable_is_type_error() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(
+        engine.eval("let x = 1; x()"),
+        Err(JSError::TypeError(_))
+    ));
+    assert!(matches!(
+        engine.eval("({}).missing()"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_instanceof() {
+    let mut engine = JSEngine::new();
+    let t = JSValue::Boolean(true);
+    let f = JSValue::Boolean(false);
+    assert_eq!(
+        engine.eval("new TypeError() instanceof TypeError").unwrap(),
+        t
+    );
+    assert_eq!(engine.eval("new TypeError() instanceof Error").unwrap(), t);
+    assert_eq!(engine.eval("new Error() instanceof TypeError").unwrap(), f);
+    assert_eq!(engine.eval("[] instanceof Array").unwrap(), t);
+    assert_eq!(engine.eval("new Map() instanceof Set").unwrap(), f);
+    // プリミティブは常に false
+    assert_eq!(engine.eval(r#""s" instanceof String"#).unwrap(), f);
+
+    // ユーザー定義のコンストラクタは prototype を継承したオブジェクトを作成する
+    engine
+        .eval("function F() { this.a = 1; } F.prototype.b = 2; F.x = 3; let obj = new F();")
+        .unwrap();
+    assert_eq!(engine.eval("obj instanceof F").unwrap(), t);
+    assert_eq!(engine.eval("obj instanceof Object").unwrap(), t);
+    assert_eq!(engine.eval("({}) instanceof F").unwrap(), f);
+    assert_eq!(engine.eval("F.prototype.constructor === F").unwrap(), t);
+    assert_eq!(
+        engine.eval("obj.a + obj.b + F.x").unwrap(),
+        JSValue::Number(6.0)
+    );
+
+    assert!(matches!(
+        engine.eval("[] instanceof {}"),
+        Err(JSError::TypeError(_))
+    ));
+}
@@ -0,0 +1,51 @@
+use pixi_byte::compiler::{BytecodeChunk, CompileCache};
+use pixi_byte::{JSEngine, JSError, JSValue};
+use std::rc::Rc;
+
+#[test]
+fn test_same_source_compiles_once() {
+    let mut engine = JSEngine::new().with_compile_cache(4);
+    engine.eval("var count = 0;").unwrap();
+    assert_eq!(engine.compile_count(), 1);
+
+    let first = engine.eval("count = count + 1; count * 10").unwrap();
+    let second = engine.eval("count = count + 1; count * 10").unwrap();
+    assert_eq!(first, JSValue::Number(10.0));
+    assert_eq!(second, JSValue::Number(20.0));
+    assert_eq!(engine.compile_count(), 2);
+
+    // キャッシュなしでは毎回コンパイルする
+    let mut engine = JSEngine::new();
+    engine.eval("1 + 2").unwrap();
+    engine.eval("1 + 2").unwrap();
+    assert_eq!(engine.compile_count(), 2);
+}
+
+#[test]
+fn test_compile_errors_are_not_cached() {
+    let mut engine = JSEngine::new().with_compile_cache(4);
+    for _ in 0..2 {
+        assert!(matches!(engine.eval("1 +"), Err(JSError::SyntaxError(_))));
+    }
+    assert_eq!(engine.compile_count(), 0);
+}
+
+#[test]
+fn test_cache_evicts_least_recently_used() {
+    let mut cache = CompileCache::new(2);
+    let chunk = || Rc::new(BytecodeChunk::new());
+    cache.insert("a", chunk());
+    cache.insert("b", chunk());
+    // a を使うと b が最も古くなる
+    assert!(cache.get("a").is_some());
+    cache.insert("c", chunk());
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("b").is_none());
+    assert!(cache.get("a").is_some());
+    assert!(cache.get("c").is_some());
+
+    let mut disabled = CompileCache::new(0);
+    disabled.insert("a", chunk());
+    assert!(disabled.is_empty());
+}
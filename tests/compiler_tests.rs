@@ -1,4 +1,4 @@
-use pixi_byte::{Compiler, Lexer, Opcode, Parser};
+use pixi_byte::{Compiler, JSEngine, JSError, JSValue, Lexer, Opcode, Parser};
 
 #[test]
 fn test_compile_literal() {
@@ -25,3 +25,383 @@ fn test_compile_binary_expr() {
 
     assert!(chunk.code.contains(&Opcode::Add));
 }
+
+#[test]
+fn test_compile_array_literal_uses_build_array() {
+    let tokens = Lexer::new("[1, 2, 3]").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let loads = chunk.code[..3]
+        .iter()
+        .map(|op| match op {
+            Opcode::LoadConst(idx) => chunk.constants[*idx].clone(),
+            other => panic!("Expected LoadConst, got {:?}", other),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        loads,
+        [
+            JSValue::Number(1.0),
+            JSValue::Number(2.0),
+            JSValue::Number(3.0)
+        ]
+    );
+    assert_eq!(chunk.code[3..], [Opcode::BuildArray(3)]);
+}
+
+#[test]
+fn test_build_array_evaluates_elements_in_order() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("[1, 2, 3]").unwrap();
+
+    let expected = engine
+        .eval("let a = []; a[0] = 1; a[1] = 2; a[2] = 3; a")
+        .unwrap();
+    assert!(result.deep_equals(&expected));
+    assert_eq!(engine.eval("[1, 2, 3][2]").unwrap(), JSValue::Number(3.0));
+    assert_eq!(
+        engine.eval("[1, 2, 3].length").unwrap(),
+        JSValue::Number(3.0)
+    );
+}
+
+#[test]
+fn test_compile_object_literal_uses_build_object() {
+    let tokens = Lexer::new("({ a: 1, b: 2 })").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    assert_eq!(chunk.code.last(), Some(&Opcode::BuildObject(2)));
+    assert_eq!(chunk.code.len(), 5);
+    assert!(!chunk.code.contains(&Opcode::ObjectSetProperty));
+
+    let mut engine = JSEngine::new();
+    let result = engine.eval("let o = { a: 1, b: 2 }; o.a + o.b").unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+    // 重複したキーは後の値で上書きされる
+    let result = engine.eval("({ a: 1, b: 2, a: 3 }).a").unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+}
+
+#[test]
+fn test_add_constant_dedup_rules() {
+    use pixi_byte::compiler::BytecodeChunk;
+    use pixi_byte::value::JSFunction;
+    use std::rc::Rc;
+
+    let mut chunk = BytecodeChunk::new();
+
+    // 数値・文字列は重複排除される
+    let a = chunk.add_constant(JSValue::Number(1.5));
+    let b = chunk.add_constant(JSValue::Number(1.5));
+    assert_eq!(a, b);
+    let s1 = chunk.add_constant(JSValue::String("x".to_string()));
+    let s2 = chunk.add_constant(JSValue::String("x".to_string()));
+    assert_eq!(s1, s2);
+    // 0 と -0 は別の定数
+    let zero = chunk.add_constant(JSValue::Number(0.0));
+    let neg_zero = chunk.add_constant(JSValue::Number(-0.0));
+    assert_ne!(zero, neg_zero);
+    // NaN は === では自身と等しくないが、定数としては同じものを使い回す
+    let nan1 = chunk.add_constant(JSValue::Number(f64::NAN));
+    let nan2 = chunk.add_constant(JSValue::Number(f64::NAN));
+    assert_eq!(nan1, nan2);
+    assert!(chunk.constants[nan1].as_number().unwrap().is_nan());
+    // 文字列の "NaN" とは区別する
+    let nan_string = chunk.add_constant(JSValue::String("NaN".to_string()));
+    assert_ne!(nan1, nan_string);
+
+    // 関数テンプレートは常に追加される
+    let template = || JSValue::Function(Rc::new(JSFunction::new(BytecodeChunk::new(), vec![])));
+    let f1 = chunk.add_constant(template());
+    let f2 = chunk.add_constant(template());
+    assert_ne!(f1, f2);
+    assert_eq!(chunk.constants.len(), 8);
+}
+
+#[test]
+fn test_compile_literals_without_constant_pool() {
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap()
+    };
+
+    let chunk = compile("true");
+    assert_eq!(chunk.code, [Opcode::LoadBool(true)]);
+    assert!(chunk.constants.is_empty());
+
+    let chunk = compile("[undefined, null, false]");
+    assert_eq!(
+        chunk.code,
+        [
+            Opcode::LoadUndefined,
+            Opcode::LoadNull,
+            Opcode::LoadBool(false),
+            Opcode::BuildArray(3),
+        ]
+    );
+    assert!(chunk.constants.is_empty());
+
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("true").unwrap(), JSValue::Boolean(true));
+    assert_eq!(engine.eval("null").unwrap(), JSValue::Null);
+    assert_eq!(engine.eval("undefined").unwrap(), JSValue::Undefined);
+}
+
+#[test]
+fn test_compile_named_property_access() {
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap()
+    };
+
+    let chunk = compile("obj.x");
+    assert_eq!(
+        chunk.code,
+        [
+            Opcode::LoadVar("obj".to_string()),
+            Opcode::GetPropertyByName("x".to_string()),
+        ]
+    );
+    assert!(chunk.constants.is_empty());
+
+    let chunk = compile("obj.x = 1");
+    assert!(
+        chunk
+            .code
+            .contains(&Opcode::SetPropertyByName("x".to_string()))
+    );
+    assert!(!chunk.code.contains(&Opcode::SetProperty));
+
+    // 計算されたキーは汎用の命令のまま
+    let chunk = compile(r#"obj["x"]; obj["x"] = 1"#);
+    assert!(chunk.code.contains(&Opcode::GetProperty));
+    assert!(chunk.code.contains(&Opcode::SetProperty));
+
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("let obj = { x: 1 }; obj.x = obj.x + 1; obj.x")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(2.0));
+}
+
+#[test]
+fn test_compile_conditional_branches_leave_one_value() {
+    let tokens = Lexer::new("c ? a : b").tokenize().unwrap();
+    let chunk = Compiler::new()
+        .compile(Parser::new(tokens).parse().unwrap())
+        .unwrap();
+
+    // 分岐の式の後に Pop を出さない
+    assert_eq!(
+        chunk.code,
+        [
+            Opcode::LoadVar("c".to_string()),
+            Opcode::JumpIfFalse(4),
+            Opcode::LoadVar("a".to_string()),
+            Opcode::Jump(5),
+            Opcode::LoadVar("b".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_compile_tail_calls() {
+    let function_code = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        let chunk = Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap();
+        match &chunk.constants[0] {
+            JSValue::Function(f) => f.chunk.code.clone(),
+            other => panic!("Expected function template, got {:?}", other),
+        }
+    };
+
+    let code = function_code("function f(n) { return n ? g(n) : h(); }");
+    assert_eq!(
+        code.iter()
+            .filter(|op| matches!(op, Opcode::TailCall(_)))
+            .count(),
+        2
+    );
+    assert!(!code.iter().any(|op| matches!(op, Opcode::CallFunction(_))));
+
+    // 演算の途中・メソッド呼び出し・try 本体の呼び出しは末尾呼び出しにしない
+    for source in [
+        "function f(n) { return 1 + g(n); }",
+        "function f(o) { return o.m(); }",
+        "function f() { try { return g(); } catch (e) {} }",
+    ] {
+        let code = function_code(source);
+        assert!(
+            !code.iter().any(|op| matches!(op, Opcode::TailCall(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_compile_logical_operators_jump_over_right_operand() {
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap()
+            .code
+    };
+
+    assert_eq!(
+        compile("a && b"),
+        [
+            Opcode::LoadVar("a".to_string()),
+            Opcode::Dup,
+            Opcode::JumpIfFalse(5),
+            Opcode::Pop,
+            Opcode::LoadVar("b".to_string()),
+        ]
+    );
+    assert_eq!(
+        compile("a || b"),
+        [
+            Opcode::LoadVar("a".to_string()),
+            Opcode::Dup,
+            Opcode::JumpIfTrue(5),
+            Opcode::Pop,
+            Opcode::LoadVar("b".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_validate_rejects_corrupted_chunk() {
+    use pixi_byte::JSError;
+
+    let tokens = Lexer::new("c ? [1] : b").tokenize().unwrap();
+    let chunk = Compiler::new()
+        .compile(Parser::new(tokens).parse().unwrap())
+        .unwrap();
+    assert!(chunk.validate().is_ok());
+
+    let expect_error = |code: Vec<Opcode>, expected: &str| {
+        let mut corrupted = chunk.clone();
+        corrupted.code = code;
+        match corrupted.validate() {
+            Err(JSError::InternalError(msg)) => assert!(msg.contains(expected), "{}", msg),
+            other => panic!("Expected InternalError, got {:?}", other),
+        }
+    };
+    expect_error(vec![Opcode::LoadConst(5)], "constant index out of bounds");
+    expect_error(
+        vec![Opcode::LoadBool(true), Opcode::JumpIfFalse(9)],
+        "jump target out of range",
+    );
+    expect_error(vec![Opcode::LoadNull, Opcode::Add], "stack underflow");
+    // 分岐の一方だけが値を残すと合流点で深さが一致しない
+    expect_error(
+        vec![
+            Opcode::LoadBool(true),
+            Opcode::JumpIfFalse(3),
+            Opcode::LoadNull,
+            Opcode::LoadNull,
+            Opcode::Pop,
+        ],
+        "does not match",
+    );
+}
+
+#[test]
+fn test_compile_spread_call_builds_argument_array() {
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        let program = Parser::new(tokens).parse().unwrap();
+        Compiler::new().compile(program).unwrap()
+    };
+
+    let chunk = compile("f(a, b)");
+    assert!(chunk.code.contains(&Opcode::CallFunction(2)));
+    assert!(!chunk.code.contains(&Opcode::CallFunctionSpread));
+
+    let chunk = compile("f(a, ...b)");
+    assert_eq!(
+        chunk.code[2..],
+        [
+            Opcode::LoadVar("a".to_string()),
+            Opcode::LoadVar("b".to_string()),
+            Opcode::BuildSpreadArray(vec![false, true]),
+            Opcode::CallFunctionSpread,
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_lexical_declaration_is_syntax_error() {
+    let mut engine = JSEngine::new();
+    for source in [
+        "let x = 1; let x = 2",
+        "const c = 1; let c = 2",
+        "let v; var v",
+        "function f() {} let f",
+        "{ let b; const b = 1; }",
+        "function g() { let x; let x; }",
+        "let n; { var n; }",
+        "{ var m; } const m = 1",
+        "let p; if (true) { while (false) { var p; } }",
+        "let q; for (var q of []) {}",
+        "{ let r; { var r; } }",
+        "function f(y) { let y = 1; }",
+        "let a = (w) => { const w = 1; }",
+        "try {} catch (e) { let e; }",
+    ] {
+        match engine.eval(source) {
+            Err(JSError::SyntaxError(message)) => {
+                assert!(message.contains("has already been declared"), "{}", source)
+            }
+            other => panic!("{}: expected SyntaxError, got {:?}", source, other),
+        }
+    }
+    assert_eq!(
+        engine.eval("var y = 1; var y = 2; y").unwrap(),
+        JSValue::Number(2.0)
+    );
+    // 関数本体の var は外側の let と衝突しない
+    assert_eq!(
+        engine
+            .eval("let k = 1; function h() { var k = 2; return k; } h() + k")
+            .unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 入れ子のブロックの let / const は自身のスコープに宣言され、外側の同名の束縛を隠す
+    assert_eq!(
+        engine.eval("let cc = 1; { let cc = 2; } cc").unwrap(),
+        JSValue::Number(1.0)
+    );
+    assert_eq!(
+        engine.eval("let t = 1; { const t = 2; } t = 3; t").unwrap(),
+        JSValue::Number(3.0)
+    );
+    assert_eq!(
+        engine
+            .eval(
+                "let s = 0; if (true) { let s = 5; } while (s < 2) { const s2 = s; s = s2 + 1; } s"
+            )
+            .unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine
+            .eval("function k(u) { { let u = 2; } return u; } k(1)")
+            .unwrap(),
+        JSValue::Number(1.0)
+    );
+    assert_eq!(
+        engine.eval("typeof s2").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+}
@@ -0,0 +1,42 @@
+use pixi_byte::{JSEngine, JSValue};
+
+#[test]
+fn test_date_now_with_injected_clock() {
+    let mut engine = JSEngine::new();
+    engine.set_clock(|| 1_700_000_000_000.0);
+    let result = engine.eval("Date.now()").unwrap();
+    assert_eq!(result, JSValue::Number(1_700_000_000_000.0));
+}
+
+#[test]
+fn test_date_get_time() {
+    let mut engine = JSEngine::new();
+    engine.set_clock(|| 42.0);
+    let result = engine.eval("let d = new Date(); d.getTime()").unwrap();
+    assert_eq!(result, JSValue::Number(42.0));
+
+    let result = engine.eval("new Date(1000).getTime()").unwrap();
+    assert_eq!(result, JSValue::Number(1000.0));
+}
+
+#[test]
+#[cfg(feature = "clock")]
+fn test_date_now_uses_system_clock_by_default() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("Date.now()").unwrap();
+    assert!(result.to_number() > 0.0);
+}
+
+#[test]
+fn test_get_time_requires_date() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("let o = { getTime: Date.prototype.getTime }; o.getTime()");
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_typeof_date() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("typeof Date").unwrap();
+    assert_eq!(result, JSValue::String("function".to_string()));
+}
@@ -0,0 +1,97 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_caught_error_name_and_message() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(r#"try { throw new TypeError("x"); } catch (e) { e.name + ":" + e.message }"#)
+        .unwrap();
+    assert_eq!(result, JSValue::String("TypeError:x".to_string()));
+}
+
+#[test]
+fn test_error_to_string() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval(r#"new RangeError("too big").toString()"#)
+            .unwrap(),
+        JSValue::String("RangeError: too big".to_string())
+    );
+    assert_eq!(
+        engine.eval("new Error().toString()").unwrap(),
+        JSValue::String("Error".to_string())
+    );
+    // new なしでも作成できる
+    assert_eq!(
+        engine.eval(r#"Error("a") + """#).unwrap(),
+        JSValue::String("Error: a".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#"new SyntaxError("s")"#).unwrap().inspect(),
+        "[SyntaxError: s]"
+    );
+}
+
+#[test]
+fn test_uncaught_error_maps_to_js_error() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(
+        engine.eval(r#"throw new TypeError("bad")"#),
+        Err(JSError::TypeError(msg)) if msg == "bad"
+    ));
+    assert!(matches!(
+        engine.eval(r#"throw new RangeError("r")"#),
+        Err(JSError::RangeError(_))
+    ));
+    assert!(matches!(
+        engine.eval(r#"throw new ReferenceError("r")"#),
+        Err(JSError::ReferenceError(_))
+    ));
+    // 汎用の Error は値のまま返す
+    assert!(matches!(
+        engine.eval(r#"throw new Error("e")"#),
+        Err(JSError::Exception(JSValue::Object(_)))
+    ));
+}
+
+#[test]
+fn test_uncaught_error_object_displays_name_and_message() {
+    let mut engine = JSEngine::new();
+    let err = engine.eval(r#"throw new Error("plain")"#).unwrap_err();
+    assert_eq!(err.to_string(), "Uncaught Error: plain");
+    let err = engine.eval("throw new Error()").unwrap_err();
+    assert_eq!(err.to_string(), "Uncaught Error");
+    let err = engine
+        .eval(
+            r#"
+            let e = Object.create(Error.prototype);
+            e.name = "ValidationError";
+            e.message = "invalid input";
+            throw e;
+            "#,
+        )
+        .unwrap_err();
+    assert_eq!(err.to_string(), "Uncaught ValidationError: invalid input");
+    // エラー風でない値はそのまま表示する
+    let err = engine.eval("throw 42").unwrap_err();
+    assert_eq!(err.to_string(), "Uncaught 42");
+}
+
+#[test]
+fn test_internal_errors_are_caught_as_error_objects() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(r#"try { null.x = 1; } catch (e) { e.name + ": " + e.message }"#)
+        .unwrap();
+    assert_eq!(
+        result,
+        JSValue::String("TypeError: Cannot set property on non-object".to_string())
+    );
+    assert_eq!(
+        engine
+            .eval("try { new Array(-1); } catch (e) { e.toString() }")
+            .unwrap(),
+        JSValue::String("RangeError: Invalid array length".to_string())
+    );
+}
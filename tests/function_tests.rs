@@ -1,4 +1,4 @@
-use pixi_byte::{JSEngine, JSValue};
+use pixi_byte::{JSEngine, JSError, JSValue};
 
 #[test]
 fn test_simple_function() {
@@ -27,3 +27,622 @@ fn test_function_with_args() {
         .unwrap();
     assert_eq!(result, JSValue::Number(5.0));
 }
+
+#[test]
+fn test_trailing_commas() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function g(a, b,) { return a + b; }
+        g(1, 2,);
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+}
+
+#[test]
+fn test_function_reads_globals() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        let base = 10;
+        function addBase(n) { return base + n; }
+        addBase(5);
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(15.0));
+}
+
+#[test]
+fn test_closure_captures_environment() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function make() {
+            let n = 10;
+            function get() { return n; }
+            return get;
+        }
+        let get = make();
+        get();
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(10.0));
+}
+
+#[test]
+fn test_function_without_return_yields_undefined() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("function f() { 5; } f();").unwrap();
+    assert_eq!(result, JSValue::Undefined);
+}
+
+#[test]
+fn test_engine_usable_after_function_throws() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("let x = 1; function fail(a) { let x = 2; throw a; }")
+        .unwrap();
+
+    assert!(engine.eval("1 + fail(3)").is_err());
+
+    // 関数内の環境やスタックが残っていないこと
+    assert_eq!(engine.eval("x").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("x + 1").unwrap(), JSValue::Number(2.0));
+}
+
+#[test]
+fn test_named_function_expression_recursion() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        let f = function fact(n) { return n <= 1 ? 1 : n * fact(n - 1); };
+        f(5)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(120.0));
+
+    // 関数式の名前は外側のスコープからは見えない
+    assert_eq!(
+        engine.eval("typeof fact").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+}
+
+#[test]
+fn test_anonymous_function_expression() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("let add = function(a, b) { return a + b; }; add(2, 3)")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(5.0));
+}
+
+#[test]
+fn test_conditional_expression() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("true ? 1 : 2").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("0 ? 1 : 2").unwrap(), JSValue::Number(2.0));
+    // 右結合
+    assert_eq!(
+        engine.eval("false ? 1 : null ? 2 : 3").unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 選ばれなかった側は評価されない
+    engine.eval("let hit = 0;").unwrap();
+    engine.eval("true ? 1 : (hit = 1)").unwrap();
+    assert_eq!(engine.eval("hit").unwrap(), JSValue::Number(0.0));
+}
+
+#[test]
+fn test_conditional_evaluates_only_chosen_branch() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(
+            r#"
+        var calls1 = 0;
+        var calls2 = 0;
+        function sideEffect1() { calls1 = calls1 + 1; return "one"; }
+        function sideEffect2() { calls2 = calls2 + 1; return "two"; }
+    "#,
+        )
+        .unwrap();
+
+    let result = engine.eval("true ? sideEffect1() : sideEffect2()").unwrap();
+    assert_eq!(result, JSValue::String("one".to_string()));
+    let result = engine
+        .eval("false ? sideEffect1() : sideEffect2()")
+        .unwrap();
+    assert_eq!(result, JSValue::String("two".to_string()));
+    assert_eq!(
+        engine.eval("calls1 * 10 + calls2").unwrap(),
+        JSValue::Number(11.0)
+    );
+
+    // 各分岐はちょうど1つの値を残す
+    let result = engine.eval("[0 ? 1 : 2, 1 ? 3 : 4].length").unwrap();
+    assert_eq!(result, JSValue::Number(2.0));
+}
+
+#[test]
+fn test_function_declaration_hoisting() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("f(); function f() { return 7; }").unwrap();
+    assert_eq!(result, JSValue::Number(7.0));
+
+    // 関数本体の中でも巻き上げられる
+    let result = engine
+        .eval(
+            r#"
+        function outer() {
+            return inner() + 1;
+            function inner() { return 41; }
+        }
+        outer()
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(42.0));
+}
+
+#[test]
+fn test_var_hoisting() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("let t = typeof x; var x; t").unwrap();
+    assert_eq!(result, JSValue::String("undefined".to_string()));
+
+    // 初期化は宣言の位置で行われる
+    let result = engine
+        .eval("function f() { let before = y; var y = 1; return [before, y]; } f()")
+        .unwrap();
+    let expected = engine.eval("[undefined, 1]").unwrap();
+    assert!(result.deep_equals(&expected));
+
+    // 初期化なしの再宣言は値を保つ
+    let result = engine.eval("var z = 5; var z; z").unwrap();
+    assert_eq!(result, JSValue::Number(5.0));
+}
+
+#[test]
+fn test_let_temporal_dead_zone() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("function f() { let a = b; let b = 1; return a; } f()");
+    assert!(matches!(result, Err(JSError::ReferenceError(_))));
+
+    let result = engine.eval("function g() { c = 2; const c = 1; } g()");
+    assert!(matches!(result, Err(JSError::ReferenceError(_))));
+
+    let result = engine.eval("q; let q = 1;");
+    assert!(matches!(result, Err(JSError::ReferenceError(_))));
+
+    // 初期化後は通常どおり参照できる
+    let result = engine
+        .eval("function h() { let d = 1; return d + 1; } h()")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(2.0));
+}
+
+#[test]
+fn test_let_tdz_through_closure() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval(
+        r#"
+        function f() {
+            function read() { return v; }
+            let early = read();
+            let v = 1;
+        }
+        f()
+    "#,
+    );
+    assert!(matches!(result, Err(JSError::ReferenceError(_))));
+}
+
+#[test]
+fn test_arguments_object() {
+    let mut engine = JSEngine::new();
+
+    let result = engine
+        .eval("function f() { return arguments.length; } f(1, 2, 3)")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+
+    let result = engine
+        .eval(r#"function g() { return arguments[1]; } g("a", "b")"#)
+        .unwrap();
+    assert_eq!(result, JSValue::String("b".to_string()));
+
+    // 仮引数に対応する引数も含まれる
+    let result = engine
+        .eval("function h(a) { return arguments[0] + arguments.length; } h(10, 20)")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(12.0));
+
+    // 仮引数 arguments は arguments オブジェクトより優先される
+    let result = engine
+        .eval("function k(arguments) { return arguments; } k(5)")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(5.0));
+}
+
+#[test]
+fn test_arguments_is_per_call() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function outer() {
+            function inner() { return arguments.length; }
+            return inner() * 10 + arguments.length;
+        }
+        outer(1, 2)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(2.0));
+}
+
+#[test]
+fn test_arguments_created_only_when_referenced() {
+    let mut engine = JSEngine::new();
+    let function = |source: &str, engine: &mut JSEngine| match engine.eval(source).unwrap() {
+        JSValue::Function(f) => f,
+        other => panic!("Expected function, got {:?}", other),
+    };
+
+    assert!(!function("(function (a) { return a; })", &mut engine).uses_arguments);
+    assert!(function("(function () { return typeof arguments; })", &mut engine).uses_arguments);
+    // 入れ子の関数が参照していても外側の関数には必要ない
+    let outer = function(
+        "(function () { return function () { return arguments; }; })",
+        &mut engine,
+    );
+    assert!(!outer.uses_arguments);
+
+    let result = engine
+        .eval("function t() { return typeof arguments; } t()")
+        .unwrap();
+    assert_eq!(result, JSValue::String("object".to_string()));
+}
+
+#[test]
+fn test_deep_recursion_does_not_use_native_stack() {
+    // 関数呼び出しごとに Rust のスタックを消費しないため、テストの既定のスタックでも深く再帰できる
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("function sum(n) { return n === 0 ? 0 : n + sum(n - 1); } sum(9000)")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(40504500.0));
+
+    // new で呼び出した関数もフレームとして実行する
+    let result = engine
+        .eval("function Make(n) { n > 0 ? new Make(n - 1) : 0; } typeof new Make(5000)")
+        .unwrap();
+    assert_eq!(result, JSValue::String("object".to_string()));
+}
+
+#[test]
+fn test_call_depth_limit_is_range_error() {
+    let mut engine = JSEngine::new();
+    engine.set_max_call_depth(100);
+    engine
+        .eval("function f(n) { return n === 0 ? 0 : 1 + f(n - 1); }")
+        .unwrap();
+
+    assert_eq!(engine.eval("f(99)").unwrap(), JSValue::Number(99.0));
+    assert!(matches!(engine.eval("f(100)"), Err(JSError::RangeError(_))));
+
+    // 捕捉でき、その後も呼び出し元の環境で実行を続けられる
+    let result = engine
+        .eval(
+            r#"
+        let caught = "";
+        try { f(1000); } catch (e) { caught = e.name; }
+        caught + f(3)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("RangeError3".to_string()));
+}
+
+#[test]
+fn test_tail_calls_reuse_frame() {
+    // 呼び出しの深さの上限（既定 10000）を超える回数でも末尾呼び出しならフレームは増えない
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            "function loop(n, acc) { return n === 0 ? acc : loop(n - 1, acc + 1); } loop(100000, 0)",
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(100000.0));
+
+    // 相互再帰
+    let result = engine
+        .eval(
+            r#"
+        function isEven(n) { return n === 0 ? true : isOdd(n - 1); }
+        function isOdd(n) { return n === 0 ? false : isEven(n - 1); }
+        isEven(50001)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Boolean(false));
+
+    // 末尾位置でない呼び出しは上限に達する
+    let result =
+        engine.eval("function count(n) { return n === 0 ? 0 : 1 + count(n - 1); } count(100000)");
+    assert!(matches!(result, Err(JSError::RangeError(_))));
+}
+
+#[test]
+fn test_tail_call_inside_try_is_caught() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function fail() { throw "boom"; }
+        function guarded() {
+            try { return fail(); } catch (e) { return "caught " + e; }
+        }
+        guarded()
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("caught boom".to_string()));
+
+    // new で呼ばれた関数の末尾呼び出しも new の結果の規則に従う
+    let result = engine
+        .eval("function id(x) { return x; } function C() { return id(1); } typeof new C()")
+        .unwrap();
+    assert_eq!(result, JSValue::String("object".to_string()));
+}
+
+#[test]
+fn test_calling_non_function_names_callee() {
+    let mut engine = JSEngine::new();
+    let message = |engine: &mut JSEngine, source: &str| match engine.eval(source) {
+        Err(JSError::TypeError(msg)) => msg,
+        other => panic!("Expected TypeError for {}, got {:?}", source, other),
+    };
+
+    assert_eq!(
+        message(&mut engine, "let x = 5; x()"),
+        "x is not a function (number)"
+    );
+    assert_eq!(
+        message(&mut engine, "undefined()"),
+        "undefined is not a function"
+    );
+    assert_eq!(
+        message(
+            &mut engine,
+            "var o = { inner: { name: 'o' } }; o.inner.name(1)"
+        ),
+        "o.inner.name is not a function (string)"
+    );
+    assert_eq!(
+        message(&mut engine, "function f() { return x(); } f()"),
+        "x is not a function (number)"
+    );
+    // 名前で辿れない呼び出し対象は値を示す
+    assert_eq!(
+        message(&mut engine, "o['inner']()"),
+        "[object Object] is not a function"
+    );
+}
+
+#[test]
+fn test_this_binding() {
+    let mut engine = JSEngine::new();
+    // メソッド呼び出しではレシーバ、new では作成したオブジェクトが this になる
+    assert_eq!(
+        engine
+            .eval("let o = { n: 2, get: function () { return this.n; } }; o.get()")
+            .unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine
+            .eval("function P(x) { this.x = x; } let p = new P(3); p.x")
+            .unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 関数として呼び出した場合と関数の外では undefined
+    assert_eq!(
+        engine.eval("function t() { return this; } t()").unwrap(),
+        JSValue::Undefined
+    );
+    assert_eq!(engine.eval("this").unwrap(), JSValue::Undefined);
+    // 入れ子の関数は外側の this を引き継がない
+    assert_eq!(
+        engine
+            .eval("let q = { m: function () { function inner() { return this; } return inner(); } }; q.m()")
+            .unwrap(),
+        JSValue::Undefined
+    );
+}
+
+#[test]
+fn test_bind() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("function f(a, b) { return this.n + a + b; } let g = f.bind({ n: 1 }, 2); g(3)")
+            .unwrap(),
+        JSValue::Number(6.0)
+    );
+    // メソッドとして呼んでも束縛した this を使う
+    assert_eq!(
+        engine.eval("let o = { n: 100, g: g }; o.g(3)").unwrap(),
+        JSValue::Number(6.0)
+    );
+    // 束縛の重ね掛けは最初の this を保ち、引数を順に前に付ける
+    assert_eq!(
+        engine
+            .eval("f.bind({ n: 1 }).bind({ n: 50 }, 10)(20)")
+            .unwrap(),
+        JSValue::Number(31.0)
+    );
+    assert_eq!(
+        engine.eval("typeof g").unwrap(),
+        JSValue::String("function".to_string())
+    );
+    // ネイティブ関数も束縛できる
+    assert_eq!(
+        engine
+            .eval("let toNumber = Number.bind(null, '7'); toNumber(1)")
+            .unwrap(),
+        JSValue::Number(7.0)
+    );
+    // new では束縛した this を無視し、引数だけを前に付ける
+    assert_eq!(
+        engine
+            .eval("function P(x, y) { this.s = x + y; } let B = P.bind({ s: 0 }, 1); new B(2).s")
+            .unwrap(),
+        JSValue::Number(3.0)
+    );
+    assert!(matches!(
+        engine.eval("let o2 = { bind: f.bind }; o2.bind()"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_arrow_functions() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("let add = (a, b) => a + b; add(2, 3)").unwrap(),
+        JSValue::Number(5.0)
+    );
+    assert_eq!(
+        engine
+            .eval("let twice = x => { let y = x * 2; return y; }; twice(4)")
+            .unwrap(),
+        JSValue::Number(8.0)
+    );
+    assert_eq!(
+        engine.eval("[1, 2].map(x => x * 10)[1]").unwrap(),
+        JSValue::Number(20.0)
+    );
+    assert_eq!(engine.eval("(() => 42)()").unwrap(), JSValue::Number(42.0));
+    // 括弧は引数リストでなければ従来どおり式のグループ化
+    assert_eq!(engine.eval("(1 + 2) * 3").unwrap(), JSValue::Number(9.0));
+    assert!(matches!(
+        engine.eval("let A = () => 1; new A()"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_arrow_functions_inherit_this_and_arguments() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("function f() { return (() => arguments.length)(); } f(1, 2, 3)")
+            .unwrap(),
+        JSValue::Number(3.0)
+    );
+    // 入れ子のアロー関数も外側の関数の arguments を参照する
+    assert_eq!(
+        engine
+            .eval("function g() { return (() => () => arguments[1])()(); } g('a', 'b')")
+            .unwrap(),
+        JSValue::String("b".to_string())
+    );
+    assert_eq!(
+        engine
+            .eval("let o = { n: 5, get() { return [1, 2].map(x => x + this.n); } }; o.get()[1]")
+            .unwrap(),
+        JSValue::Number(7.0)
+    );
+    // 呼び出し時の this やアロー関数自身の引数では置き換わらない
+    assert_eq!(
+        engine
+            .eval("function h() { let a = (x) => [this.n, arguments[0]]; return a.bind({ n: 9 })(100); } let r = h.bind({ n: 1 })('outer'); r[0] + r[1]")
+            .unwrap(),
+        JSValue::String("1outer".to_string())
+    );
+}
+
+#[test]
+fn test_spread_arguments() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("function sum(a, b, c) { return a + b + c; }")
+        .unwrap();
+    assert_eq!(
+        engine.eval("sum(...[1, 2, 3])").unwrap(),
+        JSValue::Number(6.0)
+    );
+    assert_eq!(engine.eval("sum(1, 2, 3)").unwrap(), JSValue::Number(6.0));
+    assert_eq!(
+        engine.eval("sum(1, ...[2], 3)").unwrap(),
+        JSValue::Number(6.0)
+    );
+    assert_eq!(
+        engine.eval("sum(...'abc')").unwrap(),
+        JSValue::String("abc".to_string())
+    );
+    // 実行時の引数の数は展開した要素の数になる
+    assert_eq!(
+        engine
+            .eval("function count() { return arguments.length; } count(...[], 1, ...[2, 3])")
+            .unwrap(),
+        JSValue::Number(3.0)
+    );
+    // メソッド呼び出しは this を保ち、new にも使える
+    assert_eq!(
+        engine
+            .eval("let o = { k: 10, f(a, b) { return this.k + a + b; } }; o.f(...[1, 2])")
+            .unwrap(),
+        JSValue::Number(13.0)
+    );
+    assert_eq!(
+        engine
+            .eval("function P(x, y) { this.s = x + y; } new P(...[3, 4]).s")
+            .unwrap(),
+        JSValue::Number(7.0)
+    );
+    assert_eq!(
+        engine
+            .eval("let a = [0]; a.push(...[1, 2], 3); a.join()")
+            .unwrap(),
+        JSValue::String("0,1,2,3".to_string())
+    );
+    assert!(matches!(
+        engine.eval("sum(...1)"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_engine_usable_after_throw_from_catch_scope() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("let x = 1; function rethrow() { try { throw 1; } catch (e) { throw e + 1; } }")
+        .unwrap();
+
+    // 関数内とトップレベルの catch 節から抜けるエラー
+    assert!(engine.eval("rethrow()").is_err());
+    assert!(
+        engine
+            .eval("try { throw 1; } catch (e) { throw 2; }")
+            .is_err()
+    );
+    assert!(engine.eval("[1].map(function () { rethrow(); })").is_err());
+
+    // 後の評価は元のグローバル環境で行われる
+    assert_eq!(
+        engine.eval("typeof e").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+    assert_eq!(engine.eval("x + 1").unwrap(), JSValue::Number(2.0));
+}
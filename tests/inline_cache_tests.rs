@@ -0,0 +1,69 @@
+use pixi_byte::value::JSObject;
+use pixi_byte::{JSEngine, JSValue};
+
+#[test]
+fn test_shape_changes_only_with_keys() {
+    let mut obj = JSObject::new();
+    let empty = obj.shape();
+
+    obj.set("x".to_string(), JSValue::Number(1.0));
+    let with_x = obj.shape();
+    assert_ne!(with_x, empty);
+
+    // 値の書き換えではシェイプは変わらない
+    obj.set("x".to_string(), JSValue::Number(2.0));
+    assert_eq!(obj.shape(), with_x);
+
+    // キーを全て削除すると空のオブジェクトのシェイプに戻る
+    obj.delete("x");
+    assert_eq!(obj.shape(), empty);
+}
+
+#[test]
+fn test_cached_site_sees_different_objects() {
+    let mut engine = JSEngine::new();
+    engine.eval("function get(o) { return o.x; }").unwrap();
+
+    assert_eq!(engine.eval("get({ x: 1 })").unwrap(), JSValue::Number(1.0));
+    assert_eq!(
+        engine.eval("get({ y: 0, x: 2 })").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine.eval("get({ x: 3, y: 0 })").unwrap(),
+        JSValue::Number(3.0)
+    );
+    assert_eq!(engine.eval("get({ y: 0 })").unwrap(), JSValue::Undefined);
+    // プロトタイプ上のプロパティはキャッシュせずに探す
+    engine.eval("Number.prototype.x = 9").unwrap();
+    assert_eq!(
+        engine.eval("get(new Number(1))").unwrap(),
+        JSValue::Number(9.0)
+    );
+    assert_eq!(engine.eval("get(1)").unwrap(), JSValue::Number(9.0));
+    assert_eq!(engine.eval("get({ x: 4 })").unwrap(), JSValue::Number(4.0));
+}
+
+#[test]
+fn test_cache_invalidates_when_shape_changes() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("function get(o) { return o.x; } var o = { a: 1, x: 2 };")
+        .unwrap();
+    assert_eq!(engine.eval("get(o)").unwrap(), JSValue::Number(2.0));
+
+    // 値の書き換えはキャッシュした位置から読める
+    engine.eval("o.x = 7").unwrap();
+    assert_eq!(engine.eval("get(o)").unwrap(), JSValue::Number(7.0));
+
+    // 前のキーを削除すると x の位置がずれる
+    let obj = engine.eval("o").unwrap().as_object().unwrap();
+    obj.borrow_mut().delete("a");
+    assert_eq!(engine.eval("get(o)").unwrap(), JSValue::Number(7.0));
+
+    engine.eval("o.a = 5").unwrap();
+    assert_eq!(engine.eval("get(o)").unwrap(), JSValue::Number(7.0));
+
+    obj.borrow_mut().delete("x");
+    assert_eq!(engine.eval("get(o)").unwrap(), JSValue::Undefined);
+}
@@ -0,0 +1,53 @@
+use pixi_byte::{JSEngine, JSValue};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[test]
+fn test_inspect_object_and_array() {
+    let mut engine = JSEngine::new();
+    let value = engine.eval("({ a: 1, b: [2, 3] })").unwrap();
+    assert_eq!(value.inspect(), "{ a: 1, b: [ 2, 3 ] }");
+
+    assert_eq!(engine.eval("({})").unwrap().inspect(), "{}");
+    assert_eq!(engine.eval("[]").unwrap().inspect(), "[]");
+}
+
+#[test]
+fn test_inspect_quotes_strings_and_keys() {
+    let mut engine = JSEngine::new();
+    let value = engine
+        .eval(r#"({ name: "it's", "a-b": 1, "1": true, _x$: null })"#)
+        .unwrap();
+    assert_eq!(
+        value.inspect(),
+        r#"{ '1': true, name: 'it\'s', 'a-b': 1, _x$: null }"#
+    );
+}
+
+#[test]
+fn test_inspect_circular() {
+    let mut engine = JSEngine::new();
+    let value = engine.eval("let o = { a: 1 }; o.self = o; o").unwrap();
+    assert_eq!(value.inspect(), "{ a: 1, self: [Circular] }");
+}
+
+#[test]
+fn test_inspect_keeps_to_string_spec_correct() {
+    let mut engine = JSEngine::new();
+    let value = engine.eval("({ a: 1 })").unwrap();
+    assert_eq!(value.to_string(), "[object Object]");
+    assert_eq!(JSValue::String("s".to_string()).inspect(), "'s'");
+}
+
+#[test]
+fn test_console_log_uses_inspect() {
+    let mut engine = JSEngine::new();
+    let output = Rc::new(RefCell::new(Vec::new()));
+    let sink = output.clone();
+    engine.set_printer(move |line| sink.borrow_mut().push(line.to_string()));
+
+    engine
+        .eval(r#"console.log("value:", { a: 1, b: [2, 3] }, 4)"#)
+        .unwrap();
+    assert_eq!(*output.borrow(), ["value: { a: 1, b: [ 2, 3 ] } 4"]);
+}
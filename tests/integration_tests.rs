@@ -1,4 +1,4 @@
-use pixi_byte::JSEngine;
+use pixi_byte::{JSEngine, JSError, JSValue};
 
 #[test]
 fn test_basic_arithmetic() {
@@ -57,16 +57,52 @@ fn test_boolean_operations() {
     let mut engine = JSEngine::new();
 
     let result = engine.eval("true && true").unwrap();
-    assert_eq!(result.to_boolean(), true);
+    assert!(result.to_boolean());
 
     let result = engine.eval("true && false").unwrap();
-    assert_eq!(result.to_boolean(), false);
+    assert!(!result.to_boolean());
 
     let result = engine.eval("false || true").unwrap();
-    assert_eq!(result.to_boolean(), true);
+    assert!(result.to_boolean());
 
     let result = engine.eval("!true").unwrap();
-    assert_eq!(result.to_boolean(), false);
+    assert!(!result.to_boolean());
+}
+
+#[test]
+fn test_logical_operators_short_circuit() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("var calls = 0; function crash() { throw 'crashed'; } function hit(v) { calls = calls + 1; return v; }")
+        .unwrap();
+
+    // 結果が左辺で決まる場合は右辺を評価しない
+    assert_eq!(
+        engine.eval("false && crash()").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("true || crash()").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(engine.eval("0 && crash()").unwrap(), JSValue::Number(0.0));
+    assert_eq!(
+        engine
+            .eval("'' || hit(1) && hit(0) || hit('last')")
+            .unwrap(),
+        JSValue::String("last".to_string())
+    );
+    assert_eq!(engine.eval("calls").unwrap(), JSValue::Number(3.0));
+    assert!(matches!(
+        engine.eval("true && crash()"),
+        Err(JSError::Exception(_))
+    ));
+    // 値は真偽値に変換せずそのまま返す
+    assert_eq!(
+        engine.eval("1 && 'a'").unwrap(),
+        JSValue::String("a".to_string())
+    );
+    assert_eq!(engine.eval("null || 2").unwrap(), JSValue::Number(2.0));
 }
 
 #[test]
@@ -74,19 +110,19 @@ fn test_comparisons() {
     let mut engine = JSEngine::new();
 
     let result = engine.eval("5 > 3").unwrap();
-    assert_eq!(result.to_boolean(), true);
+    assert!(result.to_boolean());
 
     let result = engine.eval("2 < 1").unwrap();
-    assert_eq!(result.to_boolean(), false);
+    assert!(!result.to_boolean());
 
     let result = engine.eval("5 === 5").unwrap();
-    assert_eq!(result.to_boolean(), true);
+    assert!(result.to_boolean());
 
     let result = engine.eval("5 === '5'").unwrap();
-    assert_eq!(result.to_boolean(), false);
+    assert!(!result.to_boolean());
 
     let result = engine.eval("5 == '5'").unwrap();
-    assert_eq!(result.to_boolean(), true);
+    assert!(result.to_boolean());
 }
 
 #[test]
@@ -105,3 +141,698 @@ fn test_typeof() {
     let result = engine.eval("typeof undefined").unwrap();
     assert_eq!(result.to_string(), "undefined");
 }
+
+#[test]
+fn test_chained_assignment() {
+    let mut engine = JSEngine::new();
+
+    let result = engine
+        .eval("let a; let b; let c; a = b = c = 5; a + b + c")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(15.0));
+
+    // 代入式は代入された値に評価される
+    let result = engine.eval("a = b = 7").unwrap();
+    assert_eq!(result, JSValue::Number(7.0));
+}
+
+#[test]
+fn test_chained_member_assignment() {
+    let mut engine = JSEngine::new();
+
+    let result = engine
+        .eval("let o = {}; let x; x = o.p = o.q = 3; x + o.p + o.q")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(9.0));
+}
+
+#[test]
+fn test_number_with_trailing_dot() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("5.").unwrap(), JSValue::Number(5.0));
+    assert_eq!(engine.eval("5. * 2").unwrap(), JSValue::Number(10.0));
+    // 2つ目の `.` はメンバーアクセス
+    assert_eq!(
+        engine.eval("5..toString()").unwrap(),
+        JSValue::String("5".to_string())
+    );
+}
+
+#[test]
+fn test_exponent_overflow_and_underflow() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("1e400").unwrap(),
+        JSValue::Number(f64::INFINITY)
+    );
+    assert_eq!(
+        engine.eval("-1e400").unwrap(),
+        JSValue::Number(f64::NEG_INFINITY)
+    );
+    assert_eq!(
+        engine.eval("1 / 1e-400").unwrap(),
+        JSValue::Number(f64::INFINITY)
+    );
+    assert_eq!(engine.eval("5e-324 > 0").unwrap(), JSValue::Boolean(true));
+    assert_eq!(engine.eval("1e-400").unwrap(), JSValue::Number(0.0));
+}
+
+#[test]
+fn test_exponentiation() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("2 ** 10").unwrap(), JSValue::Number(1024.0));
+    // 右結合・乗算より優先
+    assert_eq!(engine.eval("2 ** 3 ** 2").unwrap(), JSValue::Number(512.0));
+    assert_eq!(engine.eval("3 * 2 ** 2").unwrap(), JSValue::Number(12.0));
+    assert_eq!(engine.eval("(-2) ** 2").unwrap(), JSValue::Number(4.0));
+    assert_eq!(engine.eval("-(2 ** 2)").unwrap(), JSValue::Number(-4.0));
+    assert_eq!(engine.eval("2 ** -1").unwrap(), JSValue::Number(0.5));
+    assert!(matches!(engine.eval("1 ** (0 / 0)"), Ok(JSValue::Number(n)) if n.is_nan()));
+}
+
+#[test]
+fn test_unary_before_exponentiation_is_syntax_error() {
+    let mut engine = JSEngine::new();
+    for source in ["-2 ** 2", "!a ** 2", "typeof a ** 2"] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::SyntaxError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_eval_skips_shebang_line() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("#!/usr/bin/env node\n1 + 2").unwrap(),
+        JSValue::Number(3.0)
+    );
+}
+
+#[test]
+fn test_identifier_escapes() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("\\u0061 = 5; a").unwrap(), JSValue::Number(5.0));
+    assert_eq!(
+        engine.eval("let caf\\u{e9} = 1; café + \\u0061").unwrap(),
+        JSValue::Number(6.0)
+    );
+}
+
+#[test]
+fn test_integer_arithmetic_boundaries() {
+    let mut engine = JSEngine::new();
+    // i32 の範囲を超える結果は浮動小数点数で計算する
+    assert_eq!(
+        engine.eval("2 ** 31").unwrap(),
+        JSValue::Number(2147483648.0)
+    );
+    assert_eq!(
+        engine.eval("(-2) ** 31").unwrap(),
+        JSValue::Number(-2147483648.0)
+    );
+    assert_eq!(
+        engine.eval("2147483647 + 1").unwrap(),
+        JSValue::Number(2147483648.0)
+    );
+    assert_eq!(
+        engine.eval("-2147483648 - 1").unwrap(),
+        JSValue::Number(-2147483649.0)
+    );
+    assert_eq!(
+        engine.eval("65536 * 65536").unwrap(),
+        JSValue::Number(4294967296.0)
+    );
+    // 整数でない値を含む演算と整数にならない結果
+    assert_eq!(engine.eval("0.5 + 0.5").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("1.5 * 2").unwrap(), JSValue::Number(3.0));
+    assert_eq!(engine.eval("7 / 2").unwrap(), JSValue::Number(3.5));
+    assert_eq!(engine.eval("7.5 % 2").unwrap(), JSValue::Number(1.5));
+    // -0 と NaN になる結果
+    for source in ["0 * -5", "-4 % 2", "-2147483648 % -1", "-0 - 0"] {
+        assert_eq!(
+            engine.eval(&format!("1 / ({})", source)).unwrap(),
+            JSValue::Number(f64::NEG_INFINITY),
+            "{}",
+            source
+        );
+    }
+    assert!(matches!(engine.eval("5 % 0"), Ok(JSValue::Number(n)) if n.is_nan()));
+    assert_eq!(engine.eval("-5 % 3").unwrap(), JSValue::Number(-2.0));
+    // 数値の比較と文字列の比較
+    assert_eq!(
+        engine.eval("2147483647 < 2 ** 31").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("0.5 + 0.5 >= 1").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("\"10\" < \"9\"").unwrap(),
+        JSValue::Boolean(true)
+    );
+}
+
+#[test]
+fn test_eval_without_host_io() {
+    // `cargo test --no-default-features` で時計・標準出力を使わない構成も検証する
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval("1+2").unwrap(), JSValue::Number(3.0));
+    if cfg!(not(feature = "clock")) {
+        assert_eq!(engine.eval("Date.now()").unwrap(), JSValue::Number(0.0));
+    }
+    engine.set_clock(|| 1000.0);
+    assert_eq!(engine.eval("Date.now()").unwrap(), JSValue::Number(1000.0));
+}
+
+#[test]
+fn test_eval_file_reports_path_and_position() {
+    let dir = std::env::temp_dir().join(format!("pixi_byte_eval_file_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let ok = dir.join("ok.js");
+    let broken = dir.join("broken.js");
+    std::fs::write(&ok, "let x = 40;\nx + 2\n").unwrap();
+    let failing = dir.join("failing.js");
+    std::fs::write(&broken, "let x = 1;\nlet y = (x + ;\n").unwrap();
+    std::fs::write(&failing, "let n = 1;\nn()\n").unwrap();
+
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval_file(&ok).unwrap(), JSValue::Number(42.0));
+    let message = match engine.eval_file(&broken) {
+        Err(JSError::SyntaxError(msg)) => msg,
+        other => panic!("Expected SyntaxError, got {:?}", other),
+    };
+    let prefix = format!("{}:2:", broken.display());
+    assert!(message.starts_with(&prefix), "{}", message);
+    // 実行時エラーには位置がないためパスのみを付ける
+    let message = match engine.eval_file(&failing) {
+        Err(JSError::TypeError(msg)) => msg,
+        other => panic!("Expected TypeError, got {:?}", other),
+    };
+    assert!(
+        message.starts_with(&format!("{}: ", failing.display())),
+        "{}",
+        message
+    );
+    assert!(engine.eval_file(&dir.join("missing.js")).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_chained_method_calls_leave_single_value() {
+    use pixi_byte::vm::VM;
+    use pixi_byte::{Compiler, Lexer, Opcode, Parser};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap()
+    };
+    let chain =
+        "[1, 2, 3, 4].filter(function (x) { return x > 1; }).map(function (x) { return x * 10; })";
+
+    let mut vm = VM::new();
+    let result = vm.execute(&compile(chain)).unwrap();
+    assert_eq!(result.inspect(), "[ 20, 30, 40 ]");
+    let result = vm.execute(&compile(&format!("{}.length", chain))).unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+
+    // 途中の結果がスタックに残っていれば、次の実行の開始時点で深さが 0 にならない
+    let depths = Rc::new(RefCell::new(Vec::new()));
+    let sink = depths.clone();
+    vm.set_trace(Some(Box::new(move |_, _: &Opcode, depth| {
+        sink.borrow_mut().push(depth);
+    })));
+    vm.execute(&compile("0")).unwrap();
+    assert_eq!(depths.borrow()[..], [0]);
+}
+
+#[test]
+fn test_empty_sources_evaluate_to_undefined() {
+    let mut engine = JSEngine::new();
+    for source in [
+        "",
+        "   ",
+        "\n\t \r\n",
+        "// comment",
+        "/* block */",
+        "#!/usr/bin/env node",
+    ] {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::Undefined,
+            "{:?}",
+            source
+        );
+    }
+    // 宣言のみのプログラムも undefined
+    assert_eq!(engine.eval("let x = 1;").unwrap(), JSValue::Undefined);
+    assert_eq!(engine.eval("function f() {}").unwrap(), JSValue::Undefined);
+}
+
+#[test]
+fn test_eval_module_accepts_export_declarations() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval_module("export const x = 1; export function f() { return x + 1; } f()")
+            .unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert!(matches!(
+        engine.eval("export const y = 1"),
+        Err(JSError::SyntaxError(_))
+    ));
+    // モジュールの読み込みは未対応
+    assert!(matches!(
+        engine.eval_module(r#"import { a } from "m""#),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_legacy_octal_escapes() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#""\101""#).unwrap(),
+        JSValue::String("A".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#""\0" + "\08" + "\1234" + "\9""#).unwrap(),
+        JSValue::String("\0\08S49".to_string())
+    );
+
+    // strict モードのコードでは SyntaxError
+    for source in [
+        r#""use strict"; "\101""#,
+        "'use strict'\nlet s = '\\7';",
+        r#"function f() { "use strict"; return "\101"; }"#,
+        r#""use strict"; ({ "\101": 1 })"#,
+    ] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::SyntaxError(_))),
+            "{}",
+            source
+        );
+    }
+    assert!(matches!(
+        engine.eval_module(r#"export const s = "\101";"#),
+        Err(JSError::SyntaxError(_))
+    ));
+    // \0 は strict モードでも使える。括弧で囲んだ "use strict" は指令にならない
+    assert_eq!(
+        engine.eval(r#""use strict"; "\0""#).unwrap(),
+        JSValue::String("\0".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#"("use strict"); "\101""#).unwrap(),
+        JSValue::String("A".to_string())
+    );
+    // 関数の strict モードは外側に影響しない
+    assert_eq!(
+        engine
+            .eval(r#"function h() { "use strict"; } "\101""#)
+            .unwrap(),
+        JSValue::String("A".to_string())
+    );
+}
+
+#[test]
+fn test_if_and_while_statements() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("let n = 0; let i = 0; while (i < 5) { i = i + 1; if (i === 3) n = n + 10; else { n = n + 1; } } n")
+            .unwrap(),
+        JSValue::Number(14.0)
+    );
+    assert_eq!(
+        engine
+            .eval("function sign(x) { if (x < 0) return -1; else if (x > 0) return 1; return 0; } sign(-5) + sign(0) * 10 + sign(7) * 100")
+            .unwrap(),
+        JSValue::Number(99.0)
+    );
+    // 本体の var 宣言は外側のスコープに巻き上げる
+    assert_eq!(
+        engine
+            .eval("if (false) { var hoisted = 1; } typeof hoisted")
+            .unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+}
+
+#[test]
+fn test_statement_completion_values() {
+    let mut engine = JSEngine::new();
+    // if 文は実行した分岐の値、実行しなければ undefined
+    assert_eq!(
+        engine.eval("let x = 1; if (x) 42").unwrap(),
+        JSValue::Number(42.0)
+    );
+    assert_eq!(
+        engine.eval("if (x) { 1; 2 } else { 3 }").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(engine.eval("7; if (!x) 42").unwrap(), JSValue::Undefined);
+    assert_eq!(
+        engine.eval("if (x) { function f() {} }").unwrap(),
+        JSValue::Undefined
+    );
+    // while 文は最後に実行した本体の値、一度も実行しなければ undefined
+    assert_eq!(
+        engine.eval("5; while (false) {}").unwrap(),
+        JSValue::Undefined
+    );
+    assert_eq!(
+        engine
+            .eval("let i = 0; while (i < 3) { i = i + 1; i * 10 }")
+            .unwrap(),
+        JSValue::Number(30.0)
+    );
+    // 最後でない文の値は残さない
+    assert_eq!(
+        engine.eval("if (x) 1; while (false) {} 8").unwrap(),
+        JSValue::Number(8.0)
+    );
+}
+
+#[test]
+fn test_for_of_statement() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval(r#"let seen = ""; for (const [i, v] of ["a", "b"].entries()) { seen = seen + "(" + i + "," + v + ")"; } seen"#)
+            .unwrap(),
+        JSValue::String("(0,a)(1,b)".to_string())
+    );
+    assert_eq!(
+        engine
+            .eval(r#"let chars = ""; for (let c of "hey") chars = c + chars; chars"#)
+            .unwrap(),
+        JSValue::String("yeh".to_string())
+    );
+    // 入れ子のパターンと読み飛ばす要素
+    assert_eq!(
+        engine
+            .eval("let sum = 0; for (var [, [x, y]] of [[0, [1, 2]], [0, [3, 4]]]) sum = sum + x * y; sum")
+            .unwrap(),
+        JSValue::Number(14.0)
+    );
+    // next を持つオブジェクトはそのままイテレータとして使う
+    assert_eq!(
+        engine
+            .eval("let n = 0; let counter = { next: function () { n = n + 1; return { value: n, done: n > 3 }; } }; let total = 0; for (const k of counter) total = total + k; total")
+            .unwrap(),
+        JSValue::Number(6.0)
+    );
+    // 完了値は最後に実行した本体の値
+    assert_eq!(
+        engine.eval("for (const v of [1, 2]) v * 100").unwrap(),
+        JSValue::Number(200.0)
+    );
+    assert_eq!(
+        engine.eval("9; for (const v of []) v").unwrap(),
+        JSValue::Undefined
+    );
+    assert!(matches!(
+        engine.eval("for (const v of 5) {}"),
+        Err(JSError::TypeError(_))
+    ));
+    assert!(matches!(
+        engine.eval("for (x of [1]) {}"),
+        Err(JSError::SyntaxError(_))
+    ));
+}
+
+#[test]
+fn test_for_of_binding_is_scoped_per_iteration() {
+    let mut engine = JSEngine::new();
+    // let / const の束縛はループの外の同名の変数を隠す
+    assert_eq!(
+        engine
+            .eval(r#"let x = "outer"; for (const x of [1]) {} x"#)
+            .unwrap(),
+        JSValue::String("outer".to_string())
+    );
+    assert_eq!(
+        engine
+            .eval("for (let [p, q] of [[1, 2]]) {} typeof p + typeof q")
+            .unwrap(),
+        JSValue::String("undefinedundefined".to_string())
+    );
+    // クロージャはそれぞれの反復の束縛を捕捉する（continue / break した反復も同じ）
+    assert_eq!(
+        engine
+            .eval("let fs = []; for (const x of [1, 2, 3, 4]) { fs.push(function () { return x; }); if (x === 2) continue; if (x === 3) break; } fs[0]() * 100 + fs[1]() * 10 + fs[2]()")
+            .unwrap(),
+        JSValue::Number(123.0)
+    );
+    // var は関数スコープの1つの束縛に代入する
+    assert_eq!(
+        engine
+            .eval("let gs = []; { let b = 0; for (var y of [1, 2]) gs.push(() => y); } gs[0]() + y")
+            .unwrap(),
+        JSValue::Number(4.0)
+    );
+}
+
+#[test]
+fn test_snapshot_and_restore_globals() {
+    let mut engine = JSEngine::new();
+    engine.eval("var a = 1; let b = { n: 2 };").unwrap();
+    let snapshot = engine.snapshot();
+
+    engine.eval("var c = 3; a = 10; b.n = 20;").unwrap();
+    engine.restore(snapshot.clone());
+    assert_eq!(engine.eval("a").unwrap(), JSValue::Number(1.0));
+    assert_eq!(
+        engine.eval("typeof c").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+    // 束縛だけを戻し、オブジェクトの中身の変更は残る
+    assert_eq!(engine.eval("b.n").unwrap(), JSValue::Number(20.0));
+    // 組み込みの束縛も保たれる
+    assert_eq!(
+        engine.eval("Number(\"4\") + a").unwrap(),
+        JSValue::Number(5.0)
+    );
+
+    // 同じスナップショットに何度でも戻せる
+    engine.eval("let c = 5;").unwrap();
+    engine.restore(snapshot);
+    assert_eq!(
+        engine.eval("typeof c").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+}
+
+#[test]
+fn test_loose_equality_converts_objects_to_primitives() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("[1] == 1", true),
+        ("[] == 0", true),
+        ("[] == ''", true),
+        ("[1, 2] == '1,2'", true),
+        ("[0] == false", true),
+        ("1 == [1]", true),
+        ("[1] != 1", false),
+        ("({}) == '[object Object]'", true),
+        ("({ valueOf() { return 3; } }) == 3", true),
+        ("[2] == 1", false),
+        // オブジェクト同士は変換せず参照で比較する
+        ("[] == []", false),
+        ("[] == null", false),
+        ("undefined == [undefined]", false),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::Boolean(expected),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_block_statements() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("{ let x = 1; x + 1 }").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(engine.eval("3; {}").unwrap(), JSValue::Undefined);
+    assert_eq!(
+        engine
+            .eval("let n = 0; { n = n + 1; { n = n * 10; } } n")
+            .unwrap(),
+        JSValue::Number(10.0)
+    );
+    // ブロック内の関数宣言はブロックの先頭に巻き上げる
+    assert_eq!(
+        engine.eval("{ f(); function f() { return 4; } }").unwrap(),
+        JSValue::Number(4.0)
+    );
+}
+
+#[test]
+fn test_for_statement_with_break_and_continue() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("let i = 0; for (;;) { i = i + 1; if (i === 5) break; } i")
+            .unwrap(),
+        JSValue::Number(5.0)
+    );
+    assert_eq!(
+        engine
+            .eval("let sum = 0; for (let j = 0; j < 10; j = j + 1) { if (j % 2 === 0) continue; sum = sum + j; } sum")
+            .unwrap(),
+        JSValue::Number(25.0)
+    );
+    // break / continue は最も内側のループに作用する
+    assert_eq!(
+        engine
+            .eval("let pairs = 0; for (var a = 0; a < 3; a = a + 1) for (const b of [0, 1, 2]) { if (b > a) break; pairs = pairs + 1; } pairs")
+            .unwrap(),
+        JSValue::Number(6.0)
+    );
+    assert_eq!(
+        engine
+            .eval("let k = 0; let seen = ''; while (k < 5) { k = k + 1; if (k === 2) continue; if (k === 4) break; seen = seen + k; } seen")
+            .unwrap(),
+        JSValue::String("13".to_string())
+    );
+    // try 節の中から抜けても例外ハンドラは残らない
+    assert_eq!(
+        engine
+            .eval("let n = 0; for (;;) { try { n = n + 1; if (n > 2) break; } catch (e) {} } try { throw 'x'; } catch (e) { n = n * 10; } n")
+            .unwrap(),
+        JSValue::Number(30.0)
+    );
+    // 関数の中のループから return する
+    assert_eq!(
+        engine
+            .eval("function find(xs, x) { for (const [i, v] of xs.entries()) { if (v === x) return i; } return -1; } find(['a', 'b'], 'b') + find([], 'z')")
+            .unwrap(),
+        JSValue::Number(0.0)
+    );
+    // 完了値
+    assert_eq!(
+        engine
+            .eval("for (let m = 0; m < 3; m = m + 1) m * 2")
+            .unwrap(),
+        JSValue::Number(4.0)
+    );
+    // break した反復の完了値は undefined
+    assert_eq!(
+        engine
+            .eval("for (const v of [1, 2]) { if (v === 2) break; v }")
+            .unwrap(),
+        JSValue::Undefined
+    );
+
+    for source in [
+        "break;",
+        "if (true) { continue; }",
+        "while (true) { function f() { break; } }",
+    ] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::SyntaxError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_for_let_binding_is_scoped_per_iteration() {
+    let mut engine = JSEngine::new();
+    // 初期化部の let はループの外の同名の変数を隠す
+    assert_eq!(
+        engine
+            .eval("let i = 5; for (let i = 0; i < 2; i = i + 1) {} i")
+            .unwrap(),
+        JSValue::Number(5.0)
+    );
+    assert_eq!(
+        engine
+            .eval("for (const c = 1; false; ) {} typeof c")
+            .unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+    // クロージャはそれぞれの反復の束縛を捕捉する
+    assert_eq!(
+        engine
+            .eval("let gs = []; for (let i = 0; i < 3; i = i + 1) gs.push(() => i); gs[0]() + gs[1]() * 10 + gs[2]() * 100")
+            .unwrap(),
+        JSValue::Number(210.0)
+    );
+    assert_eq!(
+        engine
+            .eval("let hs = []; for (let j = 0; j < 5; j = j + 1) { if (j === 1) continue; hs.push(() => j); if (j === 3) break; } hs[0]() + hs[1]() * 10 + hs[2]() * 100")
+            .unwrap(),
+        JSValue::Number(320.0)
+    );
+}
+
+#[test]
+fn test_bitwise_operators_use_to_int32() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("(0 / 0) | 0", 0.0),
+        ("4294967296 | 0", 0.0),
+        ("4294967297 & 3", 1.0),
+        ("2147483648 | 0", -2147483648.0),
+        ("1e21 ^ 0", -559939584.0),
+        ("~4294967295", 0.0),
+        ("(1 / 0) << 1", 0.0),
+        ("1 << 33", 2.0),
+        ("-4294967304 >> 1", -4.0),
+        ("({ valueOf() { return 4294967298; } }) | 0", 2.0),
+        // 優先順位: 加減算 > シフト > 比較 > 等価 > & > ^ > |
+        ("1 | 6 & 3 ^ 1", 3.0),
+        ("1 + 2 << 1", 6.0),
+        ("16 >> 1 + 1", 4.0),
+        ("5 & 4 == 4", 1.0),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::Number(expected),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_unsigned_right_shift_uses_to_uint32() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("-1 >>> 0", 4294967295.0),
+        ("8 >>> 1", 4.0),
+        ("-8 >>> 1", 2147483644.0),
+        ("1 >>> 32", 1.0),
+        ("1 >>> 33", 0.0),
+        ("4294967296.5 >>> 0", 0.0),
+        ("(-1 / 0) >>> 0", 0.0),
+        ("'-1' >>> 28", 15.0),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::Number(expected),
+            "{}",
+            source
+        );
+    }
+}
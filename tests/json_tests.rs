@@ -0,0 +1,100 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+/// JSON.stringify の結果を文字列で返す
+fn stringify(engine: &mut JSEngine, source: &str) -> String {
+    match engine.eval(source).unwrap() {
+        JSValue::String(s) => s,
+        other => panic!("Expected string for {}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn test_stringify_values() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        stringify(
+            &mut engine,
+            r#"JSON.stringify({ a: 1, b: "x", c: [true, null], d: { e: -0.5 } })"#
+        ),
+        r#"{"a":1,"b":"x","c":[true,null],"d":{"e":-0.5}}"#
+    );
+    // undefined と関数はプロパティでは省略、配列の要素では null
+    assert_eq!(
+        stringify(
+            &mut engine,
+            "JSON.stringify({ u: undefined, f: function () {}, n: 0 / 0, a: [undefined, Math.random] })"
+        ),
+        r#"{"n":null,"a":[null,null]}"#
+    );
+    assert_eq!(
+        stringify(&mut engine, r#"JSON.stringify("a\"b\\c\n")"#),
+        r#""a\"b\\c\n""#
+    );
+    assert_eq!(
+        stringify(
+            &mut engine,
+            "JSON.stringify([new Number(1), new String('s')])"
+        ),
+        r#"[1,"s"]"#
+    );
+    assert_eq!(
+        engine.eval("JSON.stringify(undefined)").unwrap(),
+        JSValue::Undefined
+    );
+}
+
+#[test]
+fn test_stringify_to_json() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        stringify(
+            &mut engine,
+            r#"JSON.stringify({ d: { toJSON: function () { return "custom"; } } })"#
+        ),
+        r#"{"d":"custom"}"#
+    );
+    // toJSON にはプロパティのキーが渡される
+    assert_eq!(
+        stringify(
+            &mut engine,
+            "JSON.stringify({ k: { toJSON: function (key) { return key + '!'; } } })"
+        ),
+        r#"{"k":"k!"}"#
+    );
+}
+
+#[test]
+fn test_stringify_replacer_function() {
+    let mut engine = JSEngine::new();
+    // undefined を返したキーは省略される
+    assert_eq!(
+        stringify(
+            &mut engine,
+            r#"JSON.stringify({ a: 1, secret: 2, b: { secret: 3, c: 4 } },
+                function (key, value) { return key === "secret" ? undefined : value; })"#
+        ),
+        r#"{"a":1,"b":{"c":4}}"#
+    );
+    // 最上位の値は空文字列のキーで渡される
+    assert_eq!(
+        stringify(
+            &mut engine,
+            "JSON.stringify(5, function (key, value) { return key === '' ? [value] : value * 2; })"
+        ),
+        "[10]"
+    );
+}
+
+#[test]
+fn test_stringify_circular_is_type_error() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(
+        engine.eval("let o = {}; o.self = o; JSON.stringify(o)"),
+        Err(JSError::TypeError(_))
+    ));
+    // 同じオブジェクトを複数回参照するだけなら循環ではない
+    assert_eq!(
+        stringify(&mut engine, "let s = { x: 1 }; JSON.stringify([s, s])"),
+        r#"[{"x":1},{"x":1}]"#
+    );
+}
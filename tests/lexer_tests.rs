@@ -1,4 +1,4 @@
-use pixi_byte::{Lexer, TokenKind};
+use pixi_byte::{JSError, Lexer, Parser, TokenKind};
 
 #[test]
 fn test_tokenize_numbers() {
@@ -7,7 +7,6 @@ fn test_tokenize_numbers() {
     let kinds: Vec<TokenKind> = tokens
         .iter()
         .map(|t| t.kind.clone())
-        .into_iter()
         .filter(|k| *k != TokenKind::Eof)
         .collect();
     assert_eq!(
@@ -27,7 +26,6 @@ fn test_weird_number_digits() {
     let kinds: Vec<TokenKind> = tokens
         .iter()
         .map(|t| t.kind.clone())
-        .into_iter()
         .filter(|k| *k != TokenKind::Eof)
         .collect();
     assert_eq!(
@@ -43,6 +41,34 @@ fn test_weird_number_digits() {
     );
 }
 
+#[test]
+fn test_exponent_without_digits_is_error() {
+    for source in ["1e", "1e+", "2.5E-", "1e;"] {
+        match Lexer::new(source).tokenize() {
+            Err(JSError::SyntaxError(message)) => {
+                assert!(
+                    message.starts_with("1:1: Invalid number literal"),
+                    "{}",
+                    source
+                )
+            }
+            other => panic!("{}: expected SyntaxError, got {:?}", source, other),
+        }
+    }
+    let tokens = Lexer::new("1e400 1E-400 2e+3").tokenize().unwrap();
+    assert_eq!(
+        tokens[..3]
+            .iter()
+            .map(|t| t.kind.clone())
+            .collect::<Vec<_>>(),
+        vec![
+            TokenKind::NumberLiteral("1e400".to_string()),
+            TokenKind::NumberLiteral("1E-400".to_string()),
+            TokenKind::NumberLiteral("2e+3".to_string()),
+        ]
+    );
+}
+
 #[test]
 fn test_tokenize_operators() {
     let mut lexer = Lexer::new("+ - * / % ** ++ --");
@@ -58,6 +84,32 @@ fn test_tokenize_operators() {
     assert_eq!(tokens[7].kind, TokenKind::MinusMinus);
 }
 
+#[test]
+fn test_tokenize_shift_and_comparison_operators() {
+    let mut lexer = Lexer::new("a >>>= b >>= c >>> d >> e >= f > g >>>>= h");
+    let kinds: Vec<TokenKind> = lexer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|t| t.kind)
+        .filter(|k| !matches!(k, TokenKind::Identifier(_) | TokenKind::Eof))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::UnsignedRightShiftEq,
+            TokenKind::RightShiftEq,
+            TokenKind::UnsignedRightShift,
+            TokenKind::RightShift,
+            TokenKind::GtEq,
+            TokenKind::Gt,
+            // 最長一致で `>>>` の後に `>=` が続く
+            TokenKind::UnsignedRightShift,
+            TokenKind::GtEq,
+        ]
+    );
+}
+
 #[test]
 fn test_tokenize_keywords() {
     let mut lexer = Lexer::new("let const var function return");
@@ -89,3 +141,209 @@ fn test_tokenize_identifiers() {
     assert!(matches!(tokens[2].kind, TokenKind::Identifier(ref s) if s == "_test"));
     assert!(matches!(tokens[3].kind, TokenKind::Identifier(ref s) if s == "$value"));
 }
+
+#[test]
+fn test_tokenize_unicode_identifiers() {
+    let mut lexer = Lexer::new("café x\u{0301} a\u{200D}b 変数 2abc \u{0301}y");
+    let kinds: Vec<TokenKind> = lexer
+        .tokenize()
+        .unwrap()
+        .into_iter()
+        .map(|t| t.kind)
+        .filter(|k| *k != TokenKind::Eof)
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::Identifier("café".to_string()),
+            // 結合文字と ZWJ は2文字目以降にだけ使える
+            TokenKind::Identifier("x\u{0301}".to_string()),
+            TokenKind::Identifier("a\u{200D}b".to_string()),
+            TokenKind::Identifier("変数".to_string()),
+            // 数字から始まる部分は識別子に含めない
+            TokenKind::NumberLiteral("2".to_string()),
+            TokenKind::Identifier("abc".to_string()),
+            TokenKind::Unknown('\u{0301}'),
+            TokenKind::Identifier("y".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_identifier_escapes() {
+    let tokens = Lexer::new("\\u0061bc x\\u{30} \\u{0000062} #\\u0069f")
+        .tokenize()
+        .unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::Identifier("abc".to_string()));
+    assert_eq!((tokens[0].span.start, tokens[0].span.end), (0, 8));
+    assert_eq!(tokens[1].kind, TokenKind::Identifier("x0".to_string()));
+    assert_eq!(tokens[2].kind, TokenKind::Identifier("b".to_string()));
+    assert_eq!(
+        tokens[3].kind,
+        TokenKind::PrivateIdentifier("if".to_string())
+    );
+
+    // 先頭に使えない文字・不正なエスケープ・エスケープしたキーワードは構文エラー
+    for source in [
+        "\\u0030abc",
+        "a\\u002e",
+        "\\u00",
+        "\\u{}",
+        "\\u{110000}",
+        "\\x61",
+        "\\u0076ar",
+    ] {
+        assert!(
+            matches!(Lexer::new(source).tokenize(), Err(JSError::SyntaxError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_tokenize_private_identifier() {
+    let tokens = Lexer::new("this.#count").tokenize().unwrap();
+
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::PrivateIdentifier("count".to_string())
+    );
+    assert_eq!((tokens[2].span.start, tokens[2].span.end), (5, 11));
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
+}
+
+#[test]
+fn test_bare_hash_is_positioned_syntax_error() {
+    let err = Lexer::new("let x = 1;\n  # ").tokenize().unwrap_err();
+
+    match err {
+        JSError::SyntaxError(msg) => assert!(msg.starts_with("2:3: "), "{}", msg),
+        other => panic!("Expected SyntaxError, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_tab_width_columns() {
+    let tokens = Lexer::new("\tx").with_tab_width(4).tokenize().unwrap();
+    assert_eq!(tokens[0].span.column, 5);
+
+    // タブストップに揃える
+    let tokens = Lexer::new("ab\tc").with_tab_width(4).tokenize().unwrap();
+    assert_eq!(tokens[1].span.column, 5);
+
+    // デフォルトではタブも1列
+    let tokens = Lexer::new("\tx").tokenize().unwrap();
+    assert_eq!(tokens[0].span.column, 2);
+}
+
+#[test]
+fn test_tokenize_number_with_trailing_dot() {
+    let tokens = Lexer::new("5. + 1").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::NumberLiteral("5.".to_string()));
+    assert_eq!(tokens[1].kind, TokenKind::Plus);
+
+    // 2つ目の '.' はメンバーアクセス
+    let tokens = Lexer::new("5..toString").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::NumberLiteral("5.".to_string()));
+    assert_eq!(tokens[1].kind, TokenKind::Dot);
+    assert!(matches!(tokens[2].kind, TokenKind::Identifier(ref s) if s == "toString"));
+
+    // 小数部の後の '.' もメンバーアクセス
+    let tokens = Lexer::new("1.5.x").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::NumberLiteral("1.5".to_string()));
+    assert_eq!(tokens[1].kind, TokenKind::Dot);
+}
+
+#[test]
+fn test_comments_are_dropped_by_default() {
+    let tokens = Lexer::new("// hi\n1 /* inline */ + 2 // trailing")
+        .tokenize()
+        .unwrap();
+    let kinds: Vec<TokenKind> = tokens.into_iter().map(|t| t.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![
+            TokenKind::NumberLiteral("1".to_string()),
+            TokenKind::Plus,
+            TokenKind::NumberLiteral("2".to_string()),
+            TokenKind::Eof,
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_with_comments() {
+    let tokens = Lexer::new("// hi\n1 /** doc */")
+        .with_comments(true)
+        .tokenize()
+        .unwrap();
+
+    assert_eq!(tokens[0].kind, TokenKind::Comment("// hi".to_string()));
+    assert_eq!((tokens[0].span.start, tokens[0].span.end), (0, 5));
+    assert_eq!(tokens[1].kind, TokenKind::NumberLiteral("1".to_string()));
+    assert_eq!(tokens[2].kind, TokenKind::Comment("/** doc */".to_string()));
+    assert_eq!((tokens[2].span.line, tokens[2].span.column), (2, 3));
+    assert_eq!(tokens[3].kind, TokenKind::Eof);
+
+    // パーサーは Comment トークンを無視する
+    let program = Parser::new(tokens).parse().unwrap();
+    assert_eq!(program.body.len(), 1);
+}
+
+#[test]
+fn test_shebang_only_on_first_line() {
+    let tokens = Lexer::new("#!/usr/bin/env node\n1").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::NumberLiteral("1".to_string()));
+
+    let tokens = Lexer::new("#!/usr/bin/env node")
+        .with_comments(true)
+        .tokenize()
+        .unwrap();
+    assert_eq!(
+        tokens[0].kind,
+        TokenKind::Comment("#!/usr/bin/env node".to_string())
+    );
+
+    // 先頭以外の # は通常どおりプライベート名の記号として扱う
+    assert!(Lexer::new(" #!x").tokenize().is_err());
+    let tokens = Lexer::new("1;\n#!x").tokenize();
+    assert!(matches!(tokens, Err(JSError::SyntaxError(msg)) if msg.starts_with("2:1: ")));
+    let tokens = Lexer::new("#!\nthis.#x").tokenize().unwrap();
+    assert_eq!(
+        tokens[2].kind,
+        TokenKind::PrivateIdentifier("x".to_string())
+    );
+}
+
+#[test]
+fn test_tokenize_legacy_octal_escapes() {
+    let tokens = Lexer::new(r#""\101\0" "\0" "\8""#).tokenize().unwrap();
+    let strings: Vec<_> = tokens
+        .iter()
+        .filter_map(|token| match &token.kind {
+            TokenKind::String(s) => Some((s.clone(), token.legacy_octal_escape)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        strings,
+        [
+            ("A\0".to_string(), true),
+            ("\0".to_string(), false),
+            ("8".to_string(), true),
+        ]
+    );
+}
+
+#[test]
+fn test_tokenize_line_continuation() {
+    let tokens = Lexer::new("\"a\\\nb\" 'c\\\r\nd' x").tokenize().unwrap();
+    assert_eq!(tokens[0].kind, TokenKind::String("ab".to_string()));
+    assert_eq!(tokens[1].kind, TokenKind::String("cd".to_string()));
+    // 行継続の改行も行番号に数える
+    assert_eq!(tokens[2].span.line, 3);
+
+    // エスケープしていない改行は従来どおりエラー
+    assert!(Lexer::new("\"a\nb\"").tokenize().is_err());
+}
@@ -0,0 +1,92 @@
+use pixi_byte::{JSEngine, JSValue};
+
+/// ToBoolean が false になる値（各型の代表）
+const FALSY: [&str; 8] = [
+    "undefined",
+    "null",
+    "false",
+    "0",
+    "-0",
+    "0 / 0",
+    "''",
+    "\"\"",
+];
+
+/// ToBoolean が true になる値（各型の代表）
+const TRUTHY: [&str; 14] = [
+    "true",
+    "1",
+    "-1",
+    "1 / 0",
+    "'0'",
+    "'false'",
+    "' '",
+    "[]",
+    "{}",
+    "[0]",
+    "new Boolean(false)",
+    "new Number(0)",
+    "function() {}",
+    "Object.is",
+];
+
+#[test]
+fn test_not_falsy_values() {
+    let mut engine = JSEngine::new();
+    for source in FALSY {
+        assert_eq!(
+            engine.eval(&format!("!({})", source)).unwrap(),
+            JSValue::Boolean(true),
+            "!({})",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_not_truthy_values() {
+    let mut engine = JSEngine::new();
+    for source in TRUTHY {
+        assert_eq!(
+            engine.eval(&format!("!({})", source)).unwrap(),
+            JSValue::Boolean(false),
+            "!({})",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_double_not_matches_conditions() {
+    // !! と条件分岐（JumpIfFalse）と && / || は同じ ToBoolean で判定する
+    let mut engine = JSEngine::new();
+    for (source, expected) in FALSY
+        .iter()
+        .map(|s| (s, false))
+        .chain(TRUTHY.iter().map(|s| (s, true)))
+    {
+        let value = JSValue::Boolean(expected);
+        for template in [
+            "!!({})",
+            "({}) ? true : false",
+            "!(({}) && true) === false",
+            "!!(({}) || false)",
+        ] {
+            let code = template.replace("{}", source);
+            assert_eq!(engine.eval(&code).unwrap(), value, "{}", code);
+        }
+    }
+}
+
+#[test]
+fn test_not_on_variables_and_results() {
+    let mut engine = JSEngine::new();
+    engine.eval("let empty = []; let s = '';").unwrap();
+    assert_eq!(engine.eval("!empty").unwrap(), JSValue::Boolean(false));
+    assert_eq!(engine.eval("!s").unwrap(), JSValue::Boolean(true));
+    assert_eq!(engine.eval("![].length").unwrap(), JSValue::Boolean(true));
+    assert_eq!(
+        engine.eval("!!typeof undefined").unwrap(),
+        JSValue::Boolean(true)
+    );
+}
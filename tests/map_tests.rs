@@ -0,0 +1,172 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_map_object_and_string_keys() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        let key = {};
+        let m = new Map();
+        m.set(key, "object").set("key", "string");
+        m.get(key) + " " + m.get("key")
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("object string".to_string()));
+
+    // 別のオブジェクトは同じ形でも別のキー
+    assert_eq!(engine.eval("m.get({})").unwrap(), JSValue::Undefined);
+    assert_eq!(engine.eval("m.has(key)").unwrap(), JSValue::Boolean(true));
+}
+
+#[test]
+fn test_map_size_reflects_deletions() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(r#"let m = new Map(); m.set("a", 1); m.set("b", 2); m.set("a", 3);"#)
+        .unwrap();
+    assert_eq!(engine.eval("m.size").unwrap(), JSValue::Number(2.0));
+    assert_eq!(engine.eval(r#"m.get("a")"#).unwrap(), JSValue::Number(3.0));
+
+    assert_eq!(
+        engine.eval(r#"m.delete("a")"#).unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval(r#"m.delete("a")"#).unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(engine.eval("m.size").unwrap(), JSValue::Number(1.0));
+
+    engine.eval("m.clear();").unwrap();
+    assert_eq!(engine.eval("m.size").unwrap(), JSValue::Number(0.0));
+}
+
+#[test]
+fn test_map_same_value_zero_keys() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(r#"let m = new Map([[0 / 0, "nan"], [0, "zero"], ["1", "str"]]);"#)
+        .unwrap();
+    assert_eq!(
+        engine.eval("m.get(0 / 0)").unwrap(),
+        JSValue::String("nan".to_string())
+    );
+    assert_eq!(
+        engine.eval("m.get(-0)").unwrap(),
+        JSValue::String("zero".to_string())
+    );
+    // 数値と文字列は区別する
+    assert_eq!(engine.eval("m.get(1)").unwrap(), JSValue::Undefined);
+}
+
+#[test]
+fn test_map_for_each_in_insertion_order() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        let m = new Map([["x", 1], ["y", 2]]);
+        m.set("z", 3);
+        m.delete("y");
+        let out = "";
+        m.forEach(function (value, key) { out = out + key + value; });
+        out
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("x1z3".to_string()));
+}
+
+#[test]
+fn test_map_requires_new() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(engine.eval("Map()"), Err(JSError::TypeError(_))));
+    // Map 以外を this にした呼び出し
+    assert!(matches!(
+        engine.eval(r#"let o = { get: new Map().get }; o.get("a")"#),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_map_inspect() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval(r#"new Map([["a", 1], [2, [3]]])"#)
+            .unwrap()
+            .inspect(),
+        "Map(2) { 'a' => 1, 2 => [ 3 ] }"
+    );
+    assert_eq!(engine.eval("new Map()").unwrap().inspect(), "Map(0) {}");
+}
+
+#[test]
+fn test_map_is_iterable() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function collect(iterable) {
+            let items = [];
+            for (const item of iterable) { items.push(item); }
+            return items;
+        }
+        let m = new Map([["a", 1], ["b", 2]]);
+        let out = "";
+        for (const [k, v] of m) {
+            out = out + k + v;
+            if (k === "a") { m.set("c", 3); }
+        }
+        out
+    "#,
+        )
+        .unwrap();
+    // 走査中に追加したエントリも訪問する
+    assert_eq!(result, JSValue::String("a1b2c3".to_string()));
+    assert_eq!(
+        engine.eval("collect(m.keys()).join()").unwrap(),
+        JSValue::String("a,b,c".to_string())
+    );
+    assert_eq!(
+        engine.eval("collect(m.values()).join()").unwrap(),
+        JSValue::String("1,2,3".to_string())
+    );
+    assert_eq!(
+        engine.eval("collect(m.entries())").unwrap().inspect(),
+        "[ [ 'a', 1 ], [ 'b', 2 ], [ 'c', 3 ] ]"
+    );
+    assert_eq!(
+        engine
+            .eval("let it = m.keys(); it.next(); it.next(); it.next(); it.next().done")
+            .unwrap(),
+        JSValue::Boolean(true)
+    );
+}
+
+#[test]
+fn test_map_from_iterable() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval(r#"let copy = new Map(new Map([["a", 1], ["b", 2]])); copy.get("b")"#)
+            .unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine
+            .eval(r#"new Map(new Map([["x", 1]]).entries()).size"#)
+            .unwrap(),
+        JSValue::Number(1.0)
+    );
+    assert!(matches!(
+        engine.eval("new Map(1)"),
+        Err(JSError::TypeError(_))
+    ));
+    assert!(matches!(
+        engine.eval("new Map([1])"),
+        Err(JSError::TypeError(_))
+    ));
+}
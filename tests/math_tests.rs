@@ -0,0 +1,28 @@
+use pixi_byte::JSEngine;
+
+#[test]
+fn test_math_random_is_deterministic_with_seed() {
+    let mut first = JSEngine::new();
+    first.seed_random(12345);
+    let a1 = first.eval("Math.random()").unwrap().to_number();
+    let a2 = first.eval("Math.random()").unwrap().to_number();
+
+    let mut second = JSEngine::new();
+    second.seed_random(12345);
+    let b1 = second.eval("Math.random()").unwrap().to_number();
+    let b2 = second.eval("Math.random()").unwrap().to_number();
+
+    assert_eq!(a1, b1);
+    assert_eq!(a2, b2);
+    assert_ne!(a1, a2);
+}
+
+#[test]
+fn test_math_random_range() {
+    let mut engine = JSEngine::new();
+    engine.seed_random(0);
+    for _ in 0..1000 {
+        let n = engine.eval("Math.random()").unwrap().to_number();
+        assert!((0.0..1.0).contains(&n), "out of range: {}", n);
+    }
+}
@@ -0,0 +1,68 @@
+use pixi_byte::{JSEngine, JSValue};
+
+/// 式を評価して真偽値を取り出す
+fn eval_bool(engine: &mut JSEngine, source: &str) -> bool {
+    match engine.eval(source).unwrap() {
+        JSValue::Boolean(b) => b,
+        other => panic!("Expected boolean for {}, got {:?}", source, other),
+    }
+}
+
+#[test]
+fn test_nan_relational_comparisons_are_false() {
+    let mut engine = JSEngine::new();
+    for source in [
+        "NaN < 1",
+        "NaN > 1",
+        "1 < NaN",
+        "1 > NaN",
+        "NaN <= NaN",
+        "NaN >= NaN",
+        "NaN < NaN",
+        "NaN > NaN",
+        "undefined < 1",
+        "undefined >= 0",
+        r#""abc" < 1"#,
+        r#"1 <= "abc""#,
+        "NaN < Infinity",
+        "-Infinity > NaN",
+    ] {
+        assert!(
+            !eval_bool(&mut engine, source),
+            "{} should be false",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_nan_equality() {
+    let mut engine = JSEngine::new();
+    assert!(!eval_bool(&mut engine, "NaN === NaN"));
+    assert!(!eval_bool(&mut engine, "NaN == NaN"));
+    assert!(eval_bool(&mut engine, "NaN !== NaN"));
+    assert!(eval_bool(&mut engine, "NaN != NaN"));
+    assert!(!eval_bool(&mut engine, r#"NaN == "NaN""#));
+    // グローバルの NaN / Infinity は数値
+    assert_eq!(
+        engine.eval("typeof NaN").unwrap(),
+        JSValue::String("number".to_string())
+    );
+    assert!(eval_bool(&mut engine, "Infinity === 1 / 0"));
+    assert!(eval_bool(&mut engine, "-Infinity < -1e308"));
+}
+
+#[test]
+fn test_string_relational_comparison() {
+    let mut engine = JSEngine::new();
+
+    assert!(eval_bool(&mut engine, r#""a" < "b""#));
+    assert!(eval_bool(&mut engine, r#""abc" < "abd""#));
+    assert!(eval_bool(&mut engine, r#""ab" < "abc""#));
+    // 数値としてではなく辞書順で比較される
+    assert!(eval_bool(&mut engine, r#""10" < "9""#));
+    assert!(eval_bool(&mut engine, r#""B" < "a""#));
+    assert!(eval_bool(&mut engine, r#""a" >= "a""#));
+    // 片方が数値なら数値比較
+    assert!(!eval_bool(&mut engine, r#""10" < 9"#));
+}
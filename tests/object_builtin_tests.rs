@@ -0,0 +1,297 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_object_is() {
+    let mut engine = JSEngine::new();
+
+    assert_eq!(
+        engine.eval("Object.is(NaN, NaN)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("Object.is(NaN, 0 / 0)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("Object.is(NaN, undefined)").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("Object.is(-0, 0)").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("Object.is(1, 1)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    // === とは異なる
+    assert_eq!(engine.eval("-0 === 0").unwrap(), JSValue::Boolean(true));
+    assert_eq!(
+        engine.eval("Object.is(-0, -0)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval(r#"Object.is("a", "a")"#).unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("Object.is({}, {})").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("let o = {}; Object.is(o, o)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(engine.eval("Object.is()").unwrap(), JSValue::Boolean(true));
+}
+
+#[test]
+fn test_same_value_zero() {
+    assert!(JSValue::Number(f64::NAN).same_value_zero(&JSValue::Number(f64::NAN)));
+    assert!(JSValue::Number(-0.0).same_value_zero(&JSValue::Number(0.0)));
+    assert!(!JSValue::Number(-0.0).same_value(&JSValue::Number(0.0)));
+}
+
+#[test]
+fn test_define_non_enumerable_property() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("var o = { a: 1 }; Object.defineProperty(o, 'hidden', { value: 2 });")
+        .unwrap();
+
+    assert_eq!(engine.eval("o.hidden").unwrap(), JSValue::Number(2.0));
+    assert_eq!(engine.eval("Object.keys(o)").unwrap().inspect(), "[ 'a' ]");
+    assert_eq!(
+        engine
+            .eval("Object.getOwnPropertyDescriptor(o, 'hidden')")
+            .unwrap()
+            .inspect(),
+        "{ value: 2, writable: false, enumerable: false, configurable: false }"
+    );
+    // 書き込み不可・再定義不可
+    assert_eq!(
+        engine.eval("o.hidden = 3; o.hidden").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert!(matches!(
+        engine.eval("Object.defineProperty(o, 'hidden', { value: 3 })"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_define_property_keeps_omitted_attributes() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("var o = { a: 1, b: 2 }; Object.defineProperty(o, 'a', { enumerable: false });")
+        .unwrap();
+
+    assert_eq!(
+        engine
+            .eval("Object.getOwnPropertyDescriptor(o, 'a')")
+            .unwrap()
+            .inspect(),
+        "{ value: 1, writable: true, enumerable: false, configurable: true }"
+    );
+    assert_eq!(engine.eval("Object.keys(o)").unwrap().inspect(), "[ 'b' ]");
+    assert_eq!(
+        engine
+            .eval("Object.getOwnPropertyDescriptor(o, 'missing')")
+            .unwrap(),
+        JSValue::Undefined
+    );
+    assert_eq!(
+        engine
+            .eval("Object.defineProperty(o, 'o', o) === o")
+            .unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert!(matches!(
+        engine.eval("Object.defineProperty(1, 'x', {})"),
+        Err(JSError::TypeError(_))
+    ));
+    assert!(matches!(
+        engine.eval("Object.keys(null)"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_define_accessor_property() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval(r#"let o = {}; Object.defineProperty(o, "x", { get: function(){ return 42; } }); o.x"#)
+            .unwrap(),
+        JSValue::Number(42.0)
+    );
+    // setter のないアクセサプロパティへの代入は何もしない
+    assert_eq!(engine.eval("o.x = 1; o.x").unwrap(), JSValue::Number(42.0));
+    assert_eq!(engine.eval("o['x']").unwrap(), JSValue::Number(42.0));
+
+    engine
+        .eval(
+            r#"
+        var log = [];
+        var c = 0;
+        var temp = { c: 0 };
+        Object.defineProperty(temp, "f", {
+            get: function() { return c * 9 / 5 + 32; },
+            set: function(v) { log.push(v); c = (v - 32) * 5 / 9; },
+            enumerable: true
+        });
+    "#,
+        )
+        .unwrap();
+    assert_eq!(engine.eval("temp.f = 212").unwrap(), JSValue::Number(212.0));
+    assert_eq!(engine.eval("c").unwrap(), JSValue::Number(100.0));
+    assert_eq!(engine.eval("temp.f").unwrap(), JSValue::Number(212.0));
+    assert_eq!(engine.eval("log.length").unwrap(), JSValue::Number(1.0));
+    assert_eq!(
+        engine.eval("temp").unwrap().inspect(),
+        "{ c: 0, f: [Getter/Setter] }"
+    );
+    assert_eq!(
+        engine
+            .eval("Object.getOwnPropertyDescriptor(o, 'x').set")
+            .unwrap(),
+        JSValue::Undefined
+    );
+    assert_eq!(
+        engine
+            .eval("typeof Object.getOwnPropertyDescriptor(o, 'x').get")
+            .unwrap(),
+        JSValue::String("function".to_string())
+    );
+
+    // get と value の併用・関数でない getter は TypeError
+    for source in [
+        "Object.defineProperty({}, 'y', { get: function() {}, value: 1 })",
+        "Object.defineProperty({}, 'y', { get: 1 })",
+    ] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::TypeError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_object_create() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval(r#"let base = { greet: function() { return "hi"; } }; let o = Object.create(base);"#)
+        .unwrap();
+    assert_eq!(
+        engine.eval("o.greet()").unwrap(),
+        JSValue::String("hi".to_string())
+    );
+    // greet は継承したプロパティで、自身のプロパティではない
+    assert_eq!(
+        engine
+            .eval(r#"Object.getOwnPropertyDescriptor(o, "greet")"#)
+            .unwrap(),
+        JSValue::Undefined
+    );
+    assert_eq!(
+        engine.eval("Object.create(null).toString").unwrap(),
+        JSValue::Undefined
+    );
+    // 第2引数のディスクリプタでプロパティを定義する
+    assert_eq!(
+        engine
+            .eval("let p = Object.create(base, { x: { value: 1, enumerable: true }, y: { value: 2 } }); [p.x, p.y, Object.keys(p).length].join()")
+            .unwrap(),
+        JSValue::String("1,2,1".to_string())
+    );
+    for source in [
+        "Object.create(1)",
+        "Object.create()",
+        "Object.create({}, { x: 1 })",
+    ] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::TypeError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_has_own_property_and_is_prototype_of() {
+    let mut engine = JSEngine::new();
+    engine.eval("let o = { x: 1 };").unwrap();
+    let t = JSValue::Boolean(true);
+    let f = JSValue::Boolean(false);
+    assert_eq!(engine.eval(r#"o.hasOwnProperty("x")"#).unwrap(), t);
+    // 継承したプロパティは自身のプロパティではない
+    assert_eq!(
+        engine
+            .eval(r#"o.hasOwnProperty("hasOwnProperty")"#)
+            .unwrap(),
+        f
+    );
+    assert_eq!(engine.eval(r#"[1].hasOwnProperty("0")"#).unwrap(), t);
+    assert_eq!(engine.eval(r#"[1].hasOwnProperty("push")"#).unwrap(), f);
+    assert_eq!(engine.eval(r#""ab".hasOwnProperty("length")"#).unwrap(), t);
+    assert_eq!(engine.eval("Object.prototype.isPrototypeOf(o)").unwrap(), t);
+    assert_eq!(
+        engine.eval("Object.prototype.isPrototypeOf([])").unwrap(),
+        t
+    );
+    assert_eq!(
+        engine
+            .eval("let child = Object.create(o); o.isPrototypeOf(child)")
+            .unwrap(),
+        t
+    );
+    assert_eq!(engine.eval("child.isPrototypeOf(o)").unwrap(), f);
+    assert_eq!(engine.eval("o.isPrototypeOf(1)").unwrap(), f);
+    assert!(matches!(
+        engine.eval("Object.prototype.hasOwnProperty.bind(null)('x')"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_object_prototype_is_inherited_by_default() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("({}).toString()", "[object Object]"),
+        ("String({ a: 1 })", "[object Object]"),
+        ("Object.prototype.toString.bind([])()", "[object Array]"),
+        ("Object.prototype.toString.bind(null)()", "[object Null]"),
+        ("Object.prototype.toString.bind(1)()", "[object Number]"),
+        (
+            "Object.prototype.toString.bind(function() {})()",
+            "[object Function]",
+        ),
+        (
+            "(function() { return arguments.toString(); })()",
+            "[object Object]",
+        ),
+        ("Math.toString()", "[object Object]"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::String(expected.to_string()),
+            "{}",
+            source
+        );
+    }
+    assert_eq!(
+        engine.eval("let o = {}; o.valueOf() === o").unwrap(),
+        JSValue::Boolean(true)
+    );
+    // Object.create(null) はプロトタイプを持たない
+    assert_eq!(
+        engine.eval("Object.create(null).toString").unwrap(),
+        JSValue::Undefined
+    );
+    assert!(matches!(
+        engine.eval("Object.create(null) + ''"),
+        Err(JSError::TypeError(_))
+    ));
+}
@@ -1,4 +1,4 @@
-use pixi_byte::{Lexer, Parser};
+use pixi_byte::{JSEngine, JSError, JSValue, Lexer, Parser};
 
 #[test]
 fn test_parse_literal() {
@@ -29,3 +29,417 @@ fn test_parse_var_declaration() {
 
     assert_eq!(program.body.len(), 1);
 }
+
+#[test]
+fn test_parse_trailing_comma_in_call() {
+    use pixi_byte::parser::{ExpressionKind, StatementKind};
+
+    let tokens = Lexer::new("f(1, 2,)").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    match &program.body[0].kind {
+        StatementKind::Expression(expr) => match &expr.kind {
+            ExpressionKind::Call { args, .. } => assert_eq!(args.len(), 2),
+            other => panic!("Expected call expression, got {:?}", other),
+        },
+        other => panic!("Expected call expression, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_trailing_comma_in_params() {
+    use pixi_byte::parser::StatementKind;
+
+    let tokens = Lexer::new("function g(a, b,) { return a + b; }")
+        .tokenize()
+        .unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    match &program.body[0].kind {
+        StatementKind::FunctionDeclaration { params, .. } => assert_eq!(params, &["a", "b"]),
+        other => panic!("Expected function declaration, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_binary_expression_span() {
+    use pixi_byte::parser::{ExpressionKind, StatementKind};
+
+    let tokens = Lexer::new("  1 + 2").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let StatementKind::Expression(expr) = &program.body[0].kind else {
+        panic!("Expected expression statement");
+    };
+    assert!(matches!(expr.kind, ExpressionKind::Binary { .. }));
+    // `1` から `2` までを覆う
+    assert_eq!(expr.span.line, 1);
+    assert_eq!(expr.span.column, 3);
+    assert_eq!(expr.span.start, 2);
+    assert_eq!(expr.span.end, 7);
+}
+
+#[test]
+fn test_statement_and_nested_spans() {
+    use pixi_byte::parser::{ExpressionKind, StatementKind};
+
+    let tokens = Lexer::new("let x = 1;\nf(a, b);").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    // 文はキーワードからセミコロンまで
+    let decl = &program.body[0];
+    assert_eq!((decl.span.start, decl.span.end), (0, 10));
+
+    let StatementKind::Expression(call) = &program.body[1].kind else {
+        panic!("Expected expression statement");
+    };
+    assert_eq!((call.span.line, call.span.column), (2, 1));
+    let ExpressionKind::Call { args, .. } = &call.kind else {
+        panic!("Expected call expression");
+    };
+    assert_eq!((args[1].span.line, args[1].span.column), (2, 6));
+}
+
+#[test]
+fn test_parse_recovering_collects_errors() {
+    let source = "let = 1;\nlet x = 2;\nfoo(;\nlet y = 3;";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let (program, errors) = Parser::new(tokens.clone()).parse_recovering();
+
+    assert_eq!(errors.len(), 2);
+    // 各エラーは検出した位置を持つ
+    let messages: Vec<String> = errors
+        .iter()
+        .map(|e| match e {
+            JSError::SyntaxError(msg) => msg.clone(),
+            other => panic!("Expected syntax error: {:?}", other),
+        })
+        .collect();
+    assert!(messages[0].starts_with("1:5: "), "{}", messages[0]);
+    assert!(messages[1].starts_with("3:5: "), "{}", messages[1]);
+    // エラーの間にある正しい文は残る
+    assert_eq!(program.body.len(), 2);
+
+    // parse() は最初のエラーで中断する
+    assert!(Parser::new(tokens).parse().is_err());
+}
+
+#[test]
+fn test_parse_recovering_clean_source() {
+    let source = "let x = 1; function f(a) { return a + x; } f(2);";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+
+    let expected = Parser::new(tokens.clone()).parse().unwrap();
+    let (program, errors) = Parser::new(tokens).parse_recovering();
+
+    assert!(errors.is_empty());
+    assert_eq!(format!("{:?}", program), format!("{:?}", expected));
+}
+
+#[test]
+fn test_truncated_token_stream_is_error() {
+    use pixi_byte::TokenKind;
+
+    // Eof を取り除いた途中までのトークン列
+    let mut tokens = Lexer::new("let x = 1 +").tokenize().unwrap();
+    assert_eq!(tokens.pop().map(|t| t.kind), Some(TokenKind::Eof));
+
+    assert!(matches!(
+        Parser::new(tokens.clone()).parse(),
+        Err(JSError::InternalError(_))
+    ));
+    let (_, errors) = Parser::new(tokens).parse_recovering();
+    assert_eq!(errors.len(), 1);
+
+    assert!(matches!(
+        Parser::new(Vec::new()).parse(),
+        Err(JSError::InternalError(_))
+    ));
+}
+
+#[test]
+fn test_malformed_number_token_is_error() {
+    use pixi_byte::TokenKind;
+
+    let mut tokens = Lexer::new("1").tokenize().unwrap();
+    tokens[0].kind = TokenKind::NumberLiteral("1.2.3".to_string());
+
+    assert!(matches!(
+        Parser::new(tokens).parse(),
+        Err(JSError::SyntaxError(_))
+    ));
+}
+
+#[test]
+fn test_unexpected_end_of_input_is_syntax_error() {
+    for source in [
+        "1 +",
+        "let x =",
+        "let",
+        "f(",
+        "f(a,",
+        "[1,",
+        "({ a:",
+        "obj.",
+        "obj[",
+        "new",
+        "function f(",
+        "function f() {",
+        "try {",
+        "try {} catch (",
+        "true ?",
+        "true ? 1 :",
+        "-",
+    ] {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        let result = Parser::new(tokens).parse();
+        assert!(
+            matches!(result, Err(JSError::SyntaxError(_))),
+            "{:?} should be a SyntaxError, got {:?}",
+            source,
+            result
+        );
+    }
+}
+
+#[test]
+fn test_deep_nesting_is_syntax_error() {
+    // 上限までの再帰はデバッグビルドで数 MB のスタックを使うため、
+    // メインスレッドの標準的なスタック（8MB）と同じ大きさのスレッドで検証する
+    let messages = std::thread::Builder::new()
+        .stack_size(8 << 20)
+        .spawn(|| {
+            [
+                "(".repeat(5000) + "1" + &")".repeat(5000),
+                "!".repeat(5000) + "1",
+                "[".repeat(3000) + &"]".repeat(3000),
+                "(()=>".repeat(3000) + "1" + &")".repeat(3000),
+                "f(".repeat(3000) + &")".repeat(3000),
+                "new ".repeat(3000) + "F",
+                "{".repeat(3000) + &"}".repeat(3000),
+            ]
+            .map(|source| {
+                let tokens = Lexer::new(&source).tokenize().unwrap();
+                match Parser::new(tokens).parse() {
+                    Err(JSError::SyntaxError(msg)) => msg,
+                    other => format!("unexpected result: {:?}", other.map(|_| ())),
+                }
+            })
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+
+    for msg in messages {
+        assert_eq!(msg, "Maximum parse depth exceeded");
+    }
+}
+
+#[test]
+fn test_long_left_associative_chains_evaluate() {
+    // 連鎖は再帰しないため、ネストの深さの上限を超える長さでも評価できる
+    let eval = |source: String| JSEngine::new().eval(&source).unwrap();
+
+    assert_eq!(eval("1 + ".repeat(1000) + "1"), JSValue::Number(1001.0));
+    assert_eq!(
+        eval("var o = {}; o.o = o; o".to_string() + &".o".repeat(1000) + " === o"),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        eval("function f() { return f; } f".to_string() + &"()".repeat(1000) + " === f"),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        eval("'a' + ".repeat(150) + "'a'"),
+        JSValue::String("a".repeat(151))
+    );
+
+    // 解放とコンパイルも再帰しないため、既定のテストスレッドのスタックで足りる
+    assert_eq!(eval("1+".repeat(50000) + "1"), JSValue::Number(50001.0));
+    let tokens = Lexer::new(&("x".to_string() + &"[0]".repeat(50000)))
+        .tokenize()
+        .unwrap();
+    drop(Parser::new(tokens).parse().unwrap());
+}
+
+#[test]
+fn test_max_depth_is_configurable() {
+    let source = "((((1))))";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    assert!(
+        Parser::new(tokens.clone())
+            .with_max_depth(3)
+            .parse()
+            .is_err()
+    );
+    assert!(Parser::new(tokens).with_max_depth(6).parse().is_ok());
+
+    // 上限に達しなければ深いネストも通常どおりパースできる
+    let source = format!("{}1{}", "(".repeat(50), ")".repeat(50));
+    let tokens = Lexer::new(&source).tokenize().unwrap();
+    assert!(Parser::new(tokens).parse().is_ok());
+}
+
+#[test]
+fn test_import_export_require_module_goal() {
+    use pixi_byte::parser::{ImportSpecifier, ParseGoal, StatementKind, VarKind};
+
+    let parse = |source: &str, goal| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Parser::new(tokens).with_goal(goal).parse()
+    };
+
+    let program = parse("export const x = 1", ParseGoal::Module).unwrap();
+    match &program.body[0].kind {
+        StatementKind::Export(declaration) => assert!(matches!(
+            &declaration.kind,
+            StatementKind::VariableDeclaration { kind: VarKind::Const, name, .. } if name == "x"
+        )),
+        other => panic!("Expected export declaration, got {:?}", other),
+    }
+    assert!(matches!(
+        parse("export const x = 1", ParseGoal::Script),
+        Err(JSError::SyntaxError(_))
+    ));
+
+    let program = parse(
+        r#"import d, { a, b as c } from "m"; import * as ns from "n"; import "o";"#,
+        ParseGoal::Module,
+    )
+    .unwrap();
+    let imports: Vec<_> = program
+        .body
+        .iter()
+        .map(|statement| match &statement.kind {
+            StatementKind::Import { specifiers, source } => (specifiers.clone(), source.clone()),
+            other => panic!("Expected import declaration, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(
+        imports,
+        [
+            (
+                vec![
+                    ImportSpecifier::Default("d".to_string()),
+                    ImportSpecifier::Named {
+                        imported: "a".to_string(),
+                        local: "a".to_string()
+                    },
+                    ImportSpecifier::Named {
+                        imported: "b".to_string(),
+                        local: "c".to_string()
+                    },
+                ],
+                "m".to_string()
+            ),
+            (
+                vec![ImportSpecifier::Namespace("ns".to_string())],
+                "n".to_string()
+            ),
+            (vec![], "o".to_string()),
+        ]
+    );
+    assert!(matches!(
+        parse(r#"import "m""#, ParseGoal::Script),
+        Err(JSError::SyntaxError(_))
+    ));
+
+    // モジュールでもトップレベル以外には書けない
+    for source in [
+        "try { export var y = 1; } catch {}",
+        "export 1",
+        "import { a } \"m\"",
+    ] {
+        assert!(
+            matches!(
+                parse(source, ParseGoal::Module),
+                Err(JSError::SyntaxError(_))
+            ),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_with_statement_is_rejected() {
+    let parse = |source: &str| Parser::new(Lexer::new(source).tokenize().unwrap()).parse();
+
+    for source in ["with (o) { x }", "function f(o) { with (o) { return x; } }"] {
+        match parse(source) {
+            Err(JSError::SyntaxError(msg)) => {
+                assert_eq!(msg, "'with' statements are not supported", "{}", source)
+            }
+            other => panic!("Expected SyntaxError for {}, got {:?}", source, other),
+        }
+    }
+    // 変数名にはできないが、プロパティ名としては使える
+    assert!(parse("let with = 1").is_err());
+    assert!(parse("o.with; ({ with: 1 })").is_ok());
+}
+
+#[test]
+fn test_block_statement_at_statement_position() {
+    use pixi_byte::parser::{ExpressionKind, StatementKind};
+
+    let parse = |source: &str| {
+        Parser::new(Lexer::new(source).tokenize().unwrap())
+            .parse()
+            .unwrap()
+    };
+
+    let program = parse("{ let x = 5; }");
+    match &program.body[0].kind {
+        StatementKind::Block(body) => assert!(matches!(
+            &body[..],
+            [pixi_byte::parser::Statement {
+                kind: StatementKind::VariableDeclaration { .. },
+                ..
+            }]
+        )),
+        other => panic!("Expected block statement, got {:?}", other),
+    }
+
+    // 式の位置の `{` はオブジェクトリテラル
+    for source in ["let o = { x: 5 }", "({ x: 5 })"] {
+        let program = parse(source);
+        let expr = match &program.body[0].kind {
+            StatementKind::VariableDeclaration {
+                init: Some(init), ..
+            } => init,
+            StatementKind::Expression(expr) => expr,
+            other => panic!("Unexpected statement {:?}", other),
+        };
+        assert!(
+            matches!(expr.kind, ExpressionKind::ObjectLiteral(_)),
+            "{}",
+            source
+        );
+    }
+
+    assert!(matches!(
+        parse("if (a) {} {}").body[1].kind,
+        StatementKind::Block(_)
+    ));
+}
+
+#[test]
+fn test_invalid_assignment_target_is_positioned_syntax_error() {
+    let mut engine = JSEngine::new();
+    for (source, expected) in [
+        ("1 = 2", "1:1: Invalid left-hand side in assignment"),
+        (
+            "let a = 0;\n  f() = 3",
+            "2:3: Invalid left-hand side in assignment",
+        ),
+        ("x = a + b = 1", "1:5: Invalid left-hand side in assignment"),
+    ] {
+        match engine.eval(source) {
+            Err(JSError::SyntaxError(message)) => assert_eq!(message, expected, "{}", source),
+            other => panic!("{}: expected SyntaxError, got {:?}", source, other),
+        }
+    }
+    // 括弧で囲んだ識別子やメンバー参照は代入先になる
+    let tokens = Lexer::new("(a) = 1; o.p = 2; o[k] = 3").tokenize().unwrap();
+    assert!(Parser::new(tokens).parse().is_ok());
+}
@@ -0,0 +1,141 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_set_dedupes_on_construction() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("new Set([1, 1, 2]).size").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine.eval("new Set([1, 1, 2]).has(1)").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval(r#"new Set([1, 1, 2]).has("1")"#).unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(engine.eval("new Set().size").unwrap(), JSValue::Number(0.0));
+}
+
+#[test]
+fn test_set_add_existing_value() {
+    let mut engine = JSEngine::new();
+    engine
+        .eval("let o = {}; let s = new Set(); s.add(o).add(0 / 0);")
+        .unwrap();
+    assert_eq!(engine.eval("s.size").unwrap(), JSValue::Number(2.0));
+
+    // 既存の値（同じ参照・NaN・-0 と +0）を追加しても増えない
+    engine
+        .eval("s.add(o); s.add(0 / 0); s.add(0); s.add(-0);")
+        .unwrap();
+    assert_eq!(engine.eval("s.size").unwrap(), JSValue::Number(3.0));
+    assert_eq!(engine.eval("s.has({})").unwrap(), JSValue::Boolean(false));
+
+    assert_eq!(engine.eval("s.delete(o)").unwrap(), JSValue::Boolean(true));
+    assert_eq!(engine.eval("s.size").unwrap(), JSValue::Number(2.0));
+}
+
+#[test]
+fn test_set_for_each_in_insertion_order() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        let s = new Set(["b", "a", "b", "c"]);
+        let out = "";
+        s.forEach(function (value) { out = out + value; });
+        out
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("bac".to_string()));
+}
+
+#[test]
+fn test_set_errors_and_inspect() {
+    let mut engine = JSEngine::new();
+    assert!(matches!(engine.eval("Set()"), Err(JSError::TypeError(_))));
+    assert!(matches!(
+        engine.eval("new Set(1)"),
+        Err(JSError::TypeError(_))
+    ));
+    assert_eq!(
+        engine.eval(r#"new Set([1, "a", 1])"#).unwrap().inspect(),
+        "Set(2) { 1, 'a' }"
+    );
+}
+
+#[test]
+fn test_set_structured_clone_is_independent() {
+    let mut engine = JSEngine::new();
+    let original = engine.eval("let s = new Set([1, [2]]); s").unwrap();
+    let cloned = original.structured_clone().unwrap();
+
+    engine.eval("s.add(3);").unwrap();
+    assert_eq!(cloned.inspect(), "Set(2) { 1, [ 2 ] }");
+    assert_eq!(original.inspect(), "Set(3) { 1, [ 2 ], 3 }");
+}
+
+#[test]
+fn test_set_is_iterable() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function collect(iterable) {
+            let items = [];
+            for (const item of iterable) { items.push(item); }
+            return items;
+        }
+        let s = new Set([3, 1, 3, 2]);
+        let out = "";
+        for (const v of s) { out = out + v; }
+        out
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("312".to_string()));
+    assert_eq!(
+        engine
+            .eval("function sum(a, b, c) { return a + b + c; } sum(...s)")
+            .unwrap(),
+        JSValue::Number(6.0)
+    );
+    assert_eq!(
+        engine.eval("collect(s.keys()).join()").unwrap(),
+        JSValue::String("3,1,2".to_string())
+    );
+    assert_eq!(
+        engine.eval("collect(s.values()).join()").unwrap(),
+        JSValue::String("3,1,2".to_string())
+    );
+    assert_eq!(
+        engine.eval("collect(s.entries())").unwrap().inspect(),
+        "[ [ 3, 3 ], [ 1, 1 ], [ 2, 2 ] ]"
+    );
+}
+
+#[test]
+fn test_set_from_iterable() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#"new Set("abca").size"#).unwrap(),
+        JSValue::Number(3.0)
+    );
+    assert_eq!(
+        engine.eval("new Set(new Set([1, 2, 2])).size").unwrap(),
+        JSValue::Number(2.0)
+    );
+    assert_eq!(
+        engine
+            .eval(r#"new Set(new Map([["a", 1]]).keys()).has("a")"#)
+            .unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert!(matches!(
+        engine.eval("new Set(1)"),
+        Err(JSError::TypeError(_))
+    ));
+}
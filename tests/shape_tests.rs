@@ -0,0 +1,69 @@
+use pixi_byte::value::{JSObject, Shape};
+use pixi_byte::{JSEngine, JSValue};
+
+fn object_with(keys: &[&str]) -> JSObject {
+    let mut obj = JSObject::new();
+    for (i, key) in keys.iter().enumerate() {
+        obj.set(key.to_string(), JSValue::Number(i as f64));
+    }
+    obj
+}
+
+#[test]
+fn test_same_key_order_shares_shape() {
+    let a = object_with(&["x", "y"]);
+    let b = object_with(&["x", "y"]);
+    assert_eq!(a.shape(), b.shape());
+
+    // 順序が違えば別のシェイプ
+    assert_ne!(a.shape(), object_with(&["y", "x"]).shape());
+    assert_eq!(JSObject::new().shape(), Shape::root().id());
+
+    // オブジェクトリテラルも同じ遷移を辿る
+    let mut engine = JSEngine::new();
+    let p = engine
+        .eval("({ x: 1, y: 2 })")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    let q = engine
+        .eval("({ x: 'a', y: 'b' })")
+        .unwrap()
+        .as_object()
+        .unwrap();
+    assert_eq!(p.borrow().shape(), q.borrow().shape());
+}
+
+#[test]
+fn test_delete_transitions_shape() {
+    let mut obj = object_with(&["a", "b", "c"]);
+    assert!(obj.delete("b"));
+    // 残りのキーを最初から追加したのと同じシェイプ
+    assert_eq!(obj.shape(), object_with(&["a", "c"]).shape());
+    assert_eq!(obj.keys(), ["a", "c"]);
+    assert_eq!(obj.get("a"), JSValue::Number(0.0));
+    assert_eq!(obj.get("c"), JSValue::Number(2.0));
+
+    obj.set("b".to_string(), JSValue::Number(9.0));
+    assert_eq!(obj.shape(), object_with(&["a", "c", "b"]).shape());
+    assert_eq!(obj.keys(), ["a", "c", "b"]);
+}
+
+#[test]
+fn test_many_keys_use_dictionary_mode() {
+    let keys: Vec<String> = (0..100).map(|i| format!("k{}", i)).collect();
+    let refs: Vec<&str> = keys.iter().map(String::as_str).collect();
+    let mut a = object_with(&refs);
+    let b = object_with(&refs);
+
+    // キーの多いオブジェクトはシェイプを共有しない
+    assert_ne!(a.shape(), b.shape());
+    assert_eq!(a.keys(), keys);
+    assert_eq!(a.get("k99"), JSValue::Number(99.0));
+
+    let before = a.shape();
+    assert!(a.delete("k0"));
+    assert_ne!(a.shape(), before);
+    assert_eq!(a.get("k1"), JSValue::Number(1.0));
+    assert_eq!(a.keys().len(), 99);
+}
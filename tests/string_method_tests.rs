@@ -0,0 +1,139 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+fn string(s: &str) -> JSValue {
+    JSValue::String(s.to_string())
+}
+
+#[test]
+fn test_string_length_and_index() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#""hello".length"#).unwrap(),
+        JSValue::Number(5.0)
+    );
+    assert_eq!(engine.eval(r#""héllo"[1]"#).unwrap(), string("é"));
+    assert_eq!(engine.eval(r#""abc".charAt(2)"#).unwrap(), string("c"));
+    assert_eq!(engine.eval(r#""abc".charAt(3)"#).unwrap(), string(""));
+    assert_eq!(engine.eval(r#""abc"[3]"#).unwrap(), JSValue::Undefined);
+}
+
+#[test]
+fn test_string_trim() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval(r#""  hi  ".trim()"#).unwrap(), string("hi"));
+    assert_eq!(
+        engine.eval(r#""\n hi\t".trimStart()"#).unwrap(),
+        string("hi\t")
+    );
+    assert_eq!(engine.eval(r#"" hi ".trimEnd()"#).unwrap(), string(" hi"));
+}
+
+#[test]
+fn test_string_repeat() {
+    let mut engine = JSEngine::new();
+    assert_eq!(engine.eval(r#""ab".repeat(3)"#).unwrap(), string("ababab"));
+    assert_eq!(engine.eval(r#""ab".repeat(0)"#).unwrap(), string(""));
+    assert!(matches!(
+        engine.eval(r#""ab".repeat(-1)"#),
+        Err(JSError::RangeError(_))
+    ));
+    // 結果が長すぎる場合は確保せずに RangeError
+    for source in [r#""ab".repeat(2 ** 62)"#, r#""ab".repeat(2 ** 40)"#] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::RangeError(_))),
+            "{}",
+            source
+        );
+    }
+    assert_eq!(engine.eval(r#"''.repeat(2 ** 62)"#).unwrap(), string(""));
+}
+
+#[test]
+fn test_string_replace_first_occurrence() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#""hello".replace("l", "L")"#).unwrap(),
+        string("heLlo")
+    );
+    assert_eq!(
+        engine.eval(r#""hello".replace("x", "L")"#).unwrap(),
+        string("hello")
+    );
+    assert_eq!(
+        engine
+            .eval(r#""a-b".replace("-", function (m, i) { return "[" + m + i + "]"; })"#)
+            .unwrap(),
+        string("a[-1]b")
+    );
+}
+
+#[test]
+fn test_string_pad() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#""5".padStart(3, "0")"#).unwrap(),
+        string("005")
+    );
+    assert_eq!(
+        engine.eval(r#""5".padEnd(4, "ab")"#).unwrap(),
+        string("5aba")
+    );
+    assert_eq!(engine.eval(r#""abc".padStart(2)"#).unwrap(), string("abc"));
+    assert_eq!(engine.eval(r#""x".padStart(3)"#).unwrap(), string("  x"));
+    for source in [r#""a".padStart(2 ** 62)"#, r#""a".padEnd(2 ** 40, "xy")"#] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::RangeError(_))),
+            "{}",
+            source
+        );
+    }
+}
+
+#[test]
+fn test_string_includes() {
+    let mut engine = JSEngine::new();
+    let t = JSValue::Boolean(true);
+    let f = JSValue::Boolean(false);
+    assert_eq!(engine.eval(r#""hello".includes("ell")"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".includes("elo")"#).unwrap(), f);
+    assert_eq!(engine.eval(r#""hello".includes("")"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".includes("h", 1)"#).unwrap(), f);
+}
+
+#[test]
+fn test_string_starts_with() {
+    let mut engine = JSEngine::new();
+    let t = JSValue::Boolean(true);
+    let f = JSValue::Boolean(false);
+    assert_eq!(engine.eval(r#""hello".startsWith("he")"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".startsWith("lo")"#).unwrap(), f);
+    // 位置は文字単位
+    assert_eq!(engine.eval(r#""héllo".startsWith("llo", 2)"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".startsWith("h", -5)"#).unwrap(), t);
+}
+
+#[test]
+fn test_string_ends_with() {
+    let mut engine = JSEngine::new();
+    let t = JSValue::Boolean(true);
+    let f = JSValue::Boolean(false);
+    assert_eq!(engine.eval(r#""hello".endsWith("lo")"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".endsWith("he")"#).unwrap(), f);
+    assert_eq!(engine.eval(r#""héllo".endsWith("hé", 2)"#).unwrap(), t);
+    assert_eq!(engine.eval(r#""hello".endsWith("o", 100)"#).unwrap(), t);
+}
+
+#[test]
+fn test_string_at() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval(r#""abc".at(-1)"#).unwrap(),
+        JSValue::String("c".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#""héllo".at(1)"#).unwrap(),
+        JSValue::String("é".to_string())
+    );
+    assert_eq!(engine.eval(r#""abc".at(3)"#).unwrap(), JSValue::Undefined);
+    assert_eq!(engine.eval(r#""abc".at(-4)"#).unwrap(), JSValue::Undefined);
+}
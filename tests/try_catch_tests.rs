@@ -0,0 +1,128 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_catch_without_binding() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("try { throw 1; } catch { 99 }").unwrap();
+    assert_eq!(result, JSValue::Number(99.0));
+}
+
+#[test]
+fn test_catch_with_binding() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("try { throw 41; } catch (e) { e + 1 }")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(42.0));
+}
+
+#[test]
+fn test_catch_binding_shadows_outer_binding() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("let e = 1; try { throw 2; } catch (e) { e } e")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(1.0));
+
+    let result = engine
+        .eval("const c = 1; try { throw 2; } catch (c) { c } c")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(1.0));
+
+    let result = engine
+        .eval(
+            r#"
+        function f(e) { try { throw 2; } catch (e) {} return e; }
+        f(3)
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::Number(3.0));
+}
+
+#[test]
+fn test_catch_binding_does_not_leak() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval("try { throw 1; } catch (err) {} typeof err")
+        .unwrap();
+    assert_eq!(result, JSValue::String("undefined".to_string()));
+
+    // ループ内の catch 節から break / continue で抜けてもスコープを戻す
+    let result = engine
+        .eval(
+            r#"
+        let seen = "";
+        for (let i = 0; i < 3; i = i + 1) {
+            try { throw i; } catch (x) { if (x == 1) continue; if (x == 2) break; seen = seen + x; }
+        }
+        seen + typeof x
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("0undefined".to_string()));
+
+    // catch 節の var は外側のスコープに束縛される
+    let result = engine
+        .eval("try { throw 5; } catch (e) { var v = e; } v")
+        .unwrap();
+    assert_eq!(result, JSValue::Number(5.0));
+}
+
+#[test]
+fn test_uncaught_throw_from_catch_leaves_scope() {
+    let mut engine = JSEngine::new();
+    assert!(
+        engine
+            .eval("try { throw 1; } catch (e) { throw 2; }")
+            .is_err()
+    );
+    assert_eq!(
+        engine.eval("typeof e").unwrap(),
+        JSValue::String("undefined".to_string())
+    );
+
+    // 後の宣言はグローバル環境に作られ、snapshot / restore の対象になる
+    let snapshot = engine.snapshot();
+    engine.eval("var v = 1; let w = 2;").unwrap();
+    engine.restore(snapshot);
+    assert_eq!(
+        engine.eval("typeof v + typeof w").unwrap(),
+        JSValue::String("undefinedundefined".to_string())
+    );
+}
+
+#[test]
+fn test_try_without_throw() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("try { 1 } catch { 2 }").unwrap();
+    assert_eq!(result, JSValue::Number(1.0));
+}
+
+#[test]
+fn test_throw_from_function_is_caught() {
+    let mut engine = JSEngine::new();
+    let result = engine
+        .eval(
+            r#"
+        function fail() { throw "boom"; }
+        try { fail(); 1 } catch (e) { e }
+    "#,
+        )
+        .unwrap();
+    assert_eq!(result, JSValue::String("boom".to_string()));
+}
+
+#[test]
+fn test_uncaught_throw() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("throw 7");
+    assert!(matches!(result, Err(JSError::Exception(JSValue::Number(n))) if n == 7.0));
+}
+
+#[test]
+fn test_try_without_catch_is_syntax_error() {
+    let mut engine = JSEngine::new();
+    let result = engine.eval("try { 1 }");
+    assert!(matches!(result, Err(JSError::SyntaxError(_))));
+}
@@ -1,4 +1,4 @@
-﻿use pixi_byte::JSValue;
+use pixi_byte::JSValue;
 #[test]
 fn test_jsvalue_to_string() {
     assert_eq!(JSValue::Undefined.to_string(), "undefined");
@@ -27,3 +27,184 @@ fn test_jsvalue_abstract_equals() {
     assert!(JSValue::Null.abstract_equals(&JSValue::Undefined));
     assert!(JSValue::Number(42.0).abstract_equals(&JSValue::String("42".to_string())));
 }
+#[test]
+fn test_number_to_string() {
+    let cases = [
+        (1.0, "1"),
+        (-0.0, "0"),
+        (123.456, "123.456"),
+        (-1.5, "-1.5"),
+        (0.000001, "0.000001"),
+        (1e-7, "1e-7"),
+        (1e21, "1e+21"),
+        (1.5e300, "1.5e+300"),
+        (123456789012345680000.0, "123456789012345680000"),
+        (f64::INFINITY, "Infinity"),
+    ];
+    for (n, expected) in cases {
+        assert_eq!(JSValue::Number(n).to_string(), expected);
+    }
+}
+#[test]
+fn test_to_property_key() {
+    assert_eq!(JSValue::Number(1.0).to_property_key(), "1");
+    assert_eq!(JSValue::Number(-0.0).to_property_key(), "0");
+    assert_eq!(JSValue::String("1".to_string()).to_property_key(), "1");
+}
+#[test]
+fn test_deep_equals_nested_arrays() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let a = engine.eval("[1, [2, 3]]").unwrap();
+    let b = engine.eval("[1, [2, 3]]").unwrap();
+
+    assert!(a.deep_equals(&b));
+    assert!(!a.strict_equals(&b));
+
+    let c = engine.eval("[1, [2, 4]]").unwrap();
+    assert!(!a.deep_equals(&c));
+}
+#[test]
+fn test_deep_equals_objects() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let a = engine.eval("({ x: 1, y: { z: 'a' } })").unwrap();
+    let b = engine.eval("({ y: { z: 'a' }, x: 1 })").unwrap();
+    let c = engine.eval("({ x: 1 })").unwrap();
+
+    assert!(a.deep_equals(&b));
+    assert!(!a.deep_equals(&c));
+    assert!(!c.deep_equals(&a));
+    assert!(JSValue::Number(f64::NAN).deep_equals(&JSValue::Number(f64::NAN)));
+}
+#[test]
+fn test_deep_equals_cyclic_objects() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let a = engine.eval("let a = {}; a.self = a; a").unwrap();
+    let b = engine.eval("let b = {}; b.self = b; b").unwrap();
+
+    assert!(a.deep_equals(&b));
+}
+#[test]
+fn test_structured_clone_is_independent() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let original = engine.eval("let o = { a: [1, 2] }; o").unwrap();
+    let cloned = original.structured_clone().unwrap();
+
+    assert!(cloned.deep_equals(&original));
+    assert!(!cloned.strict_equals(&original));
+
+    // 複製側を変更しても元の値は変わらない
+    if let JSValue::Object(obj) = &cloned
+        && let JSValue::Object(arr) = obj.borrow().get("a")
+    {
+        arr.borrow_mut()
+            .set("0".to_string(), JSValue::Number(100.0));
+        arr.borrow_mut().set("2".to_string(), JSValue::Number(3.0));
+        assert_eq!(arr.borrow().get("length"), JSValue::Number(3.0));
+    } else {
+        panic!("Expected cloned object with array");
+    }
+    assert_eq!(engine.eval("o.a[0]").unwrap(), JSValue::Number(1.0));
+    assert_eq!(engine.eval("o.a.length").unwrap(), JSValue::Number(2.0));
+}
+#[test]
+fn test_structured_clone_preserves_shared_and_cyclic_references() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let original = engine
+        .eval("let s = { v: 1 }; let g = { l: s, r: s }; g.self = g; g")
+        .unwrap();
+    let JSValue::Object(cloned) = original.structured_clone().unwrap() else {
+        panic!("Expected object");
+    };
+
+    let cloned = cloned.borrow();
+    // ダイヤモンド構造は共有されたまま
+    assert!(cloned.get("l").strict_equals(&cloned.get("r")));
+    assert!(!cloned.get("l").strict_equals(&engine.eval("s").unwrap()));
+    // 循環参照は複製自身を指す
+    let JSValue::Object(self_ref) = cloned.get("self") else {
+        panic!("Expected object");
+    };
+    assert!(std::ptr::eq(self_ref.as_ptr(), &*cloned));
+}
+#[test]
+fn test_structured_clone_rejects_functions() {
+    let mut engine = pixi_byte::JSEngine::new();
+    let value = engine.eval("({ f() { return 1; } })").unwrap();
+    assert!(matches!(
+        value.structured_clone(),
+        Err(pixi_byte::JSError::TypeError(_))
+    ));
+}
+#[test]
+fn test_typed_accessors_do_not_coerce() {
+    assert_eq!(JSValue::Number(3.0).as_number(), Some(3.0));
+    assert_eq!(JSValue::Number(3.0).as_str(), None);
+    assert_eq!(JSValue::String("3".to_string()).as_str(), Some("3"));
+    assert_eq!(JSValue::String("3".to_string()).as_number(), None);
+    assert_eq!(JSValue::Boolean(false).as_bool(), Some(false));
+    assert_eq!(JSValue::Number(0.0).as_bool(), None);
+    assert!(JSValue::Null.as_object().is_none());
+
+    let mut engine = pixi_byte::JSEngine::new();
+    let value = engine.eval("({ name: 'x' })").unwrap();
+    let obj = value.as_object().unwrap();
+    assert_eq!(obj.borrow().get("name").as_str(), Some("x"));
+}
+#[test]
+fn test_array_length() {
+    let mut engine = pixi_byte::JSEngine::new();
+    assert_eq!(engine.eval("[1, 2, 3]").unwrap().array_length(), Some(3));
+    assert_eq!(
+        engine
+            .eval("let a = []; a[4] = 1; a")
+            .unwrap()
+            .array_length(),
+        Some(5)
+    );
+    assert_eq!(engine.eval("({ length: 2 })").unwrap().array_length(), None);
+    assert_eq!(JSValue::String("abc".to_string()).array_length(), None);
+}
+#[test]
+fn test_partial_eq_follows_strict_equality() {
+    let nan = JSValue::Number(f64::NAN);
+    // NaN は自身とも等しくない（== も contains も一致しない）
+    assert_ne!(nan, nan.clone());
+    assert!(![nan.clone()].contains(&nan));
+    assert!(nan.same_value(&nan));
+    assert!(nan.same_value_zero(&nan));
+    assert!(nan.deep_equals(&nan));
+    // +0 と -0 は == では等しく、same_value でのみ区別する
+    let zero = JSValue::Number(0.0);
+    let neg_zero = JSValue::Number(-0.0);
+    assert_eq!(zero, neg_zero);
+    assert!(!zero.same_value(&neg_zero));
+    assert!(zero.same_value_zero(&neg_zero));
+    // 型が異なれば変換しない
+    assert_ne!(JSValue::Number(1.0), JSValue::String("1".to_string()));
+    assert!(JSValue::Number(1.0).abstract_equals(&JSValue::String("1".to_string())));
+    assert!(JSValue::Boolean(true).abstract_equals(&JSValue::String("1".to_string())));
+    assert!(!JSValue::Boolean(false).abstract_equals(&JSValue::Null));
+    assert!(!nan.abstract_equals(&JSValue::String("NaN".to_string())));
+}
+#[test]
+fn test_to_int32_and_to_uint32_wrap_modulo_2_32() {
+    let cases = [
+        (f64::NAN, 0, 0),
+        (f64::INFINITY, 0, 0),
+        (f64::NEG_INFINITY, 0, 0),
+        (-0.0, 0, 0),
+        (1.9, 1, 1),
+        (-1.9, -1, 4_294_967_295),
+        (4_294_967_296.0, 0, 0),
+        (4_294_967_297.0, 1, 1),
+        (2_147_483_648.0, -2_147_483_648, 2_147_483_648),
+        (-2_147_483_649.0, 2_147_483_647, 2_147_483_647),
+        (1e21, -559_939_584, 3_735_027_712),
+    ];
+    for (n, int32, uint32) in cases {
+        assert_eq!(JSValue::Number(n).to_int32(), int32, "{}", n);
+        assert_eq!(JSValue::Number(n).to_uint32(), uint32, "{}", n);
+    }
+    assert_eq!(JSValue::String(" -12 ".to_string()).to_int32(), -12);
+    assert_eq!(JSValue::Undefined.to_uint32(), 0);
+}
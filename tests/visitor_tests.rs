@@ -0,0 +1,58 @@
+use pixi_byte::parser::{
+    Expression, ExpressionKind, Statement, Visitor, walk_expression, walk_statement,
+};
+use pixi_byte::{Lexer, Parser};
+
+/// 二項演算ノードを数えるビジター
+struct BinaryCounter {
+    count: usize,
+}
+
+impl Visitor for BinaryCounter {
+    fn visit_expression(&mut self, expression: &Expression) {
+        if let ExpressionKind::Binary { .. } = expression.kind {
+            self.count += 1;
+        }
+        walk_expression(self, expression);
+    }
+}
+
+#[test]
+fn test_visitor_counts_nested_binary() {
+    let source = r#"
+        let x = (1 + 2) * f(3 - 4, [5 + 6]);
+        function g(a) { return { v: a / 2 }; }
+    "#;
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let mut counter = BinaryCounter { count: 0 };
+    counter.visit_program(&program);
+
+    // (1 + 2), (... * ...), (3 - 4), (5 + 6), (a / 2)
+    assert_eq!(counter.count, 5);
+}
+
+#[test]
+fn test_visitor_defaults_walk_into_nested_nodes() {
+    /// 文だけを数え、式の走査はデフォルト実装に任せるビジター
+    struct StatementCounter {
+        count: usize,
+    }
+    impl Visitor for StatementCounter {
+        fn visit_statement(&mut self, statement: &Statement) {
+            self.count += 1;
+            walk_statement(self, statement);
+        }
+    }
+
+    let source = "let f = function() { return 1 + 2; }; if (x) { y; }";
+    let tokens = Lexer::new(source).tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+
+    let mut counter = StatementCounter { count: 0 };
+    counter.visit_program(&program);
+
+    // let 文、関数式の中の return 文（式のデフォルト走査で到達）、if 文、y;
+    assert_eq!(counter.count, 4);
+}
@@ -30,3 +30,143 @@ fn test_vm_execute_variable() {
     let result = engine.eval("let x = 10; x + 5").unwrap();
     assert_eq!(result, JSValue::Number(15.0));
 }
+#[test]
+fn test_vm_trace_hook() {
+    use pixi_byte::vm::VM;
+    use pixi_byte::{Compiler, Lexer, Opcode, Parser};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let tokens = Lexer::new("1 + 2").tokenize().unwrap();
+    let program = Parser::new(tokens).parse().unwrap();
+    let chunk = Compiler::new().compile(program).unwrap();
+
+    let trace = Rc::new(RefCell::new(Vec::new()));
+    let sink = trace.clone();
+    let mut vm = VM::new();
+    vm.set_trace(Some(Box::new(move |pc, opcode: &Opcode, depth| {
+        sink.borrow_mut().push((pc, opcode.clone(), depth));
+    })));
+    assert_eq!(vm.execute(&chunk).unwrap(), JSValue::Number(3.0));
+
+    let trace = trace.borrow();
+    let opcodes: Vec<_> = trace.iter().map(|(_, op, _)| op.clone()).collect();
+    assert!(matches!(
+        opcodes[..],
+        [Opcode::LoadConst(_), Opcode::LoadConst(_), Opcode::Add]
+    ));
+    let positions: Vec<_> = trace.iter().map(|(pc, _, depth)| (*pc, *depth)).collect();
+    assert_eq!(positions, [(0, 0), (1, 1), (2, 2)]);
+
+    // 無効化後は呼ばれない
+    vm.set_trace(None);
+    vm.execute(&chunk).unwrap();
+    assert_eq!(trace.len(), 3);
+}
+#[test]
+fn test_vm_discarded_assignments_leave_no_copies() {
+    use pixi_byte::vm::VM;
+    use pixi_byte::{Compiler, Lexer, Opcode, Parser};
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let compile = |source: &str| {
+        let tokens = Lexer::new(source).tokenize().unwrap();
+        Compiler::new()
+            .compile(Parser::new(tokens).parse().unwrap())
+            .unwrap()
+    };
+    let mut vm = VM::new();
+    vm.execute(&compile("var o = {}; var y;")).unwrap();
+
+    let trace = Rc::new(RefCell::new(Vec::new()));
+    let sink = trace.clone();
+    vm.set_trace(Some(Box::new(move |_, opcode: &Opcode, depth| {
+        sink.borrow_mut().push((opcode.clone(), depth));
+    })));
+    let chunk = compile(r#"o.x = 1; o["y"] = 2; y = 3; o.x + o.y + y"#);
+    assert_eq!(vm.execute(&chunk).unwrap(), JSValue::Number(6.0));
+
+    // 値を使わない代入は値を積まない命令になり、捨てるための Pop も生成しない
+    assert!(!chunk.code.contains(&Opcode::Pop));
+    let trace = trace.borrow();
+    assert!(
+        !trace
+            .iter()
+            .any(|(op, _)| matches!(op, Opcode::Pop | Opcode::Dup))
+    );
+    let stores = trace
+        .iter()
+        .filter(|(op, _)| {
+            matches!(
+                op,
+                Opcode::StoreProperty | Opcode::StorePropertyByName(_) | Opcode::StoreVar(_)
+            )
+        })
+        .count();
+    assert_eq!(stores, 3);
+    // 代入式の値を使う場合は従来どおり代入した値になる
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine
+            .eval("let a = {}; let b = (a.x = 5); let c; c = a[\"z\"] = b + 1; a.x + c")
+            .unwrap(),
+        JSValue::Number(11.0)
+    );
+    assert_eq!(engine.eval("a.x = 7").unwrap(), JSValue::Number(7.0));
+    assert_eq!(engine.eval("c = 8").unwrap(), JSValue::Number(8.0));
+}
+#[test]
+fn test_vm_corrupt_constant_index_is_error() {
+    use pixi_byte::compiler::BytecodeChunk;
+    use pixi_byte::vm::VM;
+    use pixi_byte::{JSError, Opcode};
+
+    let mut chunk = BytecodeChunk::new();
+    chunk.emit(Opcode::LoadConst(7));
+
+    let mut vm = VM::new();
+    assert!(matches!(vm.execute(&chunk), Err(JSError::InternalError(_))));
+
+    let mut chunk = BytecodeChunk::new();
+    chunk.emit(Opcode::CreateFunction(0));
+    assert!(matches!(vm.execute(&chunk), Err(JSError::InternalError(_))));
+}
+#[test]
+fn test_vm_dup_and_jump_if_true() {
+    use pixi_byte::Opcode;
+    use pixi_byte::compiler::BytecodeChunk;
+    use pixi_byte::vm::VM;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    let mut chunk = BytecodeChunk::new();
+    let one = chunk.add_constant(JSValue::Number(1.0));
+    let skipped = chunk.add_constant(JSValue::Number(99.0));
+    for opcode in [
+        Opcode::LoadConst(one),
+        Opcode::Dup,
+        Opcode::JumpIfTrue(5), // 1 は truthy なのでジャンプ
+        Opcode::LoadConst(skipped),
+        Opcode::Add,
+        Opcode::LoadBool(false),
+        Opcode::JumpIfTrue(9), // false なのでジャンプしない
+        Opcode::Dup,
+        Opcode::Add,
+    ] {
+        chunk.emit(opcode);
+    }
+
+    let trace = Rc::new(RefCell::new(Vec::new()));
+    let sink = trace.clone();
+    let mut vm = VM::new();
+    vm.set_trace(Some(Box::new(move |pc, _: &Opcode, depth| {
+        sink.borrow_mut().push((pc, depth));
+    })));
+    assert_eq!(vm.execute(&chunk).unwrap(), JSValue::Number(2.0));
+    // Dup は値を1つ積み、JumpIfTrue はジャンプの有無によらず条件を取り除く
+    assert_eq!(
+        *trace.borrow(),
+        [(0, 0), (1, 1), (2, 2), (5, 1), (6, 2), (7, 1), (8, 2)]
+    );
+}
@@ -0,0 +1,163 @@
+use pixi_byte::{JSEngine, JSError, JSValue};
+
+#[test]
+fn test_wrapper_typeof_is_object() {
+    let mut engine = JSEngine::new();
+    for source in ["new Number(5)", r#"new String("a")"#, "new Boolean(false)"] {
+        assert_eq!(
+            engine.eval(&format!("typeof {}", source)).unwrap(),
+            JSValue::String("object".to_string()),
+            "{}",
+            source
+        );
+    }
+    // new なしの呼び出しは型変換
+    assert_eq!(
+        engine.eval(r#"typeof Number("5")"#).unwrap(),
+        JSValue::String("number".to_string())
+    );
+    assert_eq!(engine.eval(r#"Number("5")"#).unwrap(), JSValue::Number(5.0));
+    assert_eq!(
+        engine.eval("String(12)").unwrap(),
+        JSValue::String("12".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#"Boolean("")"#).unwrap(),
+        JSValue::Boolean(false)
+    );
+}
+
+#[test]
+fn test_wrapper_unboxes_via_value_of() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("new Number(5) + 1").unwrap(),
+        JSValue::Number(6.0)
+    );
+    assert_eq!(
+        engine.eval("new Number(5) * 2").unwrap(),
+        JSValue::Number(10.0)
+    );
+    assert_eq!(
+        engine.eval(r#"new String("ab") + "c""#).unwrap(),
+        JSValue::String("abc".to_string())
+    );
+    assert_eq!(
+        engine.eval("new Number(5) == 5").unwrap(),
+        JSValue::Boolean(true)
+    );
+    assert_eq!(
+        engine.eval("new Number(5) === 5").unwrap(),
+        JSValue::Boolean(false)
+    );
+    assert_eq!(
+        engine.eval("new Number(1) < 2").unwrap(),
+        JSValue::Boolean(true)
+    );
+    // ラッパーオブジェクト自体は常に truthy
+    assert_eq!(
+        engine.eval("new Boolean(false) ? 1 : 2").unwrap(),
+        JSValue::Number(1.0)
+    );
+}
+
+#[test]
+fn test_user_defined_value_of_and_to_string() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("({ valueOf() { return 41; } }) + 1").unwrap(),
+        JSValue::Number(42.0)
+    );
+    assert_eq!(
+        engine
+            .eval(r#"String({ toString() { return "custom"; } })"#)
+            .unwrap(),
+        JSValue::String("custom".to_string())
+    );
+    // valueOf がオブジェクトを返すと継承した toString を使う
+    assert_eq!(
+        engine.eval("({ valueOf() { return {}; } }) + 1").unwrap(),
+        JSValue::String("[object Object]1".to_string())
+    );
+    assert!(matches!(
+        engine.eval("({ valueOf() { return {}; }, toString() { return {}; } }) + 1"),
+        Err(JSError::TypeError(_))
+    ));
+}
+
+#[test]
+fn test_primitive_methods_and_wrapper_properties() {
+    let mut engine = JSEngine::new();
+    assert_eq!(
+        engine.eval("(5).toString()").unwrap(),
+        JSValue::String("5".to_string())
+    );
+    assert_eq!(
+        engine.eval("true.toString()").unwrap(),
+        JSValue::String("true".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#"new String("héllo").length"#).unwrap(),
+        JSValue::Number(5.0)
+    );
+    assert_eq!(
+        engine.eval(r#"new String("abc")[1]"#).unwrap(),
+        JSValue::String("b".to_string())
+    );
+    assert_eq!(
+        engine.eval(r#"new String(" x ").trim()"#).unwrap(),
+        JSValue::String("x".to_string())
+    );
+    assert_eq!(
+        engine.eval("new Number(-0)").unwrap().inspect(),
+        "[Number: -0]"
+    );
+    assert_eq!(
+        engine.eval(r#"new String("a")"#).unwrap().inspect(),
+        "[String: 'a']"
+    );
+}
+
+#[test]
+fn test_number_to_string_radix() {
+    let mut engine = JSEngine::new();
+    let cases = [
+        ("(255).toString(2)", "11111111"),
+        ("(255).toString(16)", "ff"),
+        ("(35).toString(36)", "z"),
+        ("(-255).toString(8)", "-377"),
+        ("(10.5).toString(2)", "1010.1"),
+        ("(0.1).toString(3)", "0.0022002200220022002200220022002201"),
+        ("(123.456).toString(36)", "3f.gez4w97ry"),
+        (
+            "(0.3).toString(2)",
+            "0.010011001100110011001100110011001100110011001100110011",
+        ),
+        ("(0.5).toString(16)", "0.8"),
+        ("(1e21).toString(36)", "5v1j4f4ds7c000"),
+        (
+            "(2 ** 60).toString(2)",
+            "1000000000000000000000000000000000000000000000000000000000000",
+        ),
+        ("(255).toString()", "255"),
+        ("(255).toString(undefined)", "255"),
+        ("(0 / 0).toString(2)", "NaN"),
+        ("(-1 / 0).toString(16)", "-Infinity"),
+        ("new Number(6).toString('2')", "110"),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(
+            engine.eval(source).unwrap(),
+            JSValue::String(expected.to_string()),
+            "{}",
+            source
+        );
+    }
+    for source in ["(1).toString(1)", "(1).toString(37)", "(1).toString(0 / 0)"] {
+        assert!(
+            matches!(engine.eval(source), Err(JSError::RangeError(_))),
+            "{}",
+            source
+        );
+    }
+}